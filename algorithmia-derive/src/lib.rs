@@ -0,0 +1,217 @@
+//! Derive macros for the [`algorithmia`](https://docs.rs/algorithmia) crate
+//!
+//! Enable the `derive` feature of `algorithmia` instead of depending on this crate directly.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Fields, FnArg, GenericArgument, ItemFn,
+    PathArguments, ReturnType, Type,
+};
+
+/// Derive conversion from `AlgoIo` for an enum of alternative input types
+///
+/// See the `algorithmia::algo::AlgoInputFrom` documentation for details.
+#[proc_macro_derive(AlgoInputFrom)]
+pub fn derive_algo_input_from(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Mark the function that handles algorithm requests
+///
+/// See the `algorithmia::handler::entrypoint` documentation for details.
+#[proc_macro_attribute]
+pub fn entrypoint(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        let err = Error::new(
+            proc_macro2::TokenStream::from(attr).span(),
+            "#[entrypoint] does not accept arguments",
+        );
+        return err.to_compile_error().into();
+    }
+    let item = parse_macro_input!(item as ItemFn);
+    match expand_entrypoint(&item) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => {
+            // Keep the function and a stub `main` so only the targeted error is reported
+            let err = err.to_compile_error();
+            quote!(#err #item fn main() {}).into()
+        }
+    }
+}
+
+fn expand_entrypoint(item: &ItemFn) -> Result<proc_macro2::TokenStream, Error> {
+    let sig = &item.sig;
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(Error::new(
+            asyncness.span(),
+            "#[entrypoint] functions cannot be async",
+        ));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(Error::new(
+            sig.generics.span(),
+            "#[entrypoint] functions cannot be generic",
+        ));
+    }
+
+    // Input: exactly one owned argument
+    let input_ty = match sig.inputs.iter().collect::<Vec<_>>().as_slice() {
+        [FnArg::Typed(arg)] => &*arg.ty,
+        [FnArg::Receiver(receiver)] => {
+            return Err(Error::new(
+                receiver.span(),
+                "#[entrypoint] must be a free function, not a method",
+            ))
+        }
+        _ => {
+            return Err(Error::new(
+                sig.inputs.span(),
+                "#[entrypoint] functions must take exactly one input argument, e.g. `fn apply(input: String)`",
+            ))
+        }
+    };
+    if let Type::Reference(reference) = input_ty {
+        return Err(Error::new(
+            reference.span(),
+            "#[entrypoint] input must be an owned type (e.g. `String` instead of `&str`)",
+        ));
+    }
+
+    // Output: Result<OUT, E>
+    let (ok_ty, err_ty) = match &sig.output {
+        ReturnType::Type(_, ty) => result_types(ty).ok_or_else(|| {
+            Error::new(
+                ty.span(),
+                "#[entrypoint] functions must return `Result<T, E>`",
+            )
+        })?,
+        ReturnType::Default => {
+            return Err(Error::new(
+                sig.span(),
+                "#[entrypoint] functions must return `Result<T, E>`",
+            ))
+        }
+    };
+
+    // Trait bounds are asserted with the span of each type so that
+    // unsupported types are reported where they are written
+    let assert_input = quote_spanned! {input_ty.span()=>
+        fn assert_input<T>()
+        where
+            T: ::algorithmia::algo::TryFrom<::algorithmia::algo::AlgoIo>,
+            <T as ::algorithmia::algo::TryFrom<::algorithmia::algo::AlgoIo>>::Error:
+                ::std::convert::Into<::std::boxed::Box<dyn ::std::error::Error>>,
+        {
+        }
+        assert_input::<#input_ty>();
+    };
+    let assert_output = quote_spanned! {ok_ty.span()=>
+        fn assert_output<T: ::std::convert::Into<::algorithmia::algo::AlgoIo>>() {}
+        assert_output::<#ok_ty>();
+    };
+    let assert_error = quote_spanned! {err_ty.span()=>
+        fn assert_error<E: ::std::convert::Into<::std::boxed::Box<dyn ::std::error::Error>>>() {}
+        assert_error::<#err_ty>();
+    };
+
+    let name = &sig.ident;
+    Ok(quote! {
+        #item
+
+        fn main() {
+            #assert_input
+            #assert_output
+            #assert_error
+            ::algorithmia::handler::run(#name)
+        }
+    })
+}
+
+/// Extract `T` and `E` from a `Result<T, E>` type
+fn result_types(ty: &Type) -> Option<(&Type, &Type)> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let args = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => &args.args,
+        _ => return None,
+    };
+    let mut types = args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+    match (types.next(), types.next(), types.next()) {
+        (Some(ok), Some(err), None) => Some((ok, err)),
+        _ => None,
+    }
+}
+
+fn expand(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let name = &input.ident;
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "AlgoInputFrom can only be derived for enums",
+            ))
+        }
+    };
+    if data.variants.is_empty() {
+        return Err(Error::new(
+            Span::call_site(),
+            "AlgoInputFrom requires at least one variant",
+        ));
+    }
+
+    let mut attempts = Vec::new();
+    for variant in &data.variants {
+        let ty = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => return Err(Error::new(
+                variant.span(),
+                "AlgoInputFrom variants must have exactly one unnamed field, e.g. `Url(String)`",
+            )),
+        };
+        let ident = &variant.ident;
+        let label = ident.to_string();
+        attempts.push(quote! {
+            match <#ty as ::algorithmia::algo::TryFrom<::algorithmia::algo::AlgoIo>>::try_from(input.clone()) {
+                Ok(value) => return Ok(#name::#ident(value)),
+                Err(err) => errors.push(format!("{}: {}", #label, err)),
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let name_str = name.to_string();
+    Ok(quote! {
+        impl #impl_generics ::algorithmia::algo::TryFrom<::algorithmia::algo::AlgoIo> for #name #ty_generics #where_clause {
+            type Error = ::algorithmia::error::Error;
+
+            fn try_from(input: ::algorithmia::algo::AlgoIo) -> ::std::result::Result<Self, Self::Error> {
+                let mut errors: Vec<String> = Vec::new();
+                #(#attempts)*
+                Err(::algorithmia::error::Error::from(format!(
+                    "input did not match any variant of {} ({})",
+                    #name_str,
+                    errors.join("; ")
+                )))
+            }
+        }
+    })
+}
@@ -0,0 +1,58 @@
+//! Benchmarks decoding algorithm responses, the hot path of `Algorithm::pipe`
+//!
+//! Run with `cargo bench --bench response_parsing`.
+
+use algorithmia::algo::AlgoResponse;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde_json::json;
+
+fn response(content_type: &str, result: serde_json::Value) -> String {
+    json!({
+        "metadata": { "content_type": content_type, "duration": 0.012 },
+        "result": result,
+    })
+    .to_string()
+}
+
+fn responses() -> Vec<(&'static str, String)> {
+    let large_json: Vec<_> = (0..10_000)
+        .map(|i| json!({ "id": i, "score": i as f64 / 3.0, "label": format!("item-{}", i) }))
+        .collect();
+    vec![
+        ("json_small", response("json", json!([1, 2, 3]))),
+        ("json_large", response("json", json!(large_json))),
+        ("text_small", response("text", json!("Hello, world!"))),
+        (
+            "text_large",
+            response("text", json!("lorem ipsum ".repeat(50_000))),
+        ),
+        (
+            "binary_large",
+            response("binary", json!(base64::encode(&vec![7u8; 256 * 1024]))),
+        ),
+    ]
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_algo_response");
+    for (name, body) in responses() {
+        group.throughput(Throughput::Bytes(body.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &body, |b, body| {
+            b.iter(|| black_box(body.parse::<AlgoResponse>().unwrap()))
+        });
+    }
+    group.finish();
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let body = response("json", json!({ "values": (0..1000).collect::<Vec<_>>() }));
+    c.bench_function("decode_json_result", |b| {
+        b.iter(|| {
+            let res = body.parse::<AlgoResponse>().unwrap();
+            black_box(res.decode::<serde_json::Value>().unwrap())
+        })
+    });
+}
+
+criterion_group!(benches, bench_parse, bench_decode);
+criterion_main!(benches);
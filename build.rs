@@ -9,7 +9,7 @@ fn main() {
     write!(
         f,
         r#"
-pub static RUSTC_VERSION: &'static str = "{rustc_version}";
+pub static RUSTC_VERSION: &str = "{rustc_version}";
 "#,
         rustc_version = rustc_version::version().unwrap(),
     )
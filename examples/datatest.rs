@@ -14,7 +14,7 @@ fn print_cause_chain(err: &dyn Error) {
     }
 }
 
-fn main() -> Result<(), Box<Error>> {
+fn main() -> Result<(), Box<dyn Error>> {
     let mut args = env::args();
     args.next(); // discard args[0]
     let path = match args.next() {
@@ -30,12 +30,12 @@ fn main() -> Result<(), Box<Error>> {
     };
 
     let client = Algorithmia::client(&*api_key)?;
-    match &client.dir(&*path).create(ReadAcl::Private) {
+    match &client.dir(&path).create(ReadAcl::Private) {
         Ok(_) => println!("Successfully created collection {}", path),
         Err(e) => print_cause_chain(e),
     }
 
-    match &client.dir(&*path).delete(true) {
+    match &client.dir(&path).delete(true) {
         Ok(_) => println!("Successfully deleted collection {}", path),
         Err(e) => print_cause_chain(e),
     }
@@ -33,7 +33,7 @@ impl<'a> RouteMap<'a> {
         self,
         start: &'a str,
         end: &'a str,
-    ) -> Result<AlgoResponse, Box<Error>> {
+    ) -> Result<AlgoResponse, Box<dyn Error>> {
         let api_key = match env::var("ALGORITHMIA_API_KEY") {
             Ok(key) => key,
             Err(e) => {
@@ -57,7 +57,7 @@ impl<'a> RouteMap<'a> {
     }
 }
 
-fn main() -> Result<(), Box<Error>> {
+fn main() -> Result<(), Box<dyn Error>> {
     let mut args = env::args();
     args.next(); // discard args[0]
     let start = args.next().unwrap_or_else(|| "a".to_string());
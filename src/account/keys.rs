@@ -0,0 +1,156 @@
+use crate::client::HttpClient;
+use crate::error::{process_http_response, Error, ResultExt};
+use crate::Paginated;
+
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Permission granted to an API key
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyScope {
+    /// May call algorithms
+    AlgoCall,
+    /// May read hosted data
+    DataRead,
+    /// May write hosted data
+    DataWrite,
+    /// Scope not recognized by this client
+    #[serde(other)]
+    Unknown,
+}
+
+/// Request for creating a scoped API key
+///
+/// # Examples
+///
+/// ```
+/// use algorithmia::account::{CreateKeyRequest, KeyScope};
+///
+/// // A key that can only read data under a single collection
+/// let req = CreateKeyRequest {
+///     scopes: vec![KeyScope::DataRead],
+///     path_prefixes: vec!["data://ml-team/models".into()],
+///     ..CreateKeyRequest::new("model-reader")
+/// };
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateKeyRequest {
+    /// Name used to identify the key
+    pub name: String,
+    /// Permissions granted to the key (all permissions if empty)
+    pub scopes: Vec<KeyScope>,
+    /// Data URI prefixes the key is restricted to (unrestricted if empty)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub path_prefixes: Vec<String>,
+}
+
+impl CreateKeyRequest {
+    /// Create a request for an unrestricted API key
+    pub fn new<S: Into<String>>(name: S) -> CreateKeyRequest {
+        CreateKeyRequest {
+            name: name.into(),
+            scopes: Vec::new(),
+            path_prefixes: Vec::new(),
+        }
+    }
+}
+
+/// Description of an existing API key (never includes the secret)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ApiKeyInfo {
+    /// Unique identifier of the key, used for revocation
+    pub id: String,
+    /// Name used to identify the key
+    pub name: String,
+    /// Permissions granted to the key
+    #[serde(default)]
+    pub scopes: Vec<KeyScope>,
+    /// Data URI prefixes the key is restricted to
+    #[serde(default)]
+    pub path_prefixes: Vec<String>,
+    /// Time the key was created
+    pub created_at: Option<DateTime<Utc>>,
+    // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
+    _dummy: (),
+}
+
+/// Newly created API key, including its secret
+///
+/// The secret is only available at creation time, so store it somewhere safe.
+#[derive(Clone, Deserialize)]
+pub struct CreatedApiKey {
+    /// Description of the created key
+    #[serde(flatten)]
+    pub info: ApiKeyInfo,
+    /// Secret API key used to authenticate requests
+    #[serde(rename = "key")]
+    pub secret: String,
+}
+
+/// Avoid leaking the secret into logs
+impl fmt::Debug for CreatedApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CreatedApiKey")
+            .field("info", &self.info)
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+pub(crate) fn list_keys(
+    client: &HttpClient,
+    url: Result<Url, Error>,
+    owner: &str,
+) -> Paginated<ApiKeyInfo> {
+    Paginated::new(client.clone(), url, format!("API keys of '{}'", owner))
+}
+
+pub(crate) fn create_key(
+    client: &HttpClient,
+    url: Url,
+    request: &CreateKeyRequest,
+) -> Result<CreatedApiKey, Error> {
+    let mut res = client
+        .send(client.post(url).json(request))
+        .with_context(|| format!("request error creating API key '{}'", request.name))
+        .and_then(process_http_response)
+        .with_context(|| format!("response error creating API key '{}'", request.name))?;
+
+    res.json()
+        .with_context(|| format!("JSON decoding error creating API key '{}'", request.name))
+}
+
+pub(crate) fn revoke_key(client: &HttpClient, url: Url, key_id: &str) -> Result<(), Error> {
+    client
+        .send(client.delete(url))
+        .with_context(|| format!("request error revoking API key '{}'", key_id))
+        .and_then(process_http_response)
+        .with_context(|| format!("response error revoking API key '{}'", key_id))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_created_key_decoding() {
+        let json = r#"{
+            "id": "k-123",
+            "name": "model-reader",
+            "scopes": ["data_read", "something_new"],
+            "key": "simSecret"
+        }"#;
+        let created: CreatedApiKey = serde_json::from_str(json).unwrap();
+        assert_eq!(created.secret, "simSecret");
+        assert_eq!(
+            created.info.scopes,
+            vec![KeyScope::DataRead, KeyScope::Unknown]
+        );
+        assert!(!format!("{:?}", created).contains("simSecret"));
+    }
+}
@@ -0,0 +1,95 @@
+//! API client for Algorithmia users and their resources
+//!
+//! Instantiate from the [`Algorithmia`](../struct.Algorithmia.html) struct
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use algorithmia::Algorithmia;
+//!
+//! let client = Algorithmia::client("111112222233333444445555566")?;
+//! for algo in client.user("anowell").algorithms() {
+//!     println!("anowell/{}", algo?.name);
+//! }
+//! # Ok::<(), Box<std::error::Error>>(())
+//! ```
+
+pub use self::keys::{ApiKeyInfo, CreateKeyRequest, CreatedApiKey, KeyScope};
+pub use self::org::*;
+pub use self::user::*;
+
+use crate::algo::{AlgoSummary, AlgoUri, Algorithm, CreateAlgoBody, CreateAlgoRequest, OwnerKind};
+use crate::client::{push_segment, HttpClient};
+use crate::error::{process_http_response, Error, ResultExt};
+use crate::Paginated;
+
+use reqwest::Url;
+
+mod keys;
+mod org;
+mod user;
+
+static ALGORITHMS_BASE_PATH: &str = "v1/algorithms";
+
+/// URL of `base_path/<owner>`, escaping the owner as a single path segment
+fn owner_url(client: &HttpClient, base_path: &str, owner: &str) -> Result<Url, Error> {
+    let url = client
+        .base_url
+        .join(base_path)
+        .with_context(|| format!("invalid URL for '{}'", base_path))?;
+    push_segment(url, owner)
+}
+
+/// URL for the algorithms owned by a user or organization
+fn algorithms_url(client: &HttpClient, owner: &str) -> Result<Url, Error> {
+    owner_url(client, ALGORITHMS_BASE_PATH, owner)
+}
+
+/// List the algorithms owned by a user or organization
+fn owned_algorithms(client: &HttpClient, owner: &str, kind: &str) -> Paginated<AlgoSummary> {
+    let url = algorithms_url(client, owner);
+    Paginated::new(
+        client.clone(),
+        url,
+        format!("algorithms of {} '{}'", kind, owner),
+    )
+}
+
+/// Create an algorithm owned by a user or organization
+fn create_algorithm(
+    client: &HttpClient,
+    owner_kind: OwnerKind,
+    owner: &str,
+    request: &CreateAlgoRequest,
+) -> Result<Algorithm, Error> {
+    let url = algorithms_url(client, owner)?;
+    client
+        .send(client.post(url).json(&CreateAlgoBody::from(request)))
+        .with_context(|| format!("request error creating algorithm '{}'", request.name))
+        .and_then(process_http_response)
+        .with_context(|| format!("response error creating algorithm '{}'", request.name))?;
+
+    let uri = AlgoUri::owned_by(owner_kind, owner, &request.name);
+    Ok(Algorithm::new(client.clone(), uri))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Algorithmia;
+
+    #[test]
+    fn test_algorithms_url() {
+        let client = Algorithmia::client("").unwrap();
+        assert_eq!(
+            algorithms_url(&client.http_client, "anowell")
+                .unwrap()
+                .path(),
+            "/v1/algorithms/anowell"
+        );
+        assert_eq!(
+            algorithms_url(&client.http_client, "a/b?c").unwrap().path(),
+            "/v1/algorithms/a%2Fb%3Fc"
+        );
+    }
+}
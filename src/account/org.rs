@@ -0,0 +1,282 @@
+use super::keys::{self, ApiKeyInfo, CreateKeyRequest, CreatedApiKey};
+use super::{create_algorithm, owned_algorithms, owner_url};
+use crate::algo::{AlgoSummary, AlgoUri, Algorithm, CreateAlgoRequest, OwnerKind};
+use crate::client::{push_segment, HttpClient};
+use crate::error::{process_http_response, Error, ResultExt};
+use crate::Paginated;
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+static ORGANIZATIONS_BASE_PATH: &str = "v1/organizations";
+
+/// Algorithmia organization - intialized from the `Algorithmia` builder
+pub struct Org {
+    name: String,
+    client: HttpClient,
+}
+
+/// Member of an organization
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct OrgMember {
+    /// Username of the member
+    pub username: String,
+    /// Role of the member within the organization, e.g. `admin` or `member`
+    pub role: Option<String>,
+    // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
+    _dummy: (),
+}
+
+#[derive(Serialize)]
+struct Invitation<'a> {
+    email: &'a str,
+}
+
+impl Org {
+    pub(crate) fn new(client: HttpClient, name: &str) -> Org {
+        Org {
+            name: name.to_owned(),
+            client,
+        }
+    }
+
+    /// Returns the organization name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// List the members of this organization
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// for member in client.org("ml-team").members() {
+    ///     println!("{}", member?.username);
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn members(&self) -> Paginated<OrgMember> {
+        Paginated::new(
+            self.client.clone(),
+            self.org_url("members"),
+            format!("members of organization '{}'", self.name),
+        )
+    }
+
+    /// Invite a user to this organization
+    ///
+    /// `email_or_user` may either be the username of an existing user,
+    /// who is added directly, or an email address, which is sent an invitation.
+    pub fn invite(&self, email_or_user: &str) -> Result<(), Error> {
+        let req = if email_or_user.contains('@') {
+            self.client
+                .post(self.org_url("invitations")?)
+                .json(&Invitation {
+                    email: email_or_user,
+                })
+        } else {
+            self.client
+                .put(push_segment(self.org_url("members")?, email_or_user)?)
+        };
+
+        self.client
+            .send(req)
+            .with_context(|| {
+                format!(
+                    "request error inviting '{}' to '{}'",
+                    email_or_user, self.name
+                )
+            })
+            .and_then(process_http_response)
+            .with_context(|| {
+                format!(
+                    "response error inviting '{}' to '{}'",
+                    email_or_user, self.name
+                )
+            })?;
+        Ok(())
+    }
+
+    /// Remove a user from this organization
+    pub fn remove(&self, username: &str) -> Result<(), Error> {
+        let url = push_segment(self.org_url("members")?, username)?;
+        self.client
+            .send(self.client.delete(url))
+            .with_context(|| format!("request error removing '{}' from '{}'", username, self.name))
+            .and_then(process_http_response)
+            .with_context(|| {
+                format!(
+                    "response error removing '{}' from '{}'",
+                    username, self.name
+                )
+            })?;
+        Ok(())
+    }
+
+    /// List the algorithms owned by this organization
+    pub fn owned_algorithms(&self) -> Paginated<AlgoSummary> {
+        owned_algorithms(&self.client, &self.name, "organization")
+    }
+
+    /// Instantiate an [`Algorithm`](../algo/struct.Algorithm.html) owned by this organization
+    ///
+    /// `algo` may optionally include a version, e.g. `"Classifier/1.0"`
+    pub fn algo(&self, algo: &str) -> Algorithm {
+        let uri = AlgoUri::owned_by(OwnerKind::Org, &self.name, algo);
+        Algorithm::new(self.client.clone(), uri)
+    }
+
+    /// Create a new algorithm owned by this organization
+    ///
+    /// Returns an `Algorithm` for the newly created algorithm
+    pub fn create_algorithm(&self, request: &CreateAlgoRequest) -> Result<Algorithm, Error> {
+        create_algorithm(&self.client, OwnerKind::Org, &self.name, request)
+    }
+
+    /// Create an API key for this organization, optionally restricted in scope
+    ///
+    /// The returned key includes the secret, which cannot be retrieved later.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::account::{CreateKeyRequest, KeyScope};
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let key = client.org("ml-team").create_key(&CreateKeyRequest {
+    ///     scopes: vec![KeyScope::AlgoCall],
+    ///     ..CreateKeyRequest::new("ci-caller")
+    /// })?;
+    /// println!("Created key {}", key.info.id);
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn create_key(&self, request: &CreateKeyRequest) -> Result<CreatedApiKey, Error> {
+        keys::create_key(&self.client, self.org_url("keys")?, request)
+    }
+
+    /// List the API keys of this organization
+    pub fn keys(&self) -> Paginated<ApiKeyInfo> {
+        keys::list_keys(&self.client, self.org_url("keys"), &self.name)
+    }
+
+    /// Revoke an API key of this organization
+    pub fn revoke_key(&self, key_id: &str) -> Result<(), Error> {
+        let url = push_segment(self.org_url("keys")?, key_id)?;
+        keys::revoke_key(&self.client, url, key_id)
+    }
+
+    fn org_url(&self, resource: &str) -> Result<Url, Error> {
+        let url = owner_url(&self.client, ORGANIZATIONS_BASE_PATH, &self.name)?;
+        push_segment(url, resource)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Algorithmia;
+
+    #[test]
+    fn test_org_url() {
+        let client = Algorithmia::client("").unwrap();
+        assert_eq!(
+            client.org("ml-team").org_url("members").unwrap().path(),
+            "/v1/organizations/ml-team/members"
+        );
+        assert_eq!(
+            client
+                .org("ml-team/keys?x")
+                .org_url("members")
+                .unwrap()
+                .path(),
+            "/v1/organizations/ml-team%2Fkeys%3Fx/members"
+        );
+        assert!(client.org("..").org_url("members").is_err());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_org_members() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let members = json!({
+            "results": [{ "username": "anowell", "role": "admin" }],
+            "marker": null
+        });
+        let base = "/v1/organizations/ml-team";
+        server.route(
+            "GET",
+            &format!("{}/members", base),
+            FakeResponse::json(200, &members),
+        );
+        server.route(
+            "POST",
+            &format!("{}/invitations", base),
+            FakeResponse::status(200),
+        );
+        server.route(
+            "PUT",
+            &format!("{}/members/jdoe", base),
+            FakeResponse::status(200),
+        );
+        server.route(
+            "DELETE",
+            &format!("{}/members/jdoe", base),
+            FakeResponse::status(204),
+        );
+        server.route(
+            "DELETE",
+            &format!("{}/members/a%2Fb", base),
+            FakeResponse::status(204),
+        );
+
+        let org = client.org("ml-team");
+        let members: Vec<_> = org.members().map(Result::unwrap).collect();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].username, "anowell");
+        assert_eq!(members[0].role.as_deref(), Some("admin"));
+        org.invite("jane@example.com").unwrap();
+        org.invite("jdoe").unwrap();
+        org.remove("jdoe").unwrap();
+        org.remove("a/b").unwrap();
+
+        let requests = server.requests();
+        let summary: Vec<_> = requests
+            .iter()
+            .map(|r| (r.method.as_str(), r.url.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("GET", "/v1/organizations/ml-team/members"),
+                ("POST", "/v1/organizations/ml-team/invitations"),
+                ("PUT", "/v1/organizations/ml-team/members/jdoe"),
+                ("DELETE", "/v1/organizations/ml-team/members/jdoe"),
+                ("DELETE", "/v1/organizations/ml-team/members/a%2Fb"),
+            ]
+        );
+        let invitation: serde_json::Value =
+            serde_json::from_str(&requests[1].body_string()).unwrap();
+        assert_eq!(invitation, json!({ "email": "jane@example.com" }));
+        assert!(requests[2].body.is_empty());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_revoke_key() {
+        use crate::testing::{FakeResponse, FakeServer};
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let path = "/v1/organizations/ml-team/keys/k%2F1";
+        server.route("DELETE", path, FakeResponse::status(204));
+
+        client.org("ml-team").revoke_key("k/1").unwrap();
+        assert_eq!(server.requests()[0].url, path);
+    }
+}
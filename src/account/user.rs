@@ -0,0 +1,292 @@
+use super::keys::{self, ApiKeyInfo, CreateKeyRequest, CreatedApiKey};
+use super::{create_algorithm, owned_algorithms, owner_url};
+use crate::algo::{AlgoSummary, AlgoUri, Algorithm, CreateAlgoRequest, OwnerKind};
+use crate::client::{push_segment, HttpClient};
+use crate::data::{DataDir, HasDataPath};
+use crate::error::{process_http_response, Error, ResultExt};
+use crate::Paginated;
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+static USERS_BASE_PATH: &str = "v1/users";
+
+/// Username the API resolves to the owner of the API key
+pub(crate) static CURRENT_USER: &str = "me";
+
+/// Profile of an Algorithmia user as returned by the users API
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct UserInfo {
+    /// Username, which is also the owner of the user's algorithms and `data://<username>`
+    pub username: String,
+    /// Full name, if set
+    #[serde(default)]
+    pub fullname: Option<String>,
+    /// Email address, if visible to the caller
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Company name, if set
+    #[serde(default)]
+    pub company_name: Option<String>,
+    // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
+    _dummy: (),
+}
+
+/// Fetch the profile of a user
+pub(crate) fn user_info(client: &HttpClient, username: &str) -> Result<UserInfo, Error> {
+    let url = owner_url(client, USERS_BASE_PATH, username)?;
+    let mut res = client
+        .send(client.get(url))
+        .with_context(|| format!("request error getting user '{}'", username))
+        .and_then(process_http_response)
+        .with_context(|| format!("response error getting user '{}'", username))?;
+
+    res.json()
+        .with_context(|| format!("JSON decoding error getting user '{}'", username))
+}
+
+/// Algorithmia user - intialized from the `Algorithmia` builder
+pub struct User {
+    name: String,
+    client: HttpClient,
+}
+
+impl User {
+    pub(crate) fn new(client: HttpClient, name: &str) -> User {
+        User {
+            name: name.to_owned(),
+            client,
+        }
+    }
+
+    /// Returns the username
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the profile of this user
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let info = client.user("anowell").info()?;
+    /// println!("{} ({:?})", info.username, info.fullname);
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn info(&self) -> Result<UserInfo, Error> {
+        user_info(&self.client, &self.name)
+    }
+
+    /// List the algorithms owned by this user
+    ///
+    /// Pages of algorithms are fetched lazily while iterating.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// for algo in client.user("anowell").algorithms() {
+    ///     let algo = algo?;
+    ///     println!("{} {:?}: {:?} calls", algo.name, algo.latest_version(), algo.call_count);
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn algorithms(&self) -> Paginated<AlgoSummary> {
+        owned_algorithms(&self.client, &self.name, "user")
+    }
+
+    /// Instantiate an [`Algorithm`](../algo/struct.Algorithm.html) owned by this user
+    ///
+    /// `algo` may optionally include a version, e.g. `"Dijkstra/0.1"`
+    pub fn algo(&self, algo: &str) -> Algorithm {
+        let uri = AlgoUri::owned_by(OwnerKind::User, &self.name, algo);
+        Algorithm::new(self.client.clone(), uri)
+    }
+
+    /// Create a new algorithm owned by this user
+    ///
+    /// Returns an `Algorithm` for the newly created algorithm
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::algo::CreateAlgoRequest;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let algo = client.user("anowell").create_algorithm(&CreateAlgoRequest {
+    ///     language: Some("rust".into()),
+    ///     ..CreateAlgoRequest::new("Dijkstra")
+    /// })?;
+    /// algo.compile()?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn create_algorithm(&self, request: &CreateAlgoRequest) -> Result<Algorithm, Error> {
+        create_algorithm(&self.client, OwnerKind::User, &self.name, request)
+    }
+
+    /// Create a new API key for this user, where the platform supports managing user keys
+    ///
+    /// The secret of the new key is only available in the returned `CreatedApiKey`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::account::{CreateKeyRequest, KeyScope};
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let key = client.user("anowell").create_key(&CreateKeyRequest {
+    ///     scopes: vec![KeyScope::DataRead],
+    ///     ..CreateKeyRequest::new("model-reader")
+    /// })?;
+    /// println!("Created key {}", key.info.id);
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn create_key(&self, request: &CreateKeyRequest) -> Result<CreatedApiKey, Error> {
+        keys::create_key(&self.client, self.user_url("keys")?, request)
+    }
+
+    /// List the API keys of this user
+    pub fn keys(&self) -> Paginated<ApiKeyInfo> {
+        keys::list_keys(&self.client, self.user_url("keys"), &self.name)
+    }
+
+    /// Revoke an API key of this user
+    pub fn revoke_key(&self, key_id: &str) -> Result<(), Error> {
+        let url = push_segment(self.user_url("keys")?, key_id)?;
+        keys::revoke_key(&self.client, url, key_id)
+    }
+
+    fn user_url(&self, resource: &str) -> Result<Url, Error> {
+        let url = owner_url(&self.client, USERS_BASE_PATH, &self.name)?;
+        push_segment(url, resource)
+    }
+
+    /// Instantiate the `DataDir` for this user's hosted data, i.e. `data://<username>`
+    ///
+    /// ```
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::data::HasDataPath;
+    /// # let client = Algorithmia::client("111112222233333444445555566").unwrap();
+    /// let home = client.user("anowell").data_home();
+    /// assert_eq!(home.to_data_uri(), "data://anowell");
+    /// ```
+    pub fn data_home(&self) -> DataDir {
+        DataDir::new(self.client.clone(), &format!("data://{}", self.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Algorithmia;
+
+    fn mock_client() -> Algorithmia {
+        Algorithmia::client("").unwrap()
+    }
+
+    #[test]
+    fn test_user_algo() {
+        let algo = mock_client().user("anowell").algo("Pinky/0.1");
+        assert_eq!(algo.to_algo_uri().path(), "anowell/Pinky/0.1");
+    }
+
+    #[test]
+    fn test_user_url() {
+        let user = mock_client().user("anowell");
+        assert_eq!(
+            user.user_url("keys").unwrap().path(),
+            "/v1/users/anowell/keys"
+        );
+        let user = mock_client().user("../orgs/acme");
+        assert_eq!(
+            user.user_url("keys").unwrap().path(),
+            "/v1/users/..%2Forgs%2Facme/keys"
+        );
+        assert!(mock_client().user("..").user_url("keys").is_err());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_user_keys() {
+        use crate::account::CreateKeyRequest;
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let key = json!({ "id": "k-1", "name": "ci", "scopes": ["algo_call"] });
+        let created =
+            json!({ "id": "k-1", "name": "ci", "scopes": ["algo_call"], "key": "simSecret" });
+        let listing = json!({ "results": [key], "marker": null });
+        server.route(
+            "POST",
+            "/v1/users/anowell/keys",
+            FakeResponse::json(200, &created),
+        );
+        server.route(
+            "GET",
+            "/v1/users/anowell/keys",
+            FakeResponse::json(200, &listing),
+        );
+        server.route(
+            "DELETE",
+            "/v1/users/anowell/keys/k-1",
+            FakeResponse::status(204),
+        );
+
+        let user = client.user("anowell");
+        let created = user.create_key(&CreateKeyRequest::new("ci")).unwrap();
+        assert_eq!(created.secret, "simSecret");
+        let keys: Vec<_> = user.keys().map(Result::unwrap).collect();
+        assert_eq!(keys, vec![created.info]);
+        user.revoke_key("k-1").unwrap();
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_revoke_key() {
+        use crate::testing::{FakeResponse, FakeServer};
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let path = "/v1/users/anowell/keys/k%2F1";
+        server.route("DELETE", path, FakeResponse::status(204));
+
+        client.user("anowell").revoke_key("k/1").unwrap();
+        assert_eq!(server.requests()[0].url, path);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_user_info() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let profile = json!({
+            "username": "anowell",
+            "fullname": "Anthony Nowell",
+            "resource_type": "user"
+        });
+        server.route(
+            "GET",
+            "/v1/users/anowell",
+            FakeResponse::json(200, &profile),
+        );
+        server.route("GET", "/v1/users/me", FakeResponse::json(200, &profile));
+
+        let info = client.user("anowell").info().unwrap();
+        assert_eq!(info.username, "anowell");
+        assert_eq!(info.fullname.as_deref(), Some("Anthony Nowell"));
+        assert_eq!(info.email, None);
+        assert_eq!(client.me().unwrap(), info);
+        assert_eq!(server.requests()[1].url, "/v1/users/me");
+
+        assert!(client.user("nobody").info().is_err());
+    }
+}
@@ -0,0 +1,342 @@
+use super::{Algorithm, VersionInfo};
+use crate::client::push_segment;
+use crate::error::{process_http_response, Error, ResultExt};
+use crate::Paginated;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Status of an algorithm build
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BuildStatus {
+    /// Build is queued or currently compiling
+    InProgress,
+    /// Build completed successfully
+    Succeeded,
+    /// Build failed
+    Failed,
+    /// Status not recognized by this client
+    #[serde(other)]
+    Unknown,
+}
+
+impl BuildStatus {
+    /// Returns true if the build has finished, successfully or not
+    pub fn is_terminal(self) -> bool {
+        match self {
+            BuildStatus::Succeeded | BuildStatus::Failed => true,
+            BuildStatus::InProgress | BuildStatus::Unknown => false,
+        }
+    }
+}
+
+/// An algorithm build as returned by the builds API
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AlgoBuild {
+    /// Unique identifier of the build
+    pub build_id: String,
+    /// Git SHA of the source being built
+    pub commit_sha: Option<String>,
+    /// Current status of the build
+    pub status: BuildStatus,
+    /// Time the build started
+    pub started_at: Option<DateTime<Utc>>,
+    /// Time the build finished
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Version information, present once the build has produced a version
+    pub version_info: Option<VersionInfo>,
+    // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
+    _dummy: (),
+}
+
+/// Iterator over the builds of an `Algorithm`, optionally filtered by status
+pub struct Builds {
+    inner: Paginated<AlgoBuild>,
+    status: Option<BuildStatus>,
+}
+
+impl Builds {
+    /// Only yield builds with the given status
+    pub fn with_status(mut self, status: BuildStatus) -> Builds {
+        self.status = Some(status);
+        self
+    }
+}
+
+impl Iterator for Builds {
+    type Item = Result<AlgoBuild, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let status = self.status;
+        self.inner.find(|build| match (build, status) {
+            (Ok(b), Some(s)) => b.status == s,
+            _ => true,
+        })
+    }
+}
+
+/// Default interval between status checks in `Build::wait`
+const BUILD_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A build triggered by [`Algorithm::compile`](struct.Algorithm.html#method.compile)
+pub struct Build {
+    algo: Algorithm,
+    build: AlgoBuild,
+    poll_interval: Duration,
+}
+
+impl Build {
+    /// The build as of when it was triggered or last refreshed
+    pub fn info(&self) -> &AlgoBuild {
+        &self.build
+    }
+
+    /// Interval between status checks while waiting (default: 5 seconds)
+    pub fn poll_interval(mut self, interval: Duration) -> Build {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Fetch the current status of the build
+    pub fn refresh(&mut self) -> Result<&AlgoBuild, Error> {
+        self.build = self.algo.get_build(&self.build.build_id)?;
+        Ok(&self.build)
+    }
+
+    /// Block until the build has either succeeded or failed, for at most `timeout`
+    ///
+    /// A failed build is not treated as an error: check the `status` of the returned build.
+    /// See [`Algorithm::wait_for`](struct.Algorithm.html#method.wait_for).
+    pub fn wait(self, timeout: Duration) -> Result<AlgoBuild, Error> {
+        match self.build.status {
+            BuildStatus::Succeeded | BuildStatus::Failed => return Ok(self.build),
+            BuildStatus::InProgress => (),
+            BuildStatus::Unknown => bail!(
+                "build '{}' of algorithm '{}' has an unrecognized status",
+                self.build.build_id,
+                self.algo.algo_uri
+            ),
+        }
+        let deadline = Instant::now() + timeout;
+        self.algo
+            .wait_for(&self.build.build_id, self.poll_interval, deadline)
+    }
+}
+
+impl Algorithm {
+    /// List the builds of this algorithm, most recent first
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::algo::BuildStatus;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let failed = client.algo("anowell/Dijkstra")
+    ///     .builds()
+    ///     .with_status(BuildStatus::Failed);
+    /// for build in failed {
+    ///     println!("Failed build: {}", build?.build_id);
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn builds(&self) -> Builds {
+        Builds {
+            inner: Paginated::new(
+                self.client.clone(),
+                self.management_url("builds"),
+                format!("builds of algorithm '{}'", self.algo_uri),
+            ),
+            status: None,
+        }
+    }
+
+    /// Get a single build of this algorithm
+    pub fn get_build(&self, build_id: &str) -> Result<AlgoBuild, Error> {
+        let url = push_segment(self.management_url("builds")?, build_id)?;
+        let mut res = self
+            .client
+            .send(self.client.get(url))
+            .with_context(|| format!("request error getting build '{}'", build_id))
+            .and_then(process_http_response)
+            .with_context(|| format!("response error getting build '{}'", build_id))?;
+
+        res.json()
+            .with_context(|| format!("JSON decoding error getting build '{}'", build_id))
+    }
+
+    /// Trigger a new build from the latest commit of the algorithm's source repository
+    ///
+    /// This is equivalent to the "Build" button in the web console.
+    /// The returned build is typically still in progress: use [`Build::wait`](struct.Build.html#method.wait)
+    /// to block until it completes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::algo::BuildStatus;
+    /// # use std::time::Duration;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let build = client.algo("anowell/Dijkstra").compile()?.wait(Duration::from_secs(600))?;
+    /// if build.status == BuildStatus::Failed {
+    ///     println!("Build {} failed", build.build_id);
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn compile(&self) -> Result<Build, Error> {
+        let url = self.management_url("compile")?;
+        let mut res = self
+            .client
+            .send(self.client.post(url))
+            .with_context(|| format!("request error compiling algorithm '{}'", self.algo_uri))
+            .and_then(process_http_response)
+            .with_context(|| format!("response error compiling algorithm '{}'", self.algo_uri))?;
+
+        let build = res.json().with_context(|| {
+            format!(
+                "JSON decoding error compiling algorithm '{}'",
+                self.algo_uri
+            )
+        })?;
+        Ok(Build {
+            algo: Algorithm::new(self.client.clone(), self.algo_uri.clone()),
+            build,
+            poll_interval: BUILD_POLL_INTERVAL,
+        })
+    }
+
+    /// Block until a build has either succeeded or failed
+    ///
+    /// The build status is polled every `poll_interval`,
+    /// and an error is returned if the build has not finished by `deadline`.
+    /// A failed build is not treated as an error: check the `status` of the returned build.
+    /// A status this client doesn't recognize is an error, since it may never change.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::algo::BuildStatus;
+    /// # use std::time::{Duration, Instant};
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let algo = client.algo("anowell/Dijkstra");
+    /// let deadline = Instant::now() + Duration::from_secs(600);
+    /// let build = algo.wait_for("a1b2c3", Duration::from_secs(5), deadline)?;
+    /// assert_eq!(build.status, BuildStatus::Succeeded);
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn wait_for(
+        &self,
+        build_id: &str,
+        poll_interval: Duration,
+        deadline: Instant,
+    ) -> Result<AlgoBuild, Error> {
+        loop {
+            let build = self.get_build(build_id)?;
+            match build.status {
+                BuildStatus::Succeeded | BuildStatus::Failed => return Ok(build),
+                BuildStatus::InProgress => (),
+                BuildStatus::Unknown => bail!(
+                    "build '{}' of algorithm '{}' has an unrecognized status",
+                    build_id,
+                    self.algo_uri
+                ),
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                bail!(
+                    "timed out waiting for build '{}' of algorithm '{}'",
+                    build_id,
+                    self.algo_uri
+                );
+            }
+            thread::sleep(std::cmp::min(poll_interval, deadline - now));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_decoding() {
+        let json = r#"{
+            "build_id": "a1b2c3",
+            "commit_sha": "f00ba4",
+            "status": "in-progress",
+            "started_at": "2019-08-01T17:21:48.000Z",
+            "resource_type": "algorithm_build"
+        }"#;
+        let build: AlgoBuild = serde_json::from_str(json).unwrap();
+        assert_eq!(build.status, BuildStatus::InProgress);
+        assert!(!build.status.is_terminal());
+        assert!(build.finished_at.is_none());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_build_wait() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let build = |status: &str| json!({ "build_id": "a1b2c3", "status": status });
+        server.route(
+            "POST",
+            "/v1/algorithms/anowell/Dijkstra/compile",
+            FakeResponse::json(200, &build("in-progress")),
+        );
+        server.route(
+            "GET",
+            "/v1/algorithms/anowell/Dijkstra/builds/a1b2c3",
+            FakeResponse::json(200, &build("succeeded")),
+        );
+
+        let pending = client
+            .algo("anowell/Dijkstra")
+            .compile()
+            .unwrap()
+            .poll_interval(Duration::from_millis(10));
+        assert_eq!(pending.info().status, BuildStatus::InProgress);
+        let finished = pending.wait(Duration::from_secs(5)).unwrap();
+        assert_eq!(finished.status, BuildStatus::Succeeded);
+        assert_eq!(server.requests().len(), 2);
+
+        server.route(
+            "GET",
+            "/v1/algorithms/anowell/Dijkstra/builds/a1b2c3",
+            FakeResponse::json(200, &build("in-progress")),
+        );
+        let pending = client.algo("anowell/Dijkstra").compile().unwrap();
+        assert!(pending
+            .poll_interval(Duration::from_millis(10))
+            .wait(Duration::from_millis(50))
+            .is_err());
+
+        // An unrecognized status fails immediately instead of polling until the timeout
+        server.route(
+            "GET",
+            "/v1/algorithms/anowell/Dijkstra/builds/a1b2c3",
+            FakeResponse::json(200, &build("cancelled")),
+        );
+        let pending = client.algo("anowell/Dijkstra").compile().unwrap();
+        let started = Instant::now();
+        let err = pending.wait(Duration::from_secs(60)).err().unwrap();
+        assert!(err.to_string().contains("unrecognized status"));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_unknown_build_status() {
+        let status: BuildStatus = serde_json::from_str(r#""cancelled""#).unwrap();
+        assert_eq!(status, BuildStatus::Unknown);
+    }
+}
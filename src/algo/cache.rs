@@ -0,0 +1,330 @@
+use super::{AlgoIo, AlgoResponse, Algorithm};
+use crate::error::{err_msg, SharedError};
+
+use mime::Mime;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+const DEFAULT_MAX_ENTRIES: usize = 1024;
+
+type CacheKey = (Mime, Vec<u8>);
+type FlightResult = Result<AlgoResponse, SharedError>;
+
+/// Algorithm with a TTL cache of responses keyed by input
+///
+/// Concurrent calls with identical input share a single in-flight request,
+/// so a burst of identical calls only reaches the API once. Only successful
+/// responses are cached; an error is returned to every caller that shared the
+/// failed request, and the next call retries.
+///
+/// Created with [`Algorithm::cached`](struct.Algorithm.html#method.cached).
+/// Share between threads by wrapping it in an `Arc`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use algorithmia::Algorithmia;
+/// use std::time::Duration;
+///
+/// let client = Algorithmia::client("111112222233333444445555566")?;
+/// let minmax = client
+///     .algo("codeb34v3r/FindMinMax/0.1")
+///     .cached(Duration::from_secs(60))
+///     .max_entries(100);
+///
+/// let first = minmax.pipe(vec![2, 3, 4])?;
+/// let second = minmax.pipe(vec![2, 3, 4])?; // served from cache
+/// # Ok::<(), Box<std::error::Error>>(())
+/// ```
+pub struct CachedAlgorithm {
+    algo: Algorithm,
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<CacheKey, Entry>>,
+}
+
+enum Entry {
+    Ready {
+        response: Box<AlgoResponse>,
+        expires: Instant,
+    },
+    Pending(Arc<Flight>),
+}
+
+/// Result of an in-flight request shared with concurrent callers
+#[derive(Default)]
+struct Flight {
+    result: Mutex<Option<FlightResult>>,
+    done: Condvar,
+}
+
+impl Flight {
+    fn complete(&self, result: FlightResult) {
+        *self.result.lock().unwrap() = Some(result);
+        self.done.notify_all();
+    }
+
+    fn wait(&self) -> FlightResult {
+        let mut result = self.result.lock().unwrap();
+        loop {
+            if let Some(result) = &*result {
+                return result.clone();
+            }
+            result = self.done.wait(result).unwrap();
+        }
+    }
+}
+
+/// Completes a flight with an error if the request panics before completing it
+struct FlightGuard<'a> {
+    cache: &'a CachedAlgorithm,
+    key: Option<CacheKey>,
+    flight: Arc<Flight>,
+}
+
+impl<'a> FlightGuard<'a> {
+    fn finish(mut self, result: FlightResult) -> FlightResult {
+        let key = self.key.take().expect("flight finished twice");
+        self.cache.finish(key, &self.flight, result)
+    }
+}
+
+impl<'a> Drop for FlightGuard<'a> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let err = err_msg("cached algorithm call did not complete").into_shared();
+            let _ = self.cache.finish(key, &self.flight, Err(err));
+        }
+    }
+}
+
+impl Algorithm {
+    /// Cache successful responses of this algorithm for `ttl`
+    ///
+    /// See [`CachedAlgorithm`](struct.CachedAlgorithm.html) for details.
+    pub fn cached(self, ttl: Duration) -> CachedAlgorithm {
+        CachedAlgorithm {
+            algo: self,
+            ttl,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl CachedAlgorithm {
+    /// Limit the number of cached responses (default: 1024)
+    ///
+    /// When full, expired responses are evicted first, then those closest to expiring.
+    pub fn max_entries(mut self, max_entries: usize) -> CachedAlgorithm {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Execute the algorithm, or return a cached response for identical input
+    ///
+    /// See [`Algorithm::pipe`](struct.Algorithm.html#method.pipe) for accepted input.
+    pub fn pipe<I: Into<AlgoIo>>(&self, input_data: I) -> Result<AlgoResponse, SharedError> {
+        let (body, content_type) = self.algo.encode_input(input_data.into())?;
+        let key = (content_type, body);
+
+        let (flight, is_leader) = {
+            let mut entries = self.entries.lock().unwrap();
+            match entries.get(&key) {
+                Some(Entry::Ready { response, expires }) if *expires > Instant::now() => {
+                    return Ok(AlgoResponse::clone(response));
+                }
+                Some(Entry::Pending(flight)) => (flight.clone(), false),
+                _ => {
+                    let flight = Arc::new(Flight::default());
+                    entries.insert(key.clone(), Entry::Pending(flight.clone()));
+                    (flight, true)
+                }
+            }
+        };
+
+        // Another caller is already requesting identical input
+        if !is_leader {
+            return flight.wait();
+        }
+
+        let guard = FlightGuard {
+            cache: self,
+            key: Some(key.clone()),
+            flight,
+        };
+        let (content_type, body) = key;
+        let result = self
+            .algo
+            .pipe_encoded(body, content_type)
+            .map_err(SharedError::from);
+        guard.finish(result)
+    }
+
+    /// Remove all cached responses
+    ///
+    /// Requests that are in flight are unaffected.
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|_, entry| matches!(entry, Entry::Pending(_)));
+    }
+
+    /// The underlying algorithm
+    pub fn algo(&self) -> &Algorithm {
+        &self.algo
+    }
+
+    fn finish(&self, key: CacheKey, flight: &Flight, result: FlightResult) -> FlightResult {
+        {
+            let mut entries = self.entries.lock().unwrap();
+            match &result {
+                Ok(response) => {
+                    let now = Instant::now();
+                    entries.remove(&key);
+                    self.evict(&mut entries, now);
+                    entries.insert(
+                        key,
+                        Entry::Ready {
+                            response: Box::new(response.clone()),
+                            expires: now + self.ttl,
+                        },
+                    );
+                }
+                Err(_) => {
+                    entries.remove(&key);
+                }
+            }
+        }
+        flight.complete(result.clone());
+        result
+    }
+
+    /// Make room for one more cached response
+    fn evict(&self, entries: &mut HashMap<CacheKey, Entry>, now: Instant) {
+        let ready = |entries: &HashMap<CacheKey, Entry>| {
+            entries
+                .values()
+                .filter(|e| matches!(e, Entry::Ready { .. }))
+                .count()
+        };
+        if ready(entries) < self.max_entries {
+            return;
+        }
+
+        entries.retain(|_, entry| match entry {
+            Entry::Ready { expires, .. } => *expires > now,
+            Entry::Pending(_) => true,
+        });
+        while ready(entries) >= self.max_entries {
+            let oldest = entries
+                .iter()
+                .filter_map(|(key, entry)| match entry {
+                    Entry::Ready { expires, .. } => Some((*expires, key.clone())),
+                    Entry::Pending(_) => None,
+                })
+                .min();
+            match oldest {
+                Some((_, key)) => entries.remove(&key),
+                None => break,
+            };
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::{FakeResponse, FakeServer};
+    use serde_json::json;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+    use std::time::Duration;
+
+    const CALLERS: usize = 8;
+
+    /// Pipe the same input from concurrent callers, started together
+    fn pipe_concurrently(algo: &Arc<CachedAlgorithm>) -> Vec<FlightResult> {
+        let barrier = Arc::new(Barrier::new(CALLERS));
+        let threads: Vec<_> = (0..CALLERS)
+            .map(|_| {
+                let (algo, barrier) = (algo.clone(), barrier.clone());
+                thread::spawn(move || {
+                    barrier.wait();
+                    algo.pipe("Brain")
+                })
+            })
+            .collect();
+        threads.into_iter().map(|t| t.join().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_cached_algorithm() {
+        let server = FakeServer::start().unwrap();
+        server.algo_result("anowell/Pinky", json!("Narf!"));
+        let client = server.client().unwrap();
+
+        let algo = client
+            .algo("anowell/Pinky")
+            .cached(Duration::from_millis(200))
+            .max_entries(1);
+        let res = algo.pipe("Brain").unwrap();
+        assert_eq!(res.decode::<String>().unwrap(), "Narf!");
+        algo.pipe("Brain").unwrap();
+        assert_eq!(server.requests().len(), 1);
+
+        // Evicts "Brain" since the cache holds a single entry
+        algo.pipe("Pinky").unwrap();
+        algo.pipe("Brain").unwrap();
+        assert_eq!(server.requests().len(), 3);
+
+        thread::sleep(Duration::from_millis(250));
+        algo.pipe("Brain").unwrap();
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[test]
+    fn test_single_flight() {
+        let server = FakeServer::start().unwrap();
+        let result = json!({
+            "result": "Narf!",
+            "metadata": { "content_type": "json", "duration": 0.001 }
+        });
+        // Slow enough that every caller starts while the first request is in flight
+        server.algo_response(
+            "anowell/Pinky",
+            FakeResponse::json(200, &result).with_delay(Duration::from_millis(500)),
+        );
+        let client = server.client().unwrap();
+
+        let algo = Arc::new(client.algo("anowell/Pinky").cached(Duration::from_secs(60)));
+        for res in pipe_concurrently(&algo) {
+            assert_eq!(res.unwrap().decode::<String>().unwrap(), "Narf!");
+        }
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_cached_algorithm_errors() {
+        let server = FakeServer::start().unwrap();
+        let error = json!({ "error": { "message": "Narf!", "error_type": "AlgorithmError" } });
+        server.algo_response(
+            "anowell/Pinky",
+            FakeResponse::json(400, &error).with_delay(Duration::from_millis(500)),
+        );
+        let client = server.client().unwrap();
+
+        let algo = Arc::new(client.algo("anowell/Pinky").cached(Duration::from_secs(60)));
+        for res in pipe_concurrently(&algo) {
+            assert_eq!(res.err().unwrap().api_error().unwrap().message, "Narf!");
+        }
+        assert_eq!(server.requests().len(), 1);
+
+        // Errors aren't cached, so the next call retries
+        algo.pipe("Brain").err().unwrap();
+        assert_eq!(server.requests().len(), 2);
+    }
+}
@@ -0,0 +1,227 @@
+//! Types describing algorithms as returned by the algorithm management API
+
+use serde::{Deserialize, Serialize};
+
+/// Summary of an algorithm as returned when listing algorithms
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AlgoSummary {
+    /// Name of the algorithm (without the owner)
+    pub name: String,
+    /// Human-friendly details about the algorithm
+    #[serde(default)]
+    pub details: AlgoDetails,
+    /// Details about the most recently published version
+    pub version_info: Option<VersionInfo>,
+    /// Tags used to categorize the algorithm
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Number of times the algorithm has been called, if reported
+    #[serde(default)]
+    pub call_count: Option<u64>,
+    // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
+    _dummy: (),
+}
+
+impl AlgoSummary {
+    /// Semantic version of the most recently published version, if any
+    pub fn latest_version(&self) -> Option<&str> {
+        self.version_info
+            .as_ref()
+            .and_then(|info| info.semantic_version.as_ref())
+            .map(String::as_str)
+    }
+}
+
+/// Human-friendly details describing an algorithm
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct AlgoDetails {
+    /// Display name of the algorithm
+    pub label: Option<String>,
+    /// Short description of the algorithm
+    pub tagline: Option<String>,
+    /// Long-form description of the algorithm
+    pub summary: Option<String>,
+    // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
+    _dummy: (),
+}
+
+/// Information describing a specific version of an algorithm
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct VersionInfo {
+    /// Semantic version, e.g. `1.0.2` (absent for unpublished builds)
+    pub semantic_version: Option<String>,
+    /// Git SHA of the source the version was built from
+    pub git_hash: Option<String>,
+    /// Release notes provided when publishing
+    pub release_notes: Option<String>,
+    /// Sample input provided when publishing
+    pub sample_input: Option<String>,
+    // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
+    _dummy: (),
+}
+
+/// Whether an algorithm's source code is visible to other users
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum SourceVisibility {
+    /// Source is visible to any user
+    Open,
+    /// Source is only visible to the owner
+    Closed,
+    /// Value not recognized by this client, kept so it is written back unchanged
+    Unknown(String),
+}
+
+impl From<String> for SourceVisibility {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "open" => SourceVisibility::Open,
+            "closed" => SourceVisibility::Closed,
+            _ => SourceVisibility::Unknown(value),
+        }
+    }
+}
+
+impl From<SourceVisibility> for String {
+    fn from(visibility: SourceVisibility) -> Self {
+        match visibility {
+            SourceVisibility::Open => "open".into(),
+            SourceVisibility::Closed => "closed".into(),
+            SourceVisibility::Unknown(value) => value,
+        }
+    }
+}
+
+/// Request for creating a new algorithm
+///
+/// # Examples
+///
+/// ```
+/// use algorithmia::algo::CreateAlgoRequest;
+///
+/// let req = CreateAlgoRequest {
+///     language: Some("rust".into()),
+///     license: Some("mit".into()),
+///     ..CreateAlgoRequest::new("Dijkstra")
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct CreateAlgoRequest {
+    /// Name of the algorithm (must be unique for the owner)
+    pub name: String,
+    /// Human-friendly details about the algorithm
+    pub details: AlgoDetails,
+    /// Programming language of the algorithm, e.g. `rust` or `python3-1`
+    pub language: Option<String>,
+    /// ID of the algorithm environment, which determines the runtime and hardware (e.g. GPU)
+    pub environment: Option<String>,
+    /// Whether the source code is visible to other users
+    pub source_visibility: Option<SourceVisibility>,
+    /// License identifier, e.g. `apl`, `mit`, or `gpl3`
+    pub license: Option<String>,
+    /// Whether the algorithm may access the internet
+    pub network_access: Option<bool>,
+    /// Whether the algorithm may call other algorithms
+    pub pipeline_enabled: Option<bool>,
+}
+
+impl CreateAlgoRequest {
+    /// Create a request for a new algorithm with default settings
+    pub fn new<S: Into<String>>(name: S) -> CreateAlgoRequest {
+        CreateAlgoRequest {
+            name: name.into(),
+            details: AlgoDetails::default(),
+            language: None,
+            environment: None,
+            source_visibility: None,
+            license: None,
+            network_access: None,
+            pipeline_enabled: None,
+        }
+    }
+}
+
+/// Wire format expected by the create algorithm API
+#[derive(Serialize)]
+pub(crate) struct CreateAlgoBody<'a> {
+    name: &'a str,
+    details: &'a AlgoDetails,
+    settings: CreateAlgoSettings<'a>,
+}
+
+#[derive(Serialize)]
+struct CreateAlgoSettings<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    algorithm_environment: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_visibility: Option<SourceVisibility>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    license: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    network_access: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pipeline_enabled: Option<bool>,
+}
+
+impl<'a> From<&'a CreateAlgoRequest> for CreateAlgoBody<'a> {
+    fn from(req: &'a CreateAlgoRequest) -> Self {
+        CreateAlgoBody {
+            name: &req.name,
+            details: &req.details,
+            settings: CreateAlgoSettings {
+                language: req.language.as_deref(),
+                algorithm_environment: req.environment.as_deref(),
+                source_visibility: req.source_visibility.clone(),
+                license: req.license.as_deref(),
+                network_access: req
+                    .network_access
+                    .map(|enabled| if enabled { "full" } else { "isolated" }),
+                pipeline_enabled: req.pipeline_enabled,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_algo_body_encoding() {
+        let req = CreateAlgoRequest {
+            language: Some("rust".into()),
+            network_access: Some(false),
+            ..CreateAlgoRequest::new("Dijkstra")
+        };
+        let json = serde_json::to_value(CreateAlgoBody::from(&req)).unwrap();
+        assert_eq!(json["name"], "Dijkstra");
+        assert_eq!(json["settings"]["language"], "rust");
+        assert_eq!(json["settings"]["network_access"], "isolated");
+        assert!(json["settings"].get("license").is_none());
+    }
+
+    #[test]
+    fn test_algo_summary_decoding() {
+        let json = r#"{
+            "name": "Pinky",
+            "details": { "label": "Pinky" },
+            "version_info": { "semantic_version": "0.2.1", "git_hash": "abc123" },
+            "tags": ["text", "nlp"],
+            "call_count": 42
+        }"#;
+        let summary: AlgoSummary = serde_json::from_str(json).unwrap();
+        assert_eq!(summary.latest_version(), Some("0.2.1"));
+        assert_eq!(summary.tags, vec!["text", "nlp"]);
+        assert_eq!(summary.call_count, Some(42));
+
+        let summary: AlgoSummary = serde_json::from_str(r#"{"name": "Brain"}"#).unwrap();
+        assert_eq!(summary.latest_version(), None);
+        assert!(summary.tags.is_empty());
+        assert_eq!(summary.call_count, None);
+    }
+}
@@ -0,0 +1,219 @@
+use super::{AlgoIo, Algorithm, OutputMode};
+use crate::data::{DataFile, HasDataPath};
+use crate::error::{process_http_response, Error, ResultExt};
+
+use http::StatusCode;
+use serde::de::DeserializeOwned;
+
+use std::io::Read;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const DEFAULT_MAX_WAIT: Duration = Duration::from_secs(3600);
+const DEFAULT_INITIAL_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Algorithm call that writes its result to a data file instead of returning it
+///
+/// Long jobs can outlive an HTTP request, so the standard pattern is to call the algorithm
+/// with `output=void`, have it write its result to a caller-chosen `data://` path,
+/// and poll until that file appears. The algorithm must be told where to write,
+/// typically by including [`output_uri`](#method.output_uri) in its input,
+/// and must write JSON.
+///
+/// Polling starts at 1 second and doubles up to 30 seconds between checks,
+/// giving up after an hour unless configured otherwise.
+///
+/// Created with [`Algorithm::long_running`](struct.Algorithm.html#method.long_running).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use algorithmia::Algorithmia;
+/// use serde_json::{json, Value};
+/// use std::time::Duration;
+///
+/// let client = Algorithmia::client("111112222233333444445555566")?;
+/// let call = client
+///     .algo("demo/TrainModel")
+///     .long_running("data://.my/models/result.json")
+///     .max_wait(Duration::from_secs(4 * 3600));
+///
+/// let input = json!({ "epochs": 50, "output": call.output_uri() });
+/// let result: Value = call.run(input)?;
+/// # Ok::<(), Box<std::error::Error>>(())
+/// ```
+pub struct LongRunningCall {
+    algo: Algorithm,
+    output: DataFile,
+    max_wait: Duration,
+    initial_interval: Duration,
+    max_interval: Duration,
+}
+
+impl Algorithm {
+    /// Call this algorithm as a long-running job that writes its result to `output`
+    ///
+    /// See [`LongRunningCall`](struct.LongRunningCall.html) for details.
+    pub fn long_running(mut self, output: &str) -> LongRunningCall {
+        self.options.output(OutputMode::Void);
+        let output = DataFile::new(self.client.clone(), output);
+        LongRunningCall {
+            algo: self,
+            output,
+            max_wait: DEFAULT_MAX_WAIT,
+            initial_interval: DEFAULT_INITIAL_INTERVAL,
+            max_interval: DEFAULT_MAX_INTERVAL,
+        }
+    }
+}
+
+impl LongRunningCall {
+    /// Maximum time to wait for the result after submitting (default: 1 hour)
+    pub fn max_wait(mut self, max_wait: Duration) -> LongRunningCall {
+        self.max_wait = max_wait;
+        self
+    }
+
+    /// Time between checks for the result, starting at `initial` and doubling up to `max`
+    pub fn poll_interval(mut self, initial: Duration, max: Duration) -> LongRunningCall {
+        self.initial_interval = initial;
+        self.max_interval = max.max(initial);
+        self
+    }
+
+    /// The data URI the algorithm is expected to write its result to
+    pub fn output_uri(&self) -> String {
+        self.output.to_data_uri()
+    }
+
+    /// The data file the algorithm is expected to write its result to
+    pub fn output(&self) -> &DataFile {
+        &self.output
+    }
+
+    /// The underlying algorithm
+    pub fn algo(&self) -> &Algorithm {
+        &self.algo
+    }
+
+    /// Submit the job, then wait for and decode its result
+    pub fn run<I, D>(&self, input_data: I) -> Result<D, Error>
+    where
+        I: Into<AlgoIo>,
+        D: DeserializeOwned,
+    {
+        self.submit(input_data)?;
+        self.wait()
+    }
+
+    /// Submit the job without waiting for its result
+    ///
+    /// Any existing file at the output path is deleted first,
+    /// so a result left by an earlier run is never mistaken for this one.
+    pub fn submit<I: Into<AlgoIo>>(&self, input_data: I) -> Result<(), Error> {
+        match self.output.delete() {
+            Err(err) if err.status() != Some(StatusCode::NOT_FOUND) => {
+                return Err(err).with_context(|| {
+                    format!("clearing output of algorithm '{}'", self.algo.algo_uri)
+                });
+            }
+            _ => (),
+        }
+
+        let (body, content_type) = self.algo.encode_input(input_data.into())?;
+        self.algo
+            .pipe_as(body, content_type)
+            .and_then(process_http_response)
+            .with_context(|| format!("submitting algorithm '{}'", self.algo.algo_uri))?;
+        Ok(())
+    }
+
+    /// Wait for the result of a submitted job and decode it from JSON
+    ///
+    /// Returns an error if the output file does not appear within the configured maximum wait.
+    pub fn wait<D: DeserializeOwned>(&self) -> Result<D, Error> {
+        let deadline = Instant::now() + self.max_wait;
+        let mut interval = self.initial_interval;
+        while !self.output.exists()? {
+            let now = Instant::now();
+            if now >= deadline {
+                bail!(
+                    "timed out waiting for algorithm '{}' to write '{}'",
+                    self.algo.algo_uri,
+                    self.output.to_data_uri()
+                );
+            }
+            thread::sleep(std::cmp::min(interval, deadline - now));
+            interval = std::cmp::min(interval * 2, self.max_interval);
+        }
+
+        let mut bytes = Vec::new();
+        self.output
+            .get()?
+            .read_to_end(&mut bytes)
+            .with_context(|| format!("downloading '{}'", self.output.to_data_uri()))?;
+        serde_json::from_slice(&bytes).with_context(|| {
+            format!(
+                "decoding result of algorithm '{}' from '{}'",
+                self.algo.algo_uri,
+                self.output.to_data_uri()
+            )
+        })
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use crate::testing::{FakeResponse, FakeServer};
+    use serde_json::{json, Value};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_long_running_call() {
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let output = "data://.my/jobs/result.json";
+        server.algo_response(
+            "demo/Train",
+            FakeResponse::json(200, &json!({"async": "async", "request_id": "req-1"})),
+        );
+        server.route(
+            "DELETE",
+            "/v1/connector/data/.my/jobs/result.json",
+            FakeResponse::status(404),
+        );
+
+        let call = client
+            .algo("demo/Train")
+            .long_running(output)
+            .poll_interval(Duration::from_millis(10), Duration::from_millis(20));
+        let result: Value = thread::scope(|s| {
+            s.spawn(|| {
+                thread::sleep(Duration::from_millis(50));
+                server.data_file(output, r#"{"accuracy": 0.9}"#);
+            });
+            call.run(json!({ "output": call.output_uri() })).unwrap()
+        });
+        assert_eq!(result, json!({"accuracy": 0.9}));
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "DELETE");
+        assert_eq!(requests[1].url, "/v1/algo/demo/Train?output=void");
+        assert_eq!(
+            requests[1].body_string(),
+            r#"{"output":"data://.my/jobs/result.json"}"#
+        );
+        assert!(requests.iter().filter(|r| r.method == "HEAD").count() > 1);
+
+        // Gives up once the maximum wait has passed
+        let call = client
+            .algo("demo/Train")
+            .long_running("data://.my/jobs/missing.json")
+            .max_wait(Duration::from_millis(30))
+            .poll_interval(Duration::from_millis(10), Duration::from_millis(10));
+        let err = call.wait::<Value>().unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+    }
+}
@@ -0,0 +1,218 @@
+use super::{Algorithm, SourceVisibility, Visibility};
+use crate::error::{process_http_response, Error, ResultExt};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Settings of an algorithm as returned by the algorithm management API
+///
+/// Settings not modeled by this client are preserved in `other`,
+/// so fetching and writing back settings never resets unrelated fields.
+///
+/// Fields set to `None` are left out when settings are written, so the server keeps
+/// their current value. To clear a setting, set its field to `None` and insert
+/// `Value::Null` under the setting's name in `other`, which is sent as an explicit `null`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AlgoSettings {
+    /// Programming language of the algorithm
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// ID of the algorithm environment, which determines the runtime and hardware
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algorithm_environment: Option<String>,
+    /// Whether the source code is visible to other users
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_visibility: Option<SourceVisibility>,
+    /// License identifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Network access of the algorithm: `full` or `isolated`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_access: Option<String>,
+    /// Whether the algorithm may call other algorithms
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipeline_enabled: Option<bool>,
+    /// Royalty charged per call, in microcredits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royalty_microcredits: Option<u64>,
+    /// Who may call published versions of the algorithm
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algorithm_callability: Option<Visibility>,
+    /// Any additional settings returned by the API
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Algorithm resource as used when reading and writing settings
+///
+/// Details are kept as raw JSON so they are written back unchanged.
+#[derive(Deserialize, Serialize)]
+struct SettingsResource {
+    #[serde(default)]
+    details: Value,
+    settings: AlgoSettings,
+}
+
+impl Algorithm {
+    /// Get the current settings of this algorithm
+    pub fn settings(&self) -> Result<AlgoSettings, Error> {
+        self.get_settings_resource().map(|r| r.settings)
+    }
+
+    /// Update settings of this algorithm by applying a closure to the current settings
+    ///
+    /// Settings updates replace the entire settings object on some clusters,
+    /// so this fetches the current settings, applies `modify`, and writes the result back.
+    /// Returns the settings that were written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// client.algo("anowell/Dijkstra").modify_settings(|s| {
+    ///     s.pipeline_enabled = Some(true);
+    /// })?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn modify_settings<F>(&self, modify: F) -> Result<AlgoSettings, Error>
+    where
+        F: FnOnce(&mut AlgoSettings),
+    {
+        let mut resource = self.get_settings_resource()?;
+        modify(&mut resource.settings);
+
+        let url = self.management_url("")?;
+        self.client
+            .send(self.client.put(url).json(&resource))
+            .with_context(|| format!("request error updating algorithm '{}'", self.algo_uri))
+            .and_then(process_http_response)
+            .with_context(|| format!("response error updating algorithm '{}'", self.algo_uri))?;
+
+        Ok(resource.settings)
+    }
+
+    /// Permanently delete this algorithm
+    ///
+    /// To guard against deleting the wrong algorithm, `confirm_name` must repeat
+    /// the algorithm's owner and name (e.g. `"anowell/Dijkstra"`), otherwise no request is made.
+    /// Deletion may be rejected by the platform, e.g. if other algorithms depend on this one,
+    /// in which case the returned error includes the platform's explanation.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// client.algo("anowell/TestAlgo").delete("anowell/TestAlgo")?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn delete(&self, confirm_name: &str) -> Result<(), Error> {
+        let owner_and_name = self.owner_and_name();
+        if confirm_name != owner_and_name {
+            bail!(
+                "refusing to delete algorithm '{}': confirmation '{}' does not match",
+                owner_and_name,
+                confirm_name
+            );
+        }
+
+        let url = self.management_url("")?;
+        let res = self
+            .client
+            .send(self.client.delete(url))
+            .with_context(|| format!("request error deleting algorithm '{}'", owner_and_name))?;
+
+        // Include the platform's reason (e.g. dependent algorithms) in the error message
+        if let Err(err) = process_http_response(res) {
+            let reason = err
+                .api_error()
+                .map(|e| format!(": {}", e.message))
+                .unwrap_or_default();
+            return Err(err).with_context(|| {
+                format!(
+                    "response error deleting algorithm '{}'{}",
+                    owner_and_name, reason
+                )
+            });
+        }
+
+        Ok(())
+    }
+
+    fn get_settings_resource(&self) -> Result<SettingsResource, Error> {
+        let url = self.management_url("")?;
+        let mut res = self
+            .client
+            .send(self.client.get(url))
+            .with_context(|| format!("request error getting algorithm '{}'", self.algo_uri))
+            .and_then(process_http_response)
+            .with_context(|| format!("response error getting algorithm '{}'", self.algo_uri))?;
+
+        res.json()
+            .with_context(|| format!("JSON decoding error getting algorithm '{}'", self.algo_uri))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_roundtrip_preserves_unknown_fields() {
+        let json = r#"{
+            "details": { "label": "Dijkstra", "tagline": "shortest paths" },
+            "settings": {
+                "language": "rust",
+                "royalty_microcredits": 0,
+                "insights_enabled": true
+            }
+        }"#;
+        let mut resource: SettingsResource = serde_json::from_str(json).unwrap();
+        resource.settings.pipeline_enabled = Some(true);
+
+        let encoded = serde_json::to_value(&resource).unwrap();
+        assert_eq!(encoded["details"]["tagline"], "shortest paths");
+        assert_eq!(encoded["settings"]["insights_enabled"], true);
+        assert_eq!(encoded["settings"]["pipeline_enabled"], true);
+        assert!(encoded["settings"].get("license").is_none());
+    }
+
+    #[test]
+    fn test_settings_unknown_values_and_null() {
+        let json = r#"{
+            "settings": {
+                "license": "mit",
+                "source_visibility": "shared",
+                "algorithm_callability": "internal"
+            }
+        }"#;
+        let mut resource: SettingsResource = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            resource.settings.source_visibility,
+            Some(SourceVisibility::Unknown("shared".into()))
+        );
+        assert_eq!(
+            resource.settings.algorithm_callability,
+            Some(Visibility::Unknown("internal".into()))
+        );
+
+        resource.settings.license = None;
+        resource
+            .settings
+            .other
+            .insert("license".into(), Value::Null);
+        let encoded = serde_json::to_value(&resource).unwrap();
+        assert_eq!(encoded["settings"]["source_visibility"], "shared");
+        assert_eq!(encoded["settings"]["algorithm_callability"], "internal");
+        assert_eq!(encoded["settings"].get("license"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn test_delete_requires_confirmation() {
+        let client = crate::Algorithmia::client("").unwrap();
+        let algo = client.algo("anowell/TestAlgo/0.1");
+        let err = algo.delete("anowell/OtherAlgo").unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+    }
+}
@@ -18,11 +18,68 @@
 //! ```
 
 use crate::client::HttpClient;
-use crate::error::{ApiErrorResponse, Error, ResultExt};
-use crate::Body;
+use crate::data::DataFile;
+use crate::error::{process_http_response, ApiError, Error, ResultExt};
+use crate::{Body, TransferStats};
 
+mod builds;
 mod bytevec;
+mod cache;
+mod info;
+mod long_running;
+mod manage;
+mod prepared;
+mod raw;
+#[cfg(feature = "ndarray")]
+mod tensor;
+mod versions;
+pub use builds::*;
 pub use bytevec::ByteVec;
+pub use cache::CachedAlgorithm;
+pub use info::*;
+pub use long_running::LongRunningCall;
+pub use manage::*;
+pub use prepared::PreparedCall;
+pub use raw::RawResponse;
+#[cfg(feature = "ndarray")]
+pub use tensor::{JsonArray, Tensor, TENSOR_MAGIC};
+pub use versions::*;
+
+/// Derive conversion from `AlgoIo` for an enum of alternative input types [feature = "derive"]
+///
+/// Each variant must wrap a single type that `AlgoIo` can be converted into
+/// (e.g. `String`, `ByteVec`, or any `Deserialize` type). Conversion tries each variant
+/// in declaration order, so declare text variants before binary and JSON variants,
+/// and more specific JSON types before more general ones. If no variant matches,
+/// the error lists why each variant was rejected.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use algorithmia::algo::{AlgoInputFrom, ByteVec};
+/// use algorithmia::prelude::*;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Params { url: String, max: u32 }
+///
+/// #[derive(AlgoInputFrom)]
+/// enum Input {
+///     Url(String),
+///     Raw(ByteVec),
+///     Params(Params),
+/// }
+///
+/// fn apply(input: Input) -> Result<String, String> {
+///     match input {
+///         Input::Url(url) => Ok(url),
+///         Input::Raw(bytes) => Ok(format!("{} bytes", bytes.len())),
+///         Input::Params(params) => Ok(params.url),
+///     }
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use algorithmia_derive::AlgoInputFrom;
 
 use serde::de::DeserializeOwned;
 use serde::de::Error as SerdeError;
@@ -38,21 +95,28 @@ use reqwest::Url;
 
 use headers_ext::HeaderMapExt;
 use http::header::HeaderMap;
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::io::{self, Read, Write};
 use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+static ALGORITHM_BASE_PATH: &str = "v1/algo";
+static ALGORITHMS_MANAGEMENT_PATH: &str = "v1/algorithms";
 
-static ALGORITHM_BASE_PATH: &'static str = "v1/algo";
+/// Maximum algorithm timeout supported by the Algorithmia platform
+pub const MAX_TIMEOUT: Duration = Duration::from_secs(3000);
 
 /// Types that store either input or ouput to an algorithm
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AlgoIo {
     pub(crate) data: AlgoData,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum AlgoData {
     /// Text input or output
     Text(String),
@@ -66,22 +130,80 @@ pub(crate) enum AlgoData {
 pub struct Algorithm {
     algo_uri: AlgoUri,
     options: AlgoOptions,
+    timeout: Option<Duration>,
+    sorted_json: bool,
     client: HttpClient,
 }
 
 /// Options used to alter the algorithm call, e.g. configuring the timeout
+///
+/// Options are sent as query parameters. The typed setters and [`param`](#method.param)
+/// are checked by [`validate`](#method.validate) before each call, so a bad value fails
+/// the call instead of being silently ignored by the API. Use [`custom`](#method.custom)
+/// to send a parameter exactly as given.
+///
+/// # Examples
+///
+/// ```
+/// use algorithmia::algo::{AlgoOptions, OutputMode};
+///
+/// let mut options = AlgoOptions::new();
+/// options.timeout(10).stdout(true).output(OutputMode::Void);
+/// assert_eq!(options.get("output").map(String::as_str), Some("void"));
+/// assert!(options.validate().is_ok());
+///
+/// options.param("timeout", "ten");
+/// assert!(options.validate().is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct AlgoOptions {
     opts: HashMap<String, String>,
+    unchecked: HashSet<String>,
+}
+
+/// How the API should return an algorithm's result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputMode {
+    /// Return the result as the unparsed response body (`output=raw`)
+    Raw,
+    /// Discard the result and respond once the call is accepted (`output=void`)
+    Void,
+
+    /// Non-exhaustive for API stability if output modes are added
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// Kind of account that owns an algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnerKind {
+    /// Owned by a user
+    User,
+    /// Owned by an organization
+    Org,
+    /// Not known, e.g. when the URI was parsed from a string
+    Unknown,
+
+    /// Non-exhaustive for API stability if owner kinds are added
+    #[doc(hidden)]
+    __Nonexhaustive,
 }
 
 /// URI of an Algorithmia algorithm
-#[derive(Clone)]
+///
+/// Algorithm URIs have the form `[algo://]owner/name[/version]`, where the owner is a user or
+/// an organization. Converting from a string is lenient and invalid URIs are only reported
+/// when the algorithm is used; use [`AlgoUri::parse`](#method.parse) to validate up front.
+///
+/// URIs compare equal if their paths are equal, regardless of the known owner kind.
+#[derive(Debug, Clone)]
 pub struct AlgoUri {
     path: String,
+    owner_kind: OwnerKind,
 }
 
 /// Metadata returned from the API
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct AlgoMetadata {
     /// Algorithm execution duration
     pub duration: f32,
@@ -92,33 +214,154 @@ pub struct AlgoMetadata {
     /// Describes how the ouput's `result` field should be parsed (`text`, `json`, or `binary`)
     pub content_type: String,
     // Placeholder for API stability if additional fields are added later
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     _dummy: (),
 }
 
+/// Alert included in the metadata of an algorithm response
+///
+/// Parsed from the alert messages in [`AlgoMetadata::alerts`](struct.AlgoMetadata.html#structfield.alerts).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Alert {
+    /// The account's credit balance is running low
+    LowBalance,
+    /// The called algorithm version is deprecated
+    DeprecatedVersion,
+    /// Any other alert, with its message
+    Other(String),
+
+    /// Non-exhaustive for API stability if alert types are added
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl<'a> From<&'a str> for Alert {
+    fn from(message: &'a str) -> Alert {
+        let lower = message.to_lowercase();
+        if lower.contains("balance") {
+            Alert::LowBalance
+        } else if lower.contains("deprecat") {
+            Alert::DeprecatedVersion
+        } else {
+            Alert::Other(message.to_owned())
+        }
+    }
+}
+
+impl AlgoMetadata {
+    /// Alerts in this metadata parsed into `Alert`s
+    pub fn typed_alerts(&self) -> Vec<Alert> {
+        self.alerts
+            .iter()
+            .flatten()
+            .map(|alert| Alert::from(alert.as_str()))
+            .collect()
+    }
+}
+
 /// Successful API response that wraps the `AlgoIo` and its Metadata
+#[derive(Debug, Clone, PartialEq)]
 pub struct AlgoResponse {
     /// Any metadata associated with the API response
     pub metadata: AlgoMetadata,
     /// The algorithm output decoded into an `AlgoIo` enum
     pub result: AlgoIo,
+    stats: Option<TransferStats>,
+    options: Option<AlgoOptions>,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+/// Response to an algorithm call submitted without waiting for its result
+///
+/// Returned by [`Algorithm::pipe_async`](struct.Algorithm.html#method.pipe_async).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AsyncResponse {
+    /// How the call is being handled, e.g. `void`
+    #[serde(rename = "async")]
+    pub async_protocol: String,
+    /// Identifier of the submitted request
+    pub request_id: String,
     // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
     _dummy: (),
 }
 
+impl AlgoResponse {
+    /// Byte counts and wall time of the API call that produced this response
+    ///
+    /// Only available for responses returned by `pipe` or `pipe_json`
+    /// (not when parsing a response from a string).
+    pub fn stats(&self) -> Option<TransferStats> {
+        self.stats
+    }
+
+    /// Options sent as query parameters with the call that produced this response
+    ///
+    /// Includes the timeout set with `Algorithm::timeout`. Only available for responses
+    /// returned by `pipe`, `pipe_json`, or `pipe_from_data`.
+    pub fn options(&self) -> Option<&AlgoOptions> {
+        self.options.as_ref()
+    }
+
+    /// Whether the API warned that the account's credit balance is low
+    pub fn has_low_balance_alert(&self) -> bool {
+        self.metadata.typed_alerts().contains(&Alert::LowBalance)
+    }
+
+    /// Whether the API warned that the called algorithm version is deprecated
+    pub fn has_deprecated_version_alert(&self) -> bool {
+        self.metadata
+            .typed_alerts()
+            .contains(&Alert::DeprecatedVersion)
+    }
+}
+
 impl Algorithm {
     #[doc(hidden)]
     pub fn new(client: HttpClient, algo_uri: AlgoUri) -> Algorithm {
         Algorithm {
-            client: client,
-            algo_uri: algo_uri,
+            client,
+            algo_uri,
             options: AlgoOptions::default(),
+            timeout: None,
+            sorted_json: false,
         }
     }
 
     /// Get the API Endpoint URL for this Algorithm
+    ///
+    /// Uses the algorithm base URL if one was configured with
+    /// [`ClientBuilder::algo_base_url`](../struct.ClientBuilder.html#method.algo_base_url).
     pub fn to_url(&self) -> Result<Url, Error> {
+        self.algo_uri.validate()?;
         let path = format!("{}/{}", ALGORITHM_BASE_PATH, self.algo_uri.path);
+        self.client
+            .algo_base_url()
+            .join(&path)
+            .with_context(|| format!("invalid algorithm URI {}", path))
+    }
+
+    /// The `owner/name` portion of the algo URI, ignoring any version
+    fn owner_and_name(&self) -> String {
+        self.algo_uri
+            .path
+            .splitn(3, '/')
+            .take(2)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Get the algorithm management API URL for a resource of this Algorithm
+    ///
+    /// Any version in the algo URI is ignored since management resources belong to the algorithm
+    fn management_url(&self, resource: &str) -> Result<Url, Error> {
+        self.algo_uri.validate()?;
+        let owner_and_name = self.owner_and_name();
+        let path = match resource {
+            "" => format!("{}/{}", ALGORITHMS_MANAGEMENT_PATH, owner_and_name),
+            r => format!("{}/{}/{}", ALGORITHMS_MANAGEMENT_PATH, owner_and_name, r),
+        };
         self.client
             .base_url
             .join(&path)
@@ -152,20 +395,53 @@ impl Algorithm {
     where
         I: Into<AlgoIo>,
     {
-        let mut res = match input_data.into().data {
-            AlgoData::Text(text) => self.pipe_as(text, mime::TEXT_PLAIN)?,
+        let (body, content_type) = self.encode_input(input_data.into())?;
+        self.pipe_encoded(body, content_type)
+    }
+
+    /// Encode input as the request body and its content type
+    fn encode_input(&self, input: AlgoIo) -> Result<(Vec<u8>, Mime), Error> {
+        let encoded = match input.data {
+            AlgoData::Text(text) => (text.into_bytes(), mime::TEXT_PLAIN),
             AlgoData::Json(json) => {
+                let json = if self.sorted_json {
+                    sort_json_keys(json)
+                } else {
+                    json
+                };
                 let encoded = serde_json::to_vec(&json)
                     .context("failed to encode algorithm input as JSON")?;
-                self.pipe_as(encoded, mime::APPLICATION_JSON)?
+                (encoded, mime::APPLICATION_JSON)
             }
-            AlgoData::Binary(bytes) => self.pipe_as(bytes, mime::APPLICATION_OCTET_STREAM)?,
+            AlgoData::Binary(bytes) => (bytes, mime::APPLICATION_OCTET_STREAM),
         };
+        Ok(encoded)
+    }
 
-        let mut res_json = String::new();
-        res.read_to_string(&mut res_json)
-            .context("failed to read algorithm response")?;
-        res_json.parse()
+    fn pipe_encoded(&self, body: Vec<u8>, content_type: Mime) -> Result<AlgoResponse, Error> {
+        let request_bytes = body.len() as u64;
+        self.pipe_parsed(body, content_type, Some(request_bytes))
+    }
+
+    /// Call the algorithm and parse its response, recording the options that were sent
+    fn pipe_parsed<B>(
+        &self,
+        body: B,
+        content_type: Mime,
+        request_bytes: Option<u64>,
+    ) -> Result<AlgoResponse, Error>
+    where
+        B: Into<Body>,
+    {
+        let start = Instant::now();
+        let options = self.call_options(None)?;
+        let url = self.url_with_options(&options)?;
+        let mut res = self.post_input(url, body, content_type)?;
+
+        parse_algo_response(&mut res, start, request_bytes).map(|algo_res| AlgoResponse {
+            options: Some(options),
+            ..algo_res
+        })
     }
 
     /// Execute an algorithm with a raw JSON string as input.
@@ -187,12 +463,97 @@ impl Algorithm {
     /// let output: Vec<u8> = minmax.pipe_json("[2,3,4]")?.decode()?;
     /// # Ok::<(), Box<std::error::Error>>(())
     pub fn pipe_json(&self, json_input: &str) -> Result<AlgoResponse, Error> {
-        let mut res = self.pipe_as(json_input.to_owned(), mime::APPLICATION_JSON)?;
+        self.pipe_parsed(
+            json_input.to_owned(),
+            mime::APPLICATION_JSON,
+            Some(json_input.len() as u64),
+        )
+    }
 
-        let mut res_json = String::new();
-        res.read_to_string(&mut res_json)
-            .context("failed to read algorithm response")?;
-        res_json.parse()
+    /// Execute an algorithm with the contents of a data file as input
+    ///
+    /// The file is streamed from the Data API into the algorithm call without being buffered,
+    /// so large files can be passed through without holding them in memory.
+    /// JSON and text files are sent as JSON and text input; anything else is sent as binary.
+    ///
+    /// Bytes still pass through this client. For algorithms that accept a data URI as input,
+    /// calling `pipe(file.to_data_uri())` lets the algorithm read the file directly instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let image = client.file(".my/photos/large.png");
+    /// let response = client.algo("opencv/SmartThumbnail").pipe_from_data(&image)?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn pipe_from_data(&self, file: &DataFile) -> Result<AlgoResponse, Error> {
+        let (body, size, file_content_type) = file.get_body()?;
+        let content_type = match file_content_type.and_then(|ct| ct.parse::<Mime>().ok()) {
+            Some(ref ct) if ct.subtype() == mime::JSON || ct.suffix() == Some(mime::JSON) => {
+                mime::APPLICATION_JSON
+            }
+            Some(ref ct) if ct.type_() == mime::TEXT => mime::TEXT_PLAIN,
+            _ => mime::APPLICATION_OCTET_STREAM,
+        };
+        self.pipe_parsed(body, content_type, size)
+    }
+
+    /// Execute an algorithm with input streamed from `reader`
+    ///
+    /// The input is sent as it is read, without being buffered in memory,
+    /// so large inputs can be passed to an algorithm. Since the size isn't known
+    /// up front, the request is sent with chunked transfer encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// use std::fs::File;
+    ///
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let video = File::open("/tmp/recording.mp4")?;
+    /// let response = client
+    ///     .algo("demo/Transcribe")
+    ///     .pipe_reader(video, mime::APPLICATION_OCTET_STREAM)?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn pipe_reader<R>(&self, reader: R, content_type: Mime) -> Result<AlgoResponse, Error>
+    where
+        R: Read + Send + 'static,
+    {
+        self.pipe_parsed(Body::new(reader), content_type, None)
+    }
+
+    /// Submit an algorithm call without waiting for it to complete
+    ///
+    /// The call is made with `output=void`, so the API responds as soon as the request
+    /// is accepted and the algorithm's result is discarded. Use this for algorithms
+    /// called for their side effects, e.g. writing to a data file
+    /// (see also [`long_running`](#method.long_running)).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let submitted = client.algo("demo/ReindexSearch").pipe_async("data://.my/docs")?;
+    /// println!("submitted {}", submitted.request_id);
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn pipe_async<I>(&self, input_data: I) -> Result<AsyncResponse, Error>
+    where
+        I: Into<AlgoIo>,
+    {
+        let (body, content_type) = self.encode_input(input_data.into())?;
+        let url = self.call_url_with_output(Some(OutputMode::Void))?;
+        let mut res = self
+            .post_input(url, body, content_type)
+            .and_then(process_http_response)?;
+        res.json::<AsyncResponse>()
+            .context("failed to decode asynchronous algorithm response")
+            .map_err(|err| err.with_response(res.status(), res.headers()))
     }
 
     #[doc(hidden)]
@@ -200,50 +561,102 @@ impl Algorithm {
     where
         B: Into<Body>,
     {
-        // Append options to URL as query parameters
-        let mut url = self.to_url()?;
-        if !self.options.is_empty() {
-            let mut query_params = url.query_pairs_mut();
-            for (k, v) in self.options.iter() {
-                query_params.append_pair(&*k, &*v);
-            }
-        }
+        let url = self.call_url()?;
+        self.post_input(url, input_data, content_type)
+    }
 
-        // We just need the path and query string
+    /// Send input to an algorithm call URL
+    fn post_input<B>(&self, url: Url, input_data: B, content_type: Mime) -> Result<Response, Error>
+    where
+        B: Into<Body>,
+    {
         let mut headers = HeaderMap::new();
         headers.typed_insert(ContentType::from(content_type));
         self.client
-            .post(url)
-            .headers(headers)
-            .body(input_data)
-            .send()
+            .send(self.client.post(url).headers(headers).body(input_data))
             .with_context(|| format!("calling algorithm '{}'", self.algo_uri))
     }
 
+    /// URL for calling this algorithm, with its options as query parameters
+    fn call_url(&self) -> Result<Url, Error> {
+        self.call_url_with_output(None)
+    }
+
+    /// Like `call_url`, but with `output` taking precedence over any configured output mode
+    fn call_url_with_output(&self, output: Option<OutputMode>) -> Result<Url, Error> {
+        let options = self.call_options(output)?;
+        self.url_with_options(&options)
+    }
+
+    /// Validated options sent with a call, including the configured timeout
+    fn call_options(&self, output: Option<OutputMode>) -> Result<AlgoOptions, Error> {
+        let mut options = self.options.clone();
+        if let Some(timeout) = self.timeout {
+            options
+                .opts
+                .insert("timeout".into(), timeout_secs(timeout)?.to_string());
+            options.unchecked.remove("timeout");
+        }
+        if let Some(output) = output {
+            options.output(output);
+        }
+        options
+            .validate()
+            .with_context(|| format!("invalid options for algorithm '{}'", self.algo_uri))?;
+        Ok(options)
+    }
+
+    /// URL for calling this algorithm with `options` as query parameters
+    fn url_with_options(&self, options: &AlgoOptions) -> Result<Url, Error> {
+        let mut url = self.to_url()?;
+        if !options.is_empty() {
+            // Sorted for a stable request URL
+            let mut options: Vec<_> = options.iter().collect();
+            options.sort();
+            let mut query_params = url.query_pairs_mut();
+            for (k, v) in options {
+                query_params.append_pair(k, v);
+            }
+        }
+        Ok(url)
+    }
+
     /// Builder method to explicitly configure options
     pub fn set_options(&mut self, options: AlgoOptions) -> &mut Algorithm {
         self.options = options;
         self
     }
 
-    /// Builder method to configure the timeout in seconds
+    /// Builder method to configure the timeout
+    ///
+    /// The API accepts whole seconds, so partial seconds are rounded up.
+    /// Calling the algorithm fails with an error if the timeout is zero
+    /// or exceeds the platform maximum ([`MAX_TIMEOUT`](constant.MAX_TIMEOUT.html)).
+    /// This takes precedence over any timeout set with `set_options`.
     ///
     /// # Examples
     ///
     /// ```no_run
     /// # use algorithmia::Algorithmia;
+    /// use std::time::Duration;
     ///
     /// let client = Algorithmia::client("111112222233333444445555566")?;
     /// client.algo("codeb34v3r/FindMinMax/0.1")
-    ///     .timeout(3)
+    ///     .timeout(Duration::from_secs(3))
     ///     .pipe(vec![2,3,4])?;
     /// # Ok::<(), Box<std::error::Error>>(())
     /// ```
-    pub fn timeout(&mut self, timeout: u32) -> &mut Algorithm {
-        self.options.timeout(timeout);
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Algorithm {
+        self.timeout = Some(timeout);
         self
     }
 
+    /// Builder method to configure the timeout in seconds
+    #[deprecated(since = "3.0.0", note = "use `timeout` with a `Duration` instead")]
+    pub fn timeout_secs(&mut self, timeout: u32) -> &mut Algorithm {
+        self.timeout(Duration::from_secs(timeout.into()))
+    }
+
     /// Builder method to enabled or disable stdout in the response metadata
     ///
     /// This has no affect unless authenticated as the owner of the algorithm
@@ -251,13 +664,157 @@ impl Algorithm {
         self.options.stdout(stdout);
         self
     }
+
+    /// Builder method to serialize JSON input with object keys sorted
+    ///
+    /// This makes request bodies byte-for-byte stable across runs,
+    /// which is useful for snapshot testing of requests.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use serde_json::json;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// client.algo("anowell/Pinky/0.1")
+    ///     .sorted_json(true)
+    ///     .pipe(json!({"b": 2, "a": 1}))?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn sorted_json(&mut self, sorted_json: bool) -> &mut Algorithm {
+        self.sorted_json = sorted_json;
+        self
+    }
 }
 
 impl AlgoUri {
+    /// Parse and validate an algorithm URI
+    ///
+    /// # Examples
+    /// ```
+    /// # use algorithmia::algo::AlgoUri;
+    /// let uri = AlgoUri::parse("algo://anowell/Pinky/0.1")?;
+    /// assert_eq!(uri.owner(), "anowell");
+    /// assert_eq!(uri.name(), "Pinky");
+    /// assert_eq!(uri.version(), Some("0.1"));
+    ///
+    /// assert!(AlgoUri::parse("anowell").is_err());
+    /// assert!(AlgoUri::parse("data://anowell/Pinky").is_err());
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn parse(uri: &str) -> Result<AlgoUri, Error> {
+        let algo_uri = AlgoUri::from(uri);
+        algo_uri.validate()?;
+        Ok(algo_uri)
+    }
+
+    /// Build and validate the URI of a specific version of an algorithm
+    ///
+    /// `algo` is an `owner/name` URI without a version.
+    ///
+    /// # Examples
+    /// ```
+    /// # use algorithmia::algo::{AlgoUri, Version};
+    /// let uri = AlgoUri::with_version("algo://demo/Hello", Version::Revision(0, 1, 2))?;
+    /// assert_eq!(uri.to_string(), "demo/Hello/0.1.2");
+    /// assert_eq!(uri.typed_version(), Some(Version::Revision(0, 1, 2)));
+    ///
+    /// assert!(AlgoUri::with_version("demo/Hello/0.1", Version::Latest).is_err());
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn with_version(algo: &str, version: Version) -> Result<AlgoUri, Error> {
+        let unversioned = AlgoUri::parse(algo)?;
+        if unversioned.version().is_some() {
+            bail!(
+                "algorithm URI '{}' already includes a version",
+                unversioned.path
+            );
+        }
+        AlgoUri::parse(&format!("{}/{}", unversioned.path, version))
+    }
+
+    pub(crate) fn owned_by(owner_kind: OwnerKind, owner: &str, algo: &str) -> AlgoUri {
+        AlgoUri {
+            owner_kind,
+            ..AlgoUri::from(format!("{}/{}", owner, algo))
+        }
+    }
+
     /// Returns the algorithm's URI path
     pub fn path(&self) -> &str {
         &self.path
     }
+
+    /// Returns the username or organization name that owns the algorithm
+    pub fn owner(&self) -> &str {
+        self.path.split('/').next().unwrap_or("")
+    }
+
+    /// Returns the username or organization name that owns the algorithm
+    ///
+    /// Same as [`owner`](#method.owner).
+    pub fn user(&self) -> &str {
+        self.owner()
+    }
+
+    /// Returns the algorithm name (empty if the URI is invalid)
+    pub fn name(&self) -> &str {
+        self.path.split('/').nth(1).unwrap_or("")
+    }
+
+    /// Returns the version, if the URI includes one
+    pub fn version(&self) -> Option<&str> {
+        self.path.splitn(3, '/').nth(2)
+    }
+
+    /// Returns the version parsed into a `Version`, if the URI includes a valid one
+    pub fn typed_version(&self) -> Option<Version> {
+        self.version().and_then(|v| v.parse().ok())
+    }
+
+    /// Returns whether the algorithm is owned by a user or an organization, if known
+    ///
+    /// Only known for URIs of algorithms instantiated from a
+    /// [`User`](../account/struct.User.html) or [`Org`](../account/struct.Org.html).
+    pub fn owner_kind(&self) -> OwnerKind {
+        self.owner_kind
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if let Some(pos) = self.path.find("://") {
+            bail!(
+                "unsupported scheme '{}://' in algorithm URI '{}' (expected 'algo://')",
+                &self.path[..pos],
+                self.path
+            );
+        }
+        let segments: Vec<_> = self.path.split('/').collect();
+        let valid_segment = |s: &&str| {
+            !matches!(*s, "" | "." | "..")
+                && !s.chars().any(|c| c.is_whitespace() || "?#%\\".contains(c))
+        };
+        if !(2..=3).contains(&segments.len()) || !segments.iter().all(valid_segment) {
+            bail!(
+                "invalid algorithm URI '{}' (expected 'owner/name' or 'owner/name/version')",
+                self.path
+            );
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for AlgoUri {
+    fn eq(&self, other: &AlgoUri) -> bool {
+        self.path == other.path
+    }
+}
+
+impl Eq for AlgoUri {}
+
+impl Hash for AlgoUri {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state)
+    }
 }
 
 impl AlgoIo {
@@ -287,6 +844,14 @@ impl AlgoIo {
         }
     }
 
+    /// Like `to_json`, but with object keys sorted recursively for byte-stable output
+    pub fn to_sorted_json(&self) -> Option<String> {
+        match &self.data {
+            AlgoData::Json(json) => Some(sort_json_keys(json.clone()).to_string()),
+            _ => self.to_json(),
+        }
+    }
+
     /// If the `AlgoIo` is valid JSON, decode it to a particular type
     ///
     pub fn decode<D: DeserializeOwned>(self) -> Result<D, Error> {
@@ -307,7 +872,6 @@ impl Deref for AlgoResponse {
     }
 }
 
-
 // We need our own TryFrom trait because we can't implement
 // the conversions from AlgoIo to any generic DeserializeOwned type until specialization
 #[doc(hidden)]
@@ -341,7 +905,6 @@ impl TryFrom<AlgoIo> for ByteVec {
     }
 }
 
-
 impl AlgoResponse {
     /// If the algorithm output is JSON, decode it into a particular type
     pub fn decode<D>(self) -> Result<D, Error>
@@ -353,23 +916,113 @@ impl AlgoResponse {
 }
 
 impl AlgoOptions {
+    /// Create an empty set of options
+    pub fn new() -> AlgoOptions {
+        AlgoOptions::default()
+    }
+
     /// Configure timeout in seconds
-    pub fn timeout(&mut self, timeout: u32) {
-        self.opts.insert("timeout".into(), timeout.to_string());
+    ///
+    /// Must be greater than zero and at most [`MAX_TIMEOUT`](constant.MAX_TIMEOUT.html).
+    pub fn timeout(&mut self, timeout: u32) -> &mut AlgoOptions {
+        self.param("timeout", timeout.to_string())
     }
 
     /// Enable or disable stdout retrieval
     ///
     /// This has no affect unless authenticated as the owner of the algorithm
-    pub fn stdout(&mut self, stdout: bool) {
-        self.opts.insert("stdout".into(), stdout.to_string());
+    pub fn stdout(&mut self, stdout: bool) -> &mut AlgoOptions {
+        self.param("stdout", stdout.to_string())
+    }
+
+    /// Configure how the API returns the algorithm's result
+    ///
+    /// Calls that parse the response, like `pipe`, can't decode raw or void output; use
+    /// [`Algorithm::pipe_raw_output`](struct.Algorithm.html#method.pipe_raw_output) or
+    /// [`Algorithm::pipe_async`](struct.Algorithm.html#method.pipe_async) instead.
+    pub fn output(&mut self, output: OutputMode) -> &mut AlgoOptions {
+        self.param("output", output.as_str())
+    }
+
+    /// Request the algorithm's result as the unparsed response body (`output=raw`)
+    pub fn output_raw(&mut self) -> &mut AlgoOptions {
+        self.output(OutputMode::Raw)
+    }
+
+    /// Set a query parameter, validated like the typed setters
+    ///
+    /// Values for `timeout`, `stdout`, and `output` must be valid for that option,
+    /// and other keys may only contain ASCII letters, digits, `_`, and `-`.
+    pub fn param<K, V>(&mut self, key: K, value: V) -> &mut AlgoOptions
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let key = key.into();
+        self.unchecked.remove(&key);
+        self.opts.insert(key, value.into());
+        self
+    }
+
+    /// Set a query parameter that is sent exactly as given, without validation
+    pub fn custom<K, V>(&mut self, key: K, value: V) -> &mut AlgoOptions
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let key = key.into();
+        self.unchecked.insert(key.clone());
+        self.opts.insert(key, value.into());
+        self
+    }
+
+    /// Check that every option other than those set with `custom` is valid
+    pub fn validate(&self) -> Result<(), Error> {
+        for (key, value) in &self.opts {
+            if self.unchecked.contains(key) {
+                continue;
+            }
+            match key.as_str() {
+                "timeout" => match value.parse::<u64>() {
+                    Ok(secs) if secs > 0 && secs <= MAX_TIMEOUT.as_secs() => (),
+                    _ => bail!(
+                        "timeout must be a whole number of seconds from 1 to {}, got '{}'",
+                        MAX_TIMEOUT.as_secs(),
+                        value
+                    ),
+                },
+                "stdout" => {
+                    if value != "true" && value != "false" {
+                        bail!("stdout must be 'true' or 'false', got '{}'", value);
+                    }
+                }
+                "output" => {
+                    if value != "raw" && value != "void" {
+                        bail!("output must be 'raw' or 'void', got '{}'", value);
+                    }
+                }
+                key => {
+                    let valid = !key.is_empty()
+                        && key
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+                    if !valid {
+                        bail!("invalid option name '{}'", key);
+                    }
+                }
+            }
+        }
+        Ok(())
     }
 }
 
-impl Default for AlgoOptions {
-    fn default() -> AlgoOptions {
-        AlgoOptions {
-            opts: HashMap::new(),
+impl OutputMode {
+    /// Value of the `output` query parameter for this mode
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutputMode::Raw => "raw",
+            OutputMode::Void => "void",
+            OutputMode::__Nonexhaustive => "unknown",
         }
     }
 }
@@ -387,55 +1040,135 @@ impl DerefMut for AlgoOptions {
     }
 }
 
-impl FromStr for AlgoResponse {
-    type Err = Error;
-    fn from_str(json_str: &str) -> ::std::result::Result<Self, Self::Err> {
-        // Early return if the response decodes into ApiErrorResponse
-        if let Ok(err_res) = serde_json::from_str::<ApiErrorResponse>(json_str) {
-            return Err(err_res.error.into());
-        }
+/// Validate a timeout and convert it to the whole seconds expected by the API
+fn timeout_secs(timeout: Duration) -> Result<u64, Error> {
+    if timeout == Duration::from_secs(0) {
+        bail!("algorithm timeout must be greater than zero");
+    }
+    if timeout > MAX_TIMEOUT {
+        bail!(
+            "algorithm timeout of {:?} exceeds the platform maximum of {:?}",
+            timeout,
+            MAX_TIMEOUT
+        );
+    }
+    Ok(timeout.as_secs() + u64::from(timeout.subsec_nanos() > 0))
+}
 
-        // Parse into Json object
-        let mut data =
-            Value::from_str(json_str).context("failed to decode JSON as algorithm response")?;
-        let metadata_value = data
-            .as_object_mut()
-            .and_then(|o| o.remove("metadata"))
-            .ok_or_else(|| serde_json::Error::missing_field("metadata"))
-            .context("failed to decode JSON as algorithm response")?;
-        let result_value = data
-            .as_object_mut()
-            .and_then(|o| o.remove("result"))
-            .ok_or_else(|| serde_json::Error::missing_field("result"))
-            .context("failed to decode JSON as algorithm response")?;
-
-        // Construct the AlgoIo object
-        let metadata = serde_json::from_value::<AlgoMetadata>(metadata_value)
-            .context("failed to decode JSON as algorithm response metadata")?;
-        let data = match (&*metadata.content_type, result_value) {
-            ("void", _) => AlgoData::Json(Value::Null),
-            ("json", value) => AlgoData::Json(value),
-            ("text", value) => match value.as_str() {
-                Some(text) => AlgoData::Text(text.into()),
-                None => bail!("content did not match content type 'text'"),
-            },
-            ("binary", value) => match value.as_str() {
-                Some(text) => {
-                    let binary = base64::decode(text)
-                        .context("failed to decode base64 as algorithm response")?;
-                    AlgoData::Binary(binary)
+/// Response buffers larger than this aren't kept for reuse
+const MAX_RETAINED_BUFFER: usize = 1024 * 1024;
+
+thread_local! {
+    /// Buffer reused to read algorithm responses, avoiding an allocation per call
+    static RESPONSE_BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Read an algorithm call response, attaching response details to any error
+fn parse_algo_response(
+    res: &mut Response,
+    start: Instant,
+    request_bytes: Option<u64>,
+) -> Result<AlgoResponse, Error> {
+    RESPONSE_BUFFER.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+        buffer.clear();
+        let parsed = res
+            .read_to_end(&mut buffer)
+            .context("failed to read algorithm response")
+            .and_then(|_| decode_algo_response(&buffer))
+            .map(|algo_res| AlgoResponse {
+                stats: Some(TransferStats::new(
+                    request_bytes,
+                    buffer.len() as u64,
+                    start.elapsed(),
+                )),
+                ..algo_res
+            })
+            .map_err(|err| {
+                let is_api_error = err.api_error().is_some();
+                let err = err.with_response(res.status(), res.headers());
+                if is_api_error {
+                    err
+                } else {
+                    err.with_body_snippet(&buffer)
                 }
-                None => bail!("content did not match content type 'binary'"),
-            },
-            (content_type, _) => bail!("content did not match content type '{}'", content_type),
-        };
+            });
+        if buffer.capacity() > MAX_RETAINED_BUFFER {
+            *buffer = Vec::new();
+        }
+        parsed
+    })
+}
 
-        // Construct the AlgoResponse object
-        Ok(AlgoResponse {
-            metadata: metadata,
-            result: AlgoIo { data },
-            _dummy: (),
-        })
+/// Algorithm response or error, decoded in a single pass
+#[derive(Deserialize)]
+struct RawAlgoResponse {
+    #[serde(default)]
+    error: Option<Value>,
+    metadata: Option<AlgoMetadata>,
+    // Distinguishes a `null` result from a missing one
+    #[serde(default, deserialize_with = "deserialize_present")]
+    result: Option<Value>,
+}
+
+fn deserialize_present<'de, D>(deserializer: D) -> Result<Option<Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Value::deserialize(deserializer).map(Some)
+}
+
+fn decode_algo_response(json: &[u8]) -> Result<AlgoResponse, Error> {
+    let raw: RawAlgoResponse =
+        serde_json::from_slice(json).context("failed to decode JSON as algorithm response")?;
+
+    if let Some(error) = raw.error {
+        if let Ok(api_error) = serde_json::from_value::<ApiError>(error) {
+            return Err(api_error.into());
+        }
+    }
+    let metadata = raw
+        .metadata
+        .ok_or_else(|| serde_json::Error::missing_field("metadata"))
+        .context("failed to decode JSON as algorithm response")?;
+    let result_value = raw
+        .result
+        .ok_or_else(|| serde_json::Error::missing_field("result"))
+        .context("failed to decode JSON as algorithm response")?;
+
+    // Construct the AlgoIo object
+    let data = match (&*metadata.content_type, result_value) {
+        ("void", _) => AlgoData::Json(Value::Null),
+        ("json", value) => AlgoData::Json(value),
+        ("text", value) => match value.as_str() {
+            Some(text) => AlgoData::Text(text.into()),
+            None => bail!("content did not match content type 'text'"),
+        },
+        ("binary", value) => match value.as_str() {
+            Some(text) => {
+                let binary = base64::decode(text)
+                    .context("failed to decode base64 as algorithm response")?;
+                AlgoData::Binary(binary)
+            }
+            None => bail!("content did not match content type 'binary'"),
+        },
+        (content_type, _) => bail!("content did not match content type '{}'", content_type),
+    };
+
+    // Construct the AlgoResponse object
+    Ok(AlgoResponse {
+        metadata,
+        result: AlgoIo { data },
+        stats: None,
+        options: None,
+        _dummy: (),
+    })
+}
+
+impl FromStr for AlgoResponse {
+    type Err = Error;
+    fn from_str(json_str: &str) -> ::std::result::Result<Self, Self::Err> {
+        decode_algo_response(json_str.as_bytes())
     }
 }
 
@@ -474,10 +1207,21 @@ impl<'a> From<&'a str> for AlgoUri {
         };
         AlgoUri {
             path: path.to_owned(),
+            owner_kind: OwnerKind::Unknown,
         }
     }
 }
 
+impl<'a> From<(&'a str, Version)> for AlgoUri {
+    /// Build the URI of a specific version of an algorithm without validating it
+    ///
+    /// Use [`AlgoUri::with_version`](struct.AlgoUri.html#method.with_version) to validate up front.
+    fn from((algo, version): (&'a str, Version)) -> Self {
+        let algo = AlgoUri::from(algo);
+        AlgoUri::from(format!("{}/{}", algo.path, version))
+    }
+}
+
 impl From<String> for AlgoUri {
     fn from(path: String) -> Self {
         let path = match path {
@@ -485,7 +1229,31 @@ impl From<String> for AlgoUri {
             ref p if p.starts_with('/') => p[1..].to_owned(),
             p => p,
         };
-        AlgoUri { path: path }
+        AlgoUri {
+            path,
+            owner_kind: OwnerKind::Unknown,
+        }
+    }
+}
+
+/// Rebuild JSON objects with keys inserted in sorted order
+///
+/// `serde_json::Map` is only guaranteed to be sorted when the `preserve_order`
+/// feature is disabled, which another crate in the dependency graph may enable.
+fn sort_json_keys(json: Value) -> Value {
+    match json {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sort_json_keys(v)))
+                    .collect(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_json_keys).collect()),
+        other => other,
     }
 }
 
@@ -519,6 +1287,105 @@ mod tests {
         Algorithmia::client("").unwrap()
     }
 
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_algo_input_from() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Params {
+            url: String,
+        }
+
+        #[derive(Debug, PartialEq, AlgoInputFrom)]
+        enum Input {
+            Url(String),
+            Raw(ByteVec),
+            Params(Params),
+        }
+
+        let input = Input::try_from(AlgoIo::from("http://example.com")).unwrap();
+        assert_eq!(input, Input::Url("http://example.com".into()));
+        let input = Input::try_from(AlgoIo::from(ByteVec::from(vec![1, 2]))).unwrap();
+        assert_eq!(input, Input::Raw(ByteVec::from(vec![1, 2])));
+        let input = Input::try_from(AlgoIo::from(json!({"url": "x"}))).unwrap();
+        assert_eq!(input, Input::Params(Params { url: "x".into() }));
+
+        let err = Input::try_from(AlgoIo::from(json!([1]))).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("input did not match any variant of Input"));
+    }
+
+    #[test]
+    fn test_timeout_secs() {
+        assert_eq!(timeout_secs(Duration::from_secs(3)).unwrap(), 3);
+        assert_eq!(timeout_secs(Duration::from_millis(1500)).unwrap(), 2);
+        assert_eq!(timeout_secs(MAX_TIMEOUT).unwrap(), 3000);
+        assert!(timeout_secs(Duration::from_secs(0)).is_err());
+        assert!(timeout_secs(MAX_TIMEOUT + Duration::from_millis(1)).is_err());
+    }
+
+    #[test]
+    fn test_algo_options_validation() {
+        let mut options = AlgoOptions::new();
+        options
+            .timeout(3000)
+            .stdout(false)
+            .output(OutputMode::Raw)
+            .param("seed", "42");
+        assert!(options.validate().is_ok());
+
+        assert!(AlgoOptions::new().timeout(0).validate().is_err());
+        assert!(AlgoOptions::new().timeout(3001).validate().is_err());
+        assert!(AlgoOptions::new()
+            .param("stdout", "yes")
+            .validate()
+            .is_err());
+        assert!(AlgoOptions::new()
+            .param("output", "json")
+            .validate()
+            .is_err());
+        assert!(AlgoOptions::new()
+            .output(OutputMode::__Nonexhaustive)
+            .validate()
+            .is_err());
+        assert!(AlgoOptions::new().param("a&b", "1").validate().is_err());
+        assert!(AlgoOptions::new()
+            .custom("output", "json")
+            .validate()
+            .is_ok());
+        assert!(AlgoOptions::new()
+            .custom("output", "json")
+            .param("output", "json")
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_invalid_options_fail_call() {
+        let mut options = AlgoOptions::new();
+        options.param("stdout", "yes");
+        let mut algo = mock_client().algo("anowell/Pinky");
+        algo.set_options(options);
+        assert!(algo.build_request("Brain").is_err());
+
+        // The typed algorithm timeout replaces an invalid timeout option
+        let mut options = AlgoOptions::new();
+        options.param("timeout", "soon").custom("beta", "x=1");
+        algo.set_options(options);
+        algo.timeout(Duration::from_secs(5));
+        let call = algo.build_request("Brain").unwrap();
+        assert_eq!(call.url().query(), Some("beta=x%3D1&timeout=5"));
+    }
+
+    #[test]
+    fn test_to_sorted_json() {
+        let input = AlgoIo::from(json!({"b": [{"d": 1, "c": 2}], "a": null}));
+        assert_eq!(
+            input.to_sorted_json().unwrap(),
+            r#"{"a":null,"b":[{"c":2,"d":1}]}"#
+        );
+    }
+
     #[test]
     fn test_algo_without_version_to_url() {
         let mock_client = mock_client();
@@ -556,13 +1423,247 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_algo_uri_parse() {
+        let uri = AlgoUri::parse("/anowell/Pinky").unwrap();
+        assert_eq!(
+            (uri.owner(), uri.name(), uri.version()),
+            ("anowell", "Pinky", None)
+        );
+        assert_eq!(uri.owner_kind(), OwnerKind::Unknown);
+
+        for invalid in &[
+            "anowell",
+            "anowell//0.1",
+            "a/b/c/d",
+            "anowell/Pin ky",
+            "s3://a/b",
+            "../Pinky",
+            "anowell/..",
+            "anowell/./0.1",
+            "anowell/Pinky/..",
+        ] {
+            assert!(AlgoUri::parse(invalid).is_err(), "{}", invalid);
+        }
+        let err = mock_client()
+            .algo("data://anowell/Pinky")
+            .to_url()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported scheme 'data://' in algorithm URI 'data://anowell/Pinky' (expected 'algo://')"
+        );
+
+        let org_algo = mock_client().org("acme").algo("Classifier/1.0");
+        assert_eq!(org_algo.to_algo_uri().owner_kind(), OwnerKind::Org);
+        assert_eq!(
+            org_algo.to_algo_uri(),
+            &AlgoUri::from("acme/Classifier/1.0")
+        );
+    }
+
+    #[test]
+    fn test_algo_uri_with_version() {
+        let uri = AlgoUri::with_version("demo/Hello", Version::Minor(0, 1)).unwrap();
+        assert_eq!(
+            (uri.user(), uri.name(), uri.version()),
+            ("demo", "Hello", Some("0.1"))
+        );
+        assert_eq!(uri, AlgoUri::from(("/demo/Hello", Version::Minor(0, 1))));
+        assert_eq!(
+            AlgoUri::from("demo/Hello/abcdef123456").typed_version(),
+            Some(Version::Hash("abcdef123456".into()))
+        );
+        assert_eq!(AlgoUri::from("demo/Hello").typed_version(), None);
+
+        assert!(AlgoUri::with_version("demo", Version::Latest).is_err());
+        assert!(AlgoUri::with_version("demo/Hello/0.1", Version::Latest).is_err());
+        assert!(AlgoUri::with_version("demo/Hello", Version::Hash("a b".into())).is_err());
+    }
+
+    #[test]
+    fn test_algo_management_url() {
+        let mock_client = mock_client();
+        let algorithm = mock_client.algo("algo://anowell/Pinky/0.1");
+        assert_eq!(
+            algorithm.management_url("versions").unwrap().path(),
+            "/v1/algorithms/anowell/Pinky/versions"
+        );
+        assert_eq!(
+            algorithm.management_url("").unwrap().path(),
+            "/v1/algorithms/anowell/Pinky"
+        );
+    }
+
     #[test]
     fn test_json_decoding() {
         let json_output =
             r#"{"metadata":{"duration":0.46739511,"content_type":"json"},"result":[5,41]}"#;
         let expected_result = [5, 41];
         let decoded = json_output.parse::<AlgoResponse>().unwrap();
-        assert_eq!(0.46739511f32, decoded.metadata.duration);
+        assert_eq!(0.467_395_1_f32, decoded.metadata.duration);
         assert_eq!(expected_result, &*decoded.decode::<Vec<i32>>().unwrap());
     }
+
+    #[test]
+    fn test_response_decoding() {
+        let null_result = r#"{"metadata":{"duration":0.1,"content_type":"json"},"result":null}"#;
+        let decoded = null_result.parse::<AlgoResponse>().unwrap();
+        assert_eq!(decoded.result.to_json(), Some("null".into()));
+
+        let missing_result = r#"{"metadata":{"duration":0.1,"content_type":"json"}}"#;
+        let err = missing_result.parse::<AlgoResponse>().unwrap_err();
+        assert!(format!("{:?}", err).contains("result"));
+
+        let api_error = r#"{"error":{"message":"algorithm not found"}}"#;
+        let err = api_error.parse::<AlgoResponse>().unwrap_err();
+        assert_eq!(err.api_error().unwrap().message, "algorithm not found");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_pipe_from_data() {
+        use crate::testing::{FakeResponse, FakeServer};
+
+        let server = FakeServer::start().unwrap();
+        server.route(
+            "GET",
+            "/v1/connector/data/.my/in/input.json",
+            FakeResponse::status(200)
+                .with_header("X-Data-Type", "file")
+                .with_header("Content-Type", "application/json")
+                .with_body(r#"{"values": [1, 2, 3]}"#),
+        );
+        server.data_file("data://.my/in/image.png", b"\x89PNG".to_vec());
+        server.algo_result("demo/Sum", json!(6));
+        let client = server.client().unwrap();
+        let algo = client.algo("demo/Sum");
+
+        let response = algo
+            .pipe_from_data(&client.file("data://.my/in/input.json"))
+            .unwrap();
+        assert_eq!(response.decode::<u32>().unwrap(), 6);
+        let request = &server.requests()[1];
+        assert_eq!(request.url, "/v1/algo/demo/Sum");
+        assert_eq!(request.header("Content-Type"), Some("application/json"));
+        assert_eq!(request.body_string(), r#"{"values": [1, 2, 3]}"#);
+
+        algo.pipe_from_data(&client.file("data://.my/in/image.png"))
+            .unwrap();
+        let request = &server.requests()[3];
+        assert_eq!(
+            request.header("Content-Type"),
+            Some("application/octet-stream")
+        );
+        assert_eq!(request.body, b"\x89PNG");
+
+        let err = algo
+            .pipe_from_data(&client.file("data://.my/in/missing.txt"))
+            .err()
+            .unwrap();
+        assert!(err.data_error().is_some());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_pipe_reader() {
+        use crate::testing::FakeServer;
+
+        let server = FakeServer::start().unwrap();
+        server.algo_result("demo/Length", json!(100_000));
+        let client = server.client().unwrap();
+        let input: Vec<u8> = (0..100_000).map(|i| i as u8).collect();
+
+        let response = client
+            .algo("demo/Length")
+            .pipe_reader(
+                io::Cursor::new(input.clone()),
+                mime::APPLICATION_OCTET_STREAM,
+            )
+            .unwrap();
+        assert_eq!(response.stats().unwrap().request_bytes, None);
+        assert_eq!(response.decode::<u32>().unwrap(), 100_000);
+        let request = &server.requests()[0];
+        assert_eq!(
+            request.header("Content-Type"),
+            Some("application/octet-stream")
+        );
+        assert_eq!(request.body, input);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_pipe_async() {
+        use crate::testing::{FakeResponse, FakeServer};
+
+        let server = FakeServer::start().unwrap();
+        server.algo_response(
+            "demo/Reindex",
+            FakeResponse::json(200, &json!({"async": "void", "request_id": "req-1"})),
+        );
+        let client = server.client().unwrap();
+        let mut algo = client.algo("demo/Reindex");
+        algo.timeout(Duration::from_secs(5));
+        algo.options.insert("output".into(), "raw".into());
+
+        let submitted = algo.pipe_async("go").unwrap();
+        assert_eq!(submitted.async_protocol, "void");
+        assert_eq!(submitted.request_id, "req-1");
+        assert_eq!(
+            server.requests()[0].url,
+            "/v1/algo/demo/Reindex?output=void&timeout=5"
+        );
+
+        let err = client.algo("demo/Missing").pipe_async("go").err().unwrap();
+        assert_eq!(err.status(), Some(http::StatusCode::NOT_FOUND));
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_response_options() {
+        use crate::testing::FakeServer;
+
+        let server = FakeServer::start().unwrap();
+        server.algo_result("demo/Hello", json!("hi"));
+        let client = server.client().unwrap();
+        let mut algo = client.algo("demo/Hello");
+        algo.stdout(true).timeout(Duration::from_secs(10));
+
+        let response = algo.pipe("world").unwrap();
+        let options = response.options().unwrap();
+        assert_eq!(options.get("stdout").map(String::as_str), Some("true"));
+        assert_eq!(options.get("timeout").map(String::as_str), Some("10"));
+
+        let parsed: AlgoResponse =
+            r#"{"metadata": {"duration": 0.1, "content_type": "text"}, "result": "hi"}"#
+                .parse()
+                .unwrap();
+        assert!(parsed.options().is_none());
+    }
+
+    #[test]
+    fn test_alerts() {
+        let json_output = r#"{
+            "metadata": {
+                "duration": 0.1,
+                "content_type": "json",
+                "alerts": ["Your account balance is low", "Maintenance scheduled"]
+            },
+            "result": null
+        }"#;
+        let decoded = json_output.parse::<AlgoResponse>().unwrap();
+        assert_eq!(
+            decoded.metadata.typed_alerts(),
+            vec![
+                Alert::LowBalance,
+                Alert::Other("Maintenance scheduled".into())
+            ]
+        );
+        assert!(decoded.has_low_balance_alert());
+        assert!(!decoded.has_deprecated_version_alert());
+        assert_eq!(
+            Alert::from("Version 0.1.0 is deprecated"),
+            Alert::DeprecatedVersion
+        );
+    }
 }
@@ -0,0 +1,141 @@
+use super::{AlgoIo, Algorithm};
+use crate::error::Error;
+
+use headers_ext::{ContentType, HeaderMapExt};
+use http::header::{HeaderMap, AUTHORIZATION};
+use mime::Mime;
+use reqwest::Url;
+
+use std::fmt;
+
+/// Fully encoded algorithm call that has not been sent
+///
+/// Exposes exactly what [`pipe`](struct.Algorithm.html#method.pipe) would send,
+/// so calls can be logged, verified in tests, or replayed with other tools like `curl`.
+/// The headers include the `Authorization` header with the API key,
+/// which the `Debug` output redacts.
+///
+/// Created with [`Algorithm::build_request`](struct.Algorithm.html#method.build_request).
+#[derive(Clone)]
+pub struct PreparedCall {
+    url: Url,
+    headers: HeaderMap,
+    content_type: Mime,
+    body: Vec<u8>,
+}
+
+impl Algorithm {
+    /// Encode a call to this algorithm without sending it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let mut algo = client.algo("demo/Hello/0.1.1");
+    /// algo.timeout(std::time::Duration::from_secs(10));
+    ///
+    /// let call = algo.build_request("world")?;
+    /// assert_eq!(call.url().as_str(), "https://api.algorithmia.com/v1/algo/demo/Hello/0.1.1?timeout=10");
+    /// assert_eq!(call.content_type(), &mime::APPLICATION_JSON);
+    /// assert_eq!(call.body(), br#""world""#);
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn build_request<I: Into<AlgoIo>>(&self, input_data: I) -> Result<PreparedCall, Error> {
+        let (body, content_type) = self.encode_input(input_data.into())?;
+        let mut headers = self.client.request_headers();
+        headers.typed_insert(ContentType::from(content_type.clone()));
+        Ok(PreparedCall {
+            url: self.call_url()?,
+            headers,
+            content_type,
+            body,
+        })
+    }
+}
+
+impl PreparedCall {
+    /// HTTP method of the call, which is always `POST`
+    pub fn method(&self) -> &str {
+        "POST"
+    }
+
+    /// URL of the call, including options as query parameters
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Every header sent with the call, including `Authorization` and `Content-Type`
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Content type of the body
+    pub fn content_type(&self) -> &Mime {
+        &self.content_type
+    }
+
+    /// Encoded request body
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Consume the call, returning the encoded request body
+    pub fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
+
+impl fmt::Debug for PreparedCall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let headers: Vec<_> = self
+            .headers
+            .iter()
+            .map(|(name, value)| match name {
+                n if n == AUTHORIZATION => (name.as_str(), "[redacted]".into()),
+                _ => (name.as_str(), String::from_utf8_lossy(value.as_bytes())),
+            })
+            .collect();
+        f.debug_struct("PreparedCall")
+            .field("url", &self.url.as_str())
+            .field("headers", &headers)
+            .field("content_type", &self.content_type.as_ref())
+            .field("body_len", &self.body.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Algorithmia;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_request() {
+        let client = Algorithmia::client("111112222233333444445555566").unwrap();
+        let mut algo = client.algo("demo/Hello");
+        algo.stdout(true);
+
+        let call = algo.build_request(json!({"b": 1, "a": 2})).unwrap();
+        assert_eq!(call.method(), "POST");
+        assert_eq!(
+            call.url().as_str(),
+            "https://api.algorithmia.com/v1/algo/demo/Hello?stdout=true"
+        );
+        assert_eq!(call.content_type(), &mime::APPLICATION_JSON);
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(call.body()).unwrap(),
+            json!({"a": 2, "b": 1})
+        );
+        assert_eq!(
+            call.headers()["authorization"],
+            "Simple 111112222233333444445555566"
+        );
+        assert_eq!(call.headers()["content-type"], "application/json");
+        assert!(call.headers().contains_key("user-agent"));
+
+        let debug = format!("{:?}", call);
+        assert!(debug.contains("[redacted]"));
+        assert!(!debug.contains("111112222233333444445555566"));
+    }
+}
@@ -0,0 +1,110 @@
+use super::{AlgoIo, Algorithm, OutputMode};
+use crate::error::{process_http_response, Error, ResultExt};
+
+use http::header::CONTENT_TYPE;
+use reqwest::Response;
+
+use std::io::{self, Read};
+
+/// Unparsed result of an algorithm call made with `output=raw`
+///
+/// The API returns the algorithm's result as the response body instead of wrapping it
+/// in a JSON envelope, so binary results can be streamed without base64 decoding.
+/// Implements `Read`, so the result can be copied straight to a file with `io::copy`.
+///
+/// Created with [`Algorithm::pipe_raw_output`](struct.Algorithm.html#method.pipe_raw_output).
+pub struct RawResponse {
+    content_type: Option<String>,
+    response: Response,
+}
+
+impl Algorithm {
+    /// Execute an algorithm and return its result without parsing it
+    ///
+    /// The call is made with `output=raw`, taking precedence over any configured output mode.
+    /// No metadata (e.g. duration or stdout) is returned in this mode.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// use std::fs::File;
+    /// use std::io;
+    ///
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let mut thumbnail = client
+    ///     .algo("opencv/SmartThumbnail")
+    ///     .pipe_raw_output("data://.my/photos/large.png")?;
+    /// io::copy(&mut thumbnail, &mut File::create("thumbnail.png")?)?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn pipe_raw_output<I>(&self, input_data: I) -> Result<RawResponse, Error>
+    where
+        I: Into<AlgoIo>,
+    {
+        let (body, content_type) = self.encode_input(input_data.into())?;
+        let url = self.call_url_with_output(Some(OutputMode::Raw))?;
+        let response = self
+            .post_input(url, body, content_type)
+            .and_then(process_http_response)?;
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|ct| ct.to_str().ok())
+            .map(ToOwned::to_owned);
+        Ok(RawResponse {
+            content_type,
+            response,
+        })
+    }
+}
+
+impl RawResponse {
+    /// Content type of the result from the `Content-Type` header, if any
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Read the rest of the result into memory
+    pub fn into_bytes(mut self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::new();
+        self.response
+            .read_to_end(&mut bytes)
+            .context("failed to read raw algorithm response")?;
+        Ok(bytes)
+    }
+}
+
+impl Read for RawResponse {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.response.read(buf)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use crate::testing::{FakeResponse, FakeServer};
+
+    #[test]
+    fn test_pipe_raw_output() {
+        let server = FakeServer::start().unwrap();
+        server.algo_response(
+            "demo/Thumbnail",
+            FakeResponse::status(200)
+                .with_header("Content-Type", "image/png")
+                .with_body(b"\x89PNG".to_vec()),
+        );
+        let client = server.client().unwrap();
+
+        let raw = client.algo("demo/Thumbnail").pipe_raw_output("in").unwrap();
+        assert_eq!(raw.content_type(), Some("image/png"));
+        assert_eq!(raw.into_bytes().unwrap(), b"\x89PNG");
+        assert_eq!(
+            server.requests()[0].url,
+            "/v1/algo/demo/Thumbnail?output=raw"
+        );
+
+        let err = client.algo("demo/Missing").pipe_raw_output("in").err();
+        assert!(err.is_some());
+    }
+}
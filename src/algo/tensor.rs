@@ -0,0 +1,251 @@
+use super::{AlgoData, AlgoIo, TryFrom};
+use crate::error::{err_msg, Error};
+
+use ndarray::{ArrayD, IxDyn};
+use serde_json::{Number, Value};
+
+use std::convert::TryInto;
+
+/// Magic bytes at the start of a binary-encoded `Tensor`
+pub const TENSOR_MAGIC: &[u8; 4] = b"ALGT";
+
+/// Wrapper around `ArrayD<f64>` for converting to/from `AlgoIo` as nested JSON arrays [feature = "ndarray"]
+///
+/// An array of shape `[2, 3]` is encoded as `[[a, b, c], [d, e, f]]`, and a 0-dimensional
+/// array as a single number. This matches the nested lists most algorithms accept.
+///
+/// # Examples
+///
+/// ```no_run
+/// use algorithmia::Algorithmia;
+/// use algorithmia::algo::JsonArray;
+/// use ndarray::ArrayD;
+///
+/// let client = Algorithmia::client("111112222233333444445555566")?;
+/// let input = ArrayD::<f64>::zeros(ndarray::IxDyn(&[2, 3]));
+/// let output = client.algo("demo/Transpose").pipe(JsonArray(input))?;
+/// let transposed = output.to_array()?;
+/// # Ok::<(), Box<std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonArray(pub ArrayD<f64>);
+
+/// Wrapper around `ArrayD<f32>` for converting to/from `AlgoIo` as compact binary [feature = "ndarray"]
+///
+/// The encoding is the 4 bytes of [`TENSOR_MAGIC`](constant.TENSOR_MAGIC.html), the number
+/// of dimensions as a little-endian `u32`, each dimension as a little-endian `u64`, then every
+/// element as a little-endian `f32` in row-major order. Both sides of the call must agree on
+/// this format, so it suits algorithms written against this crate or a matching decoder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tensor(pub ArrayD<f32>);
+
+impl AlgoIo {
+    /// If the `AlgoIo` is JSON of nested, rectangular number arrays, convert it to an array [feature = "ndarray"]
+    pub fn to_array(&self) -> Result<ArrayD<f64>, Error> {
+        match &self.data {
+            AlgoData::Json(json) => json_to_array(json),
+            AlgoData::Text(_) => bail!("cannot convert text to an array"),
+            AlgoData::Binary(_) => bail!("cannot convert binary data to a JSON array"),
+        }
+    }
+
+    /// If the `AlgoIo` is a binary-encoded `Tensor`, decode it [feature = "ndarray"]
+    pub fn to_tensor(&self) -> Result<ArrayD<f32>, Error> {
+        match &self.data {
+            AlgoData::Binary(bytes) => decode_tensor(bytes),
+            AlgoData::Text(_) => bail!("cannot convert text to a tensor"),
+            AlgoData::Json(_) => bail!("cannot convert JSON to a tensor"),
+        }
+    }
+}
+
+impl From<JsonArray> for AlgoIo {
+    fn from(array: JsonArray) -> Self {
+        let data = AlgoData::Json(array_to_json(&array.0));
+        AlgoIo { data }
+    }
+}
+
+impl From<Tensor> for AlgoIo {
+    fn from(tensor: Tensor) -> Self {
+        let data = AlgoData::Binary(encode_tensor(&tensor.0));
+        AlgoIo { data }
+    }
+}
+
+impl TryFrom<AlgoIo> for JsonArray {
+    type Error = Error;
+    fn try_from(val: AlgoIo) -> Result<Self, Self::Error> {
+        val.to_array().map(JsonArray)
+    }
+}
+
+impl TryFrom<AlgoIo> for Tensor {
+    type Error = Error;
+    fn try_from(val: AlgoIo) -> Result<Self, Self::Error> {
+        val.to_tensor().map(Tensor)
+    }
+}
+
+impl From<JsonArray> for ArrayD<f64> {
+    fn from(array: JsonArray) -> ArrayD<f64> {
+        array.0
+    }
+}
+
+impl From<Tensor> for ArrayD<f32> {
+    fn from(tensor: Tensor) -> ArrayD<f32> {
+        tensor.0
+    }
+}
+
+fn array_to_json(array: &ArrayD<f64>) -> Value {
+    fn nest(shape: &[usize], values: &mut dyn Iterator<Item = f64>) -> Value {
+        match shape.split_first() {
+            None => values
+                .next()
+                .and_then(Number::from_f64)
+                .map_or(Value::Null, Value::Number),
+            Some((&len, rest)) => Value::Array((0..len).map(|_| nest(rest, values)).collect()),
+        }
+    }
+    // `iter` visits elements in logical row-major order regardless of memory layout
+    nest(array.shape(), &mut array.iter().cloned())
+}
+
+fn json_to_array(json: &Value) -> Result<ArrayD<f64>, Error> {
+    let mut shape = Vec::new();
+    let mut level = json;
+    while let Value::Array(items) = level {
+        shape.push(items.len());
+        match items.first() {
+            Some(first) => level = first,
+            None => break,
+        }
+    }
+
+    let mut values = Vec::with_capacity(shape.iter().product());
+    flatten(json, &shape, &mut values)?;
+    ArrayD::from_shape_vec(IxDyn(&shape), values).map_err(|err| err_msg(err.to_string()))
+}
+
+fn flatten(json: &Value, shape: &[usize], values: &mut Vec<f64>) -> Result<(), Error> {
+    match (json, shape.split_first()) {
+        (Value::Array(items), Some((&len, rest))) if items.len() == len => items
+            .iter()
+            .try_for_each(|item| flatten(item, rest, values)),
+        (Value::Number(n), None) => {
+            values.push(
+                n.as_f64()
+                    .ok_or_else(|| err_msg("array element is not a float"))?,
+            );
+            Ok(())
+        }
+        (Value::Null, None) => {
+            values.push(f64::NAN);
+            Ok(())
+        }
+        (Value::Array(_), _) | (_, Some(_)) => bail!("JSON array is not rectangular"),
+        (other, None) => bail!("array element is not a number: {}", other),
+    }
+}
+
+fn encode_tensor(array: &ArrayD<f32>) -> Vec<u8> {
+    let header_len = TENSOR_MAGIC.len() + 4 + 8 * array.ndim();
+    let mut bytes = Vec::with_capacity(header_len + 4 * array.len());
+    bytes.extend_from_slice(TENSOR_MAGIC);
+    bytes.extend_from_slice(&(array.ndim() as u32).to_le_bytes());
+    for &dim in array.shape() {
+        bytes.extend_from_slice(&(dim as u64).to_le_bytes());
+    }
+    for value in array.iter() {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+fn decode_tensor(bytes: &[u8]) -> Result<ArrayD<f32>, Error> {
+    fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+        if bytes.len() < len {
+            bail!("tensor data is truncated");
+        }
+        let (head, tail) = bytes.split_at(len);
+        *bytes = tail;
+        Ok(head)
+    }
+
+    let mut rest = bytes;
+    if take(&mut rest, TENSOR_MAGIC.len())? != TENSOR_MAGIC {
+        bail!("binary data is not an encoded tensor");
+    }
+    let ndim = u32::from_le_bytes(take(&mut rest, 4)?.try_into().unwrap());
+    let mut shape = Vec::new();
+    for _ in 0..ndim {
+        let dim = u64::from_le_bytes(take(&mut rest, 8)?.try_into().unwrap());
+        shape.push(dim.try_into().map_err(|_| err_msg("tensor is too large"))?);
+    }
+
+    let len = shape
+        .iter()
+        .try_fold(1usize, |acc, &dim| acc.checked_mul(dim))
+        .and_then(|len| len.checked_mul(4))
+        .ok_or_else(|| err_msg("tensor is too large"))?;
+    if rest.len() != len {
+        bail!(
+            "tensor data has {} bytes but its shape requires {}",
+            rest.len(),
+            len
+        );
+    }
+    let values = rest
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+        .collect();
+    ArrayD::from_shape_vec(IxDyn(&shape), values).map_err(|err| err_msg(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{arr2, Array};
+    use serde_json::json;
+
+    #[test]
+    fn test_json_array() {
+        let array = arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]).into_dyn();
+        let io = AlgoIo::from(JsonArray(array.clone()));
+        assert_eq!(io.to_json().unwrap(), "[[1.0,2.0,3.0],[4.0,5.0,6.0]]");
+        assert_eq!(io.to_array().unwrap(), array);
+
+        // Transposed arrays are encoded in logical order
+        let transposed = AlgoIo::from(JsonArray(array.t().to_owned()));
+        assert_eq!(
+            transposed.to_json().unwrap(),
+            "[[1.0,4.0],[2.0,5.0],[3.0,6.0]]"
+        );
+
+        let scalar = AlgoIo::from(json!(7)).to_array().unwrap();
+        assert_eq!(scalar.shape(), &[] as &[usize]);
+        let empty = AlgoIo::from(json!([[], []])).to_array().unwrap();
+        assert_eq!(empty.shape(), &[2, 0]);
+        assert!(AlgoIo::from(json!([[1, 2], [3]])).to_array().is_err());
+        assert!(AlgoIo::from(json!([[1, 2], 3])).to_array().is_err());
+        assert!(AlgoIo::from(json!(["a"])).to_array().is_err());
+    }
+
+    #[test]
+    fn test_tensor() {
+        let array =
+            Array::from_shape_vec(IxDyn(&[2, 1, 3]), vec![1.5f32, 2., 3., 4., 5., -6.]).unwrap();
+        let io = AlgoIo::from(Tensor(array.clone()));
+        let bytes = io.as_bytes().unwrap();
+        assert_eq!(&bytes[..4], TENSOR_MAGIC);
+        assert_eq!(bytes.len(), 4 + 4 + 3 * 8 + 6 * 4);
+        assert_eq!(io.to_tensor().unwrap(), array);
+
+        let truncated = AlgoIo::from(crate::algo::ByteVec::from(&bytes[..bytes.len() - 1]));
+        assert!(truncated.to_tensor().is_err());
+        let garbage = AlgoIo::from(crate::algo::ByteVec::from(&b"not a tensor"[..]));
+        assert!(garbage.to_tensor().is_err());
+    }
+}
@@ -0,0 +1,506 @@
+use super::{AlgoDetails, AlgoUri, Algorithm, VersionInfo};
+use crate::error::{process_http_response, Error, ResultExt};
+use crate::Paginated;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Version of an algorithm in an [`AlgoUri`](struct.AlgoUri.html)
+///
+/// # Examples
+///
+/// ```
+/// use algorithmia::algo::{AlgoUri, Version};
+///
+/// let uri = AlgoUri::with_version("demo/Hello", Version::Minor(0, 1))?;
+/// assert_eq!(uri.path(), "demo/Hello/0.1");
+/// assert_eq!("0.1.2".parse::<Version>()?, Version::Revision(0, 1, 2));
+/// # Ok::<(), Box<std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Version {
+    /// The latest published version
+    Latest,
+    /// The latest published revision of a minor version, e.g. `0.1`
+    Minor(u32, u32),
+    /// An exact published version, e.g. `0.1.2`
+    Revision(u32, u32, u32),
+    /// A build identified by its git hash, e.g. `abcdef123456`
+    Hash(String),
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Version::Latest => f.write_str("latest"),
+            Version::Minor(major, minor) => write!(f, "{}.{}", major, minor),
+            Version::Revision(major, minor, patch) => write!(f, "{}.{}.{}", major, minor, patch),
+            Version::Hash(hash) => f.write_str(hash),
+        }
+    }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    /// Parse `latest`, `0.1` (or `0.1.*`), `0.1.2`, or a git hash of 7 to 40 hex digits
+    fn from_str(version: &str) -> Result<Version, Error> {
+        if version == "latest" {
+            return Ok(Version::Latest);
+        }
+        let is_hash =
+            (7..=40).contains(&version.len()) && version.chars().all(|c| c.is_ascii_hexdigit());
+        if is_hash && version.contains(|c: char| !c.is_ascii_digit()) {
+            return Ok(Version::Hash(version.to_owned()));
+        }
+        let parts = version
+            .trim_end_matches(".*")
+            .split('.')
+            .map(|part| part.parse::<u32>().ok())
+            .collect::<Option<Vec<_>>>();
+        match parts.as_deref() {
+            Some(&[major, minor]) => Ok(Version::Minor(major, minor)),
+            Some(&[major, minor, patch]) if !version.ends_with(".*") => {
+                Ok(Version::Revision(major, minor, patch))
+            }
+            _ if is_hash => Ok(Version::Hash(version.to_owned())),
+            _ => bail!("invalid algorithm version '{}'", version),
+        }
+    }
+}
+
+/// A version of an algorithm as returned by the versions API
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AlgoVersion {
+    /// Version details such as the semantic version and git hash
+    pub version_info: VersionInfo,
+    /// Human-friendly details as of this version
+    #[serde(default)]
+    pub details: AlgoDetails,
+    // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
+    _dummy: (),
+}
+
+/// Which part of the semantic version to increment when publishing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionType {
+    /// Breaking changes, e.g. `1.2.3` to `2.0.0`
+    Major,
+    /// New functionality, e.g. `1.2.3` to `1.3.0`
+    #[default]
+    Minor,
+    /// Bug fixes, e.g. `1.2.3` to `1.2.4`
+    Patch,
+}
+
+/// Who may call a published algorithm version
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum Visibility {
+    /// Callable by any user
+    Public,
+    /// Callable only by the owner (and their organization)
+    Private,
+    /// Value not recognized by this client, kept so it is written back unchanged
+    Unknown(String),
+}
+
+impl From<String> for Visibility {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "public" => Visibility::Public,
+            "private" => Visibility::Private,
+            _ => Visibility::Unknown(value),
+        }
+    }
+}
+
+impl From<Visibility> for String {
+    fn from(visibility: Visibility) -> Self {
+        match visibility {
+            Visibility::Public => "public".into(),
+            Visibility::Private => "private".into(),
+            Visibility::Unknown(value) => value,
+        }
+    }
+}
+
+/// Options for publishing a new version of an algorithm
+///
+/// # Examples
+///
+/// ```
+/// use algorithmia::algo::{PublishOptions, VersionType};
+///
+/// let opts = PublishOptions {
+///     version_type: VersionType::Patch,
+///     release_notes: Some("Fix off-by-one in path cost".into()),
+///     ..PublishOptions::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PublishOptions {
+    /// Which part of the version to increment (defaults to `Minor`)
+    pub version_type: VersionType,
+    /// Release notes for the new version
+    pub release_notes: Option<String>,
+    /// Sample input shown on the algorithm's page
+    pub sample_input: Option<String>,
+    /// Royalty charged per call, in microcredits (keeps the current setting if `None`)
+    pub royalty_microcredits: Option<u64>,
+    /// Visibility of the new version (keeps the current setting if `None`)
+    pub visibility: Option<Visibility>,
+}
+
+#[derive(Serialize)]
+struct PublishRequest<'a> {
+    version_info: PublishVersionInfo<'a>,
+    #[serde(skip_serializing_if = "PublishSettings::is_empty")]
+    settings: PublishSettings,
+}
+
+#[derive(Serialize)]
+struct PublishVersionInfo<'a> {
+    version_type: VersionType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    release_notes: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sample_input: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct PublishSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    royalty_microcredits: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    algorithm_callability: Option<Visibility>,
+}
+
+impl PublishSettings {
+    fn is_empty(&self) -> bool {
+        self.royalty_microcredits.is_none() && self.algorithm_callability.is_none()
+    }
+}
+
+impl<'a> From<&'a PublishOptions> for PublishRequest<'a> {
+    fn from(opts: &'a PublishOptions) -> Self {
+        PublishRequest {
+            version_info: PublishVersionInfo {
+                version_type: opts.version_type,
+                release_notes: opts.release_notes.as_deref(),
+                sample_input: opts.sample_input.as_deref(),
+            },
+            settings: PublishSettings {
+                royalty_microcredits: opts.royalty_microcredits,
+                algorithm_callability: opts.visibility.clone(),
+            },
+        }
+    }
+}
+
+impl Algorithm {
+    /// List the versions of this algorithm
+    ///
+    /// Pages of versions are fetched lazily while iterating,
+    /// so iteration can stop as soon as the desired version is found.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let latest_minor = client.algo("anowell/Dijkstra")
+    ///     .versions()
+    ///     .filter_map(Result::ok)
+    ///     .filter_map(|v| v.version_info.semantic_version)
+    ///     .find(|v| v.starts_with("0."));
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn versions(&self) -> Paginated<AlgoVersion> {
+        Paginated::new(
+            self.client.clone(),
+            self.management_url("versions"),
+            format!("versions of algorithm '{}'", self.algo_uri),
+        )
+    }
+
+    /// Publish a new version of this algorithm from its latest successful build
+    ///
+    /// Returns the newly published semantic version, e.g. `"1.3.0"`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::algo::{PublishOptions, VersionType};
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let version = client.algo("anowell/Dijkstra").publish(&PublishOptions {
+    ///     version_type: VersionType::Major,
+    ///     release_notes: Some("Support weighted edges".into()),
+    ///     ..PublishOptions::default()
+    /// })?;
+    /// println!("Published anowell/Dijkstra/{}", version);
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn publish(&self, options: &PublishOptions) -> Result<String, Error> {
+        let url = self.management_url("versions")?;
+        let mut res = self
+            .client
+            .send(self.client.post(url).json(&PublishRequest::from(options)))
+            .with_context(|| format!("request error publishing algorithm '{}'", self.algo_uri))
+            .and_then(process_http_response)
+            .with_context(|| format!("response error publishing algorithm '{}'", self.algo_uri))?;
+
+        let published: AlgoVersion = res.json().with_context(|| {
+            format!(
+                "JSON decoding error publishing algorithm '{}'",
+                self.algo_uri
+            )
+        })?;
+        match published.version_info.semantic_version {
+            Some(version) => Ok(version),
+            None => bail!(
+                "API response missing version publishing algorithm '{}'",
+                self.algo_uri
+            ),
+        }
+    }
+
+    /// Resolve the version this algorithm's URI currently refers to and bind to it
+    ///
+    /// Without a version, or with `latest`, this is the most recently published version.
+    /// A partial version such as `1` or `1.2.*` resolves to the highest published version
+    /// it matches. The returned algorithm keeps this algorithm's options and calls that exact
+    /// version (available from `to_algo_uri().version()`), so batch jobs behave the same
+    /// even if a new version is published while they run.
+    ///
+    /// URIs that already name an exact version or git hash are returned unchanged,
+    /// without an API request.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let dijkstra = client.algo("anowell/Dijkstra").pin()?;
+    /// println!("Pinned to {}", dijkstra.to_algo_uri().version().unwrap());
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn pin(&self) -> Result<Algorithm, Error> {
+        let requested = self.algo_uri.version();
+        let prefix = match requested.map(|v| (v, v.parse::<Version>())) {
+            None | Some((_, Ok(Version::Latest))) => Vec::new(),
+            Some((v, Ok(Version::Revision(..)))) | Some((v, Ok(Version::Hash(_)))) => {
+                return Ok(self.with_version(v));
+            }
+            Some((_, Ok(Version::Minor(major, minor)))) => vec![u64::from(major), u64::from(minor)],
+            // `Version` has no major-only form, e.g. `1` or `1.*`
+            Some((v, Err(_))) => match v.trim_end_matches(".*").parse::<u64>() {
+                Ok(major) => vec![major],
+                Err(_) => bail!("invalid version in algorithm URI '{}'", self.algo_uri),
+            },
+        };
+
+        let mut latest: Option<((u64, u64, u64), String)> = None;
+        for version in self.versions() {
+            let version = match version?.version_info.semantic_version {
+                Some(version) => version,
+                None => continue,
+            };
+            let parsed = match parse_semver(&version) {
+                Some(parsed) => parsed,
+                None => continue,
+            };
+            let parts = [parsed.0, parsed.1, parsed.2];
+            let is_newer = match &latest {
+                Some((max, _)) => parsed > *max,
+                None => true,
+            };
+            if parts.starts_with(&prefix) && is_newer {
+                latest = Some((parsed, version));
+            }
+        }
+        match latest {
+            Some((_, version)) => Ok(self.with_version(&version)),
+            None => bail!(
+                "no published version of algorithm '{}' matches its URI",
+                self.algo_uri
+            ),
+        }
+    }
+
+    /// A copy of this algorithm, with the same options, calling a specific version
+    fn with_version(&self, version: &str) -> Algorithm {
+        let algo_uri = AlgoUri {
+            path: format!("{}/{}", self.owner_and_name(), version),
+            owner_kind: self.algo_uri.owner_kind,
+        };
+        Algorithm {
+            algo_uri,
+            options: self.options.clone(),
+            timeout: self.timeout,
+            sorted_json: self.sorted_json,
+            client: self.client.clone(),
+        }
+    }
+}
+
+/// Parse a complete `major.minor.patch` version
+fn parse_semver(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.split('.').map(|part| part.parse::<u64>().ok());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) => {
+            Some((major, minor, patch))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_parsing() {
+        let cases = vec![
+            ("latest", Version::Latest),
+            ("0.1", Version::Minor(0, 1)),
+            ("0.1.*", Version::Minor(0, 1)),
+            ("1.2.3", Version::Revision(1, 2, 3)),
+            ("abcdef123456", Version::Hash("abcdef123456".into())),
+            ("1234567", Version::Hash("1234567".into())),
+        ];
+        for (text, version) in cases {
+            assert_eq!(text.parse::<Version>().unwrap(), version, "{}", text);
+        }
+        assert_eq!(Version::Revision(1, 2, 3).to_string(), "1.2.3");
+        assert_eq!(Version::Minor(0, 1).to_string(), "0.1");
+        for invalid in &["", "1", "1.2.3.4", "1.2.3.*", "v1.0", "abc", "latest/1"] {
+            assert!(invalid.parse::<Version>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_publish_request_encoding() {
+        let opts = PublishOptions {
+            version_type: VersionType::Patch,
+            release_notes: Some("notes".into()),
+            ..PublishOptions::default()
+        };
+        let json = serde_json::to_value(PublishRequest::from(&opts)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "version_info": { "version_type": "patch", "release_notes": "notes" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_publish_request_settings() {
+        let opts = PublishOptions {
+            royalty_microcredits: Some(0),
+            visibility: Some(Visibility::Private),
+            ..PublishOptions::default()
+        };
+        let json = serde_json::to_value(PublishRequest::from(&opts)).unwrap();
+        assert_eq!(json["version_info"]["version_type"], "minor");
+        assert_eq!(json["settings"]["algorithm_callability"], "private");
+        assert_eq!(json["settings"]["royalty_microcredits"], 0);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_publish() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let path = "/v1/algorithms/anowell/Dijkstra/versions";
+        let published = json!({ "version_info": { "semantic_version": "1.3.0" } });
+        server.route("POST", path, FakeResponse::json(200, &published));
+
+        let opts = PublishOptions {
+            release_notes: Some("Support weighted edges".into()),
+            sample_input: Some("[[0, 1]]".into()),
+            royalty_microcredits: Some(5),
+            ..PublishOptions::default()
+        };
+        let algo = client.algo("anowell/Dijkstra");
+        assert_eq!(algo.publish(&opts).unwrap(), "1.3.0");
+        let request = server.requests().pop().unwrap();
+        assert_eq!(
+            (request.method.as_str(), request.url.as_str()),
+            ("POST", path)
+        );
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "version_info": {
+                    "version_type": "minor",
+                    "release_notes": "Support weighted edges",
+                    "sample_input": "[[0, 1]]"
+                },
+                "settings": { "royalty_microcredits": 5 }
+            })
+        );
+
+        server.route(
+            "POST",
+            path,
+            FakeResponse::json(200, &json!({ "version_info": {} })),
+        );
+        assert!(algo.publish(&opts).is_err());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_pin() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let version = |v: &str| json!({ "version_info": { "semantic_version": v } });
+        let versions = json!({
+            "results": [version("1.2.0"), version("1.10.1"), version("2.0.0"), version("1.9.3")],
+            "marker": null,
+        });
+        server.route(
+            "GET",
+            "/v1/algorithms/anowell/Dijkstra/versions",
+            FakeResponse::json(200, &versions),
+        );
+
+        let pinned = |uri: &str| {
+            let algo = client.algo(uri).pin().unwrap();
+            algo.to_algo_uri().path().to_owned()
+        };
+        assert_eq!(pinned("anowell/Dijkstra"), "anowell/Dijkstra/2.0.0");
+        assert_eq!(pinned("anowell/Dijkstra/latest"), "anowell/Dijkstra/2.0.0");
+        assert_eq!(pinned("anowell/Dijkstra/1"), "anowell/Dijkstra/1.10.1");
+        assert_eq!(pinned("anowell/Dijkstra/1.9.*"), "anowell/Dijkstra/1.9.3");
+        assert_eq!(server.requests().len(), 4);
+
+        // Exact versions don't need resolving
+        assert_eq!(pinned("anowell/Dijkstra/1.0.0"), "anowell/Dijkstra/1.0.0");
+        assert_eq!(
+            pinned("anowell/Dijkstra/abcdef1"),
+            "anowell/Dijkstra/abcdef1"
+        );
+        let hash = "0123456789abcdef0123456789abcdef01234567";
+        assert_eq!(
+            pinned(&format!("anowell/Dijkstra/{}", hash)),
+            format!("anowell/Dijkstra/{}", hash)
+        );
+        assert_eq!(server.requests().len(), 4);
+
+        assert!(client.algo("anowell/Dijkstra/3").pin().is_err());
+        assert!(client.algo("anowell/Dijkstra/beta").pin().is_err());
+    }
+}
@@ -0,0 +1,383 @@
+//! Async wrappers around the blocking client [feature = "async"]
+//!
+//! Mirrors the blocking client, but calling algorithms and transferring data return futures
+//! that can be awaited from any executor (e.g. tokio or async-std) without blocking it.
+//!
+//! This is not a non-blocking HTTP client: each request is still made by the blocking client,
+//! on a pool of worker threads shared by every async client, and its future resolves once
+//! the request completes. The pool grows to at most 256 threads, so that many requests can be
+//! in flight at once and further calls wait for a free thread. This saves wrapping each call
+//! in a runtime-specific `spawn_blocking`, but every in-flight request still occupies a thread.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use algorithmia::r#async::Algorithmia;
+//! use futures::future::try_join_all;
+//!
+//! # futures::executor::block_on(async {
+//! let client = Algorithmia::client("111112222233333444445555566")?;
+//! let algo = client.algo("demo/Hello");
+//! let calls = (0..100).map(|i| algo.pipe(format!("caller {}", i)));
+//! for response in try_join_all(calls).await? {
+//!     println!("{}", response.decode::<String>()?);
+//! }
+//! # Ok::<(), Box<std::error::Error>>(())
+//! # });
+//! ```
+
+use crate::algo::{AlgoIo, AlgoResponse};
+use crate::data::{DataAcl, DataItem, DirectoryDeleted, HasDataPath};
+use crate::error::Error;
+use crate::unblock::unblock;
+
+use futures::channel::mpsc;
+use futures::executor::{block_on_stream, BlockingStream};
+use futures::io::{AsyncRead, AsyncReadExt};
+use futures::{SinkExt, Stream};
+use mime::Mime;
+
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Size of the chunks read from an `AsyncRead` passed to `Algorithm::pipe_reader`
+const READER_CHUNK_SIZE: usize = 64 * 1024;
+/// Chunks read ahead of the upload before reading waits for it to catch up
+const READER_CHUNKS_BUFFERED: usize = 4;
+
+/// Async client for instantiating Algorithmia client endpoints
+///
+/// Created from the same configuration as the blocking client,
+/// or converted from one with `From`.
+#[derive(Clone)]
+pub struct Algorithmia {
+    inner: crate::Algorithmia,
+}
+
+impl Algorithmia {
+    /// Instantiate a new client with an API key
+    pub fn client<A: Into<String>>(api_key: A) -> Result<Algorithmia, Error> {
+        crate::Algorithmia::client(api_key).map(Algorithmia::from)
+    }
+
+    /// Instantiate a new client, configured from the environment like `Algorithmia::new`
+    pub fn new() -> Result<Algorithmia, Error> {
+        crate::Algorithmia::new().map(Algorithmia::from)
+    }
+
+    /// Instantiate an `Algorithm` from this client
+    pub fn algo<A: Into<crate::algo::AlgoUri>>(&self, algorithm: A) -> Algorithm {
+        self.inner.algo(algorithm).into()
+    }
+
+    /// Instantiate a `DataFile` from this client
+    pub fn file(&self, path: &str) -> DataFile {
+        self.inner.file(path).into()
+    }
+
+    /// Instantiate a `DataDir` from this client
+    pub fn dir(&self, path: &str) -> DataDir {
+        self.inner.dir(path).into()
+    }
+
+    /// The blocking client sharing this client's configuration and connections
+    pub fn blocking(&self) -> &crate::Algorithmia {
+        &self.inner
+    }
+}
+
+impl From<crate::Algorithmia> for Algorithmia {
+    fn from(inner: crate::Algorithmia) -> Algorithmia {
+        Algorithmia { inner }
+    }
+}
+
+/// Async handle for calling an algorithm
+///
+/// Configure options like the timeout on a blocking `Algorithm`, then convert it with `From`.
+#[derive(Clone)]
+pub struct Algorithm {
+    inner: Arc<crate::algo::Algorithm>,
+}
+
+impl Algorithm {
+    /// Execute the algorithm with the specified input, resolving to its response
+    ///
+    /// See the blocking [`Algorithm::pipe`](../algo/struct.Algorithm.html#method.pipe).
+    pub async fn pipe<I: Into<AlgoIo>>(&self, input_data: I) -> Result<AlgoResponse, Error> {
+        let algo = self.inner.clone();
+        let input = input_data.into();
+        unblock(move || algo.pipe(input)).await
+    }
+
+    /// Execute the algorithm with a raw JSON string as input
+    pub async fn pipe_json(&self, json_input: &str) -> Result<AlgoResponse, Error> {
+        let algo = self.inner.clone();
+        let json_input = json_input.to_owned();
+        unblock(move || algo.pipe_json(&json_input)).await
+    }
+
+    /// Execute the algorithm with input streamed from `reader`, without buffering it in memory
+    ///
+    /// `reader` is read by the returned future on the caller's executor while the call
+    /// is sent from a worker thread, so readers tied to a specific runtime work too.
+    /// See the blocking [`Algorithm::pipe_reader`](../algo/struct.Algorithm.html#method.pipe_reader).
+    pub async fn pipe_reader<R>(
+        &self,
+        mut reader: R,
+        content_type: Mime,
+    ) -> Result<AlgoResponse, Error>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let (mut tx, rx) = mpsc::channel(READER_CHUNKS_BUFFERED);
+        let algo = self.inner.clone();
+        let call = unblock(move || {
+            let reader = ChannelReader {
+                chunks: block_on_stream(rx),
+                current: io::Cursor::new(Vec::new()),
+            };
+            algo.pipe_reader(reader, content_type)
+        });
+        let feed = async move {
+            loop {
+                let mut chunk = vec![0; READER_CHUNK_SIZE];
+                let chunk = match reader.read(&mut chunk).await {
+                    Ok(0) => break,
+                    Ok(len) => {
+                        chunk.truncate(len);
+                        Ok(chunk)
+                    }
+                    Err(err) => Err(err),
+                };
+                let failed = chunk.is_err();
+                // The call has finished (e.g. failed) if the receiver was dropped
+                if tx.send(chunk).await.is_err() || failed {
+                    break;
+                }
+            }
+        };
+        let ((), response) = futures::join!(feed, call);
+        response
+    }
+
+    /// The blocking algorithm this wraps
+    pub fn blocking(&self) -> &crate::algo::Algorithm {
+        &self.inner
+    }
+}
+
+/// Blocking reader of the chunks sent by `Algorithm::pipe_reader`
+struct ChannelReader {
+    chunks: BlockingStream<mpsc::Receiver<io::Result<Vec<u8>>>>,
+    current: io::Cursor<Vec<u8>>,
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let len = self.current.read(buf)?;
+            if len > 0 || buf.is_empty() {
+                return Ok(len);
+            }
+            match self.chunks.next() {
+                Some(Ok(chunk)) => self.current = io::Cursor::new(chunk),
+                Some(Err(err)) => return Err(err),
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
+impl From<crate::algo::Algorithm> for Algorithm {
+    fn from(inner: crate::algo::Algorithm) -> Algorithm {
+        Algorithm {
+            inner: Arc::new(inner),
+        }
+    }
+}
+
+/// Async handle for a file in the Algorithmia Data API
+#[derive(Clone)]
+pub struct DataFile {
+    inner: crate::data::DataFile,
+}
+
+impl DataFile {
+    /// Download the file's contents into memory
+    pub async fn get(&self) -> Result<Vec<u8>, Error> {
+        let file = self.inner.clone();
+        unblock(move || {
+            let mut bytes = Vec::new();
+            file.get()?.read_to_end(&mut bytes)?;
+            Ok(bytes)
+        })
+        .await
+    }
+
+    /// Download the file to a local path without buffering it in memory,
+    /// resolving to the number of bytes written
+    ///
+    /// See [`DataFile::get_to_path`](../data/struct.DataFile.html#method.get_to_path).
+    pub async fn get_to_path<P: AsRef<Path>>(&self, path: P) -> Result<u64, Error> {
+        let file = self.inner.clone();
+        let path = path.as_ref().to_owned();
+        unblock(move || file.get_to_path(path)).await
+    }
+
+    /// Upload the file's contents
+    pub async fn put<B: Into<Vec<u8>>>(&self, data: B) -> Result<(), Error> {
+        let file = self.inner.clone();
+        let data = data.into();
+        unblock(move || file.put(data)).await
+    }
+
+    /// Upload a local file, streaming it from disk
+    pub async fn put_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let file = self.inner.clone();
+        let path = path.as_ref().to_owned();
+        unblock(move || file.put_file(path)).await
+    }
+
+    /// Determine if the file exists
+    pub async fn exists(&self) -> Result<bool, Error> {
+        let file = self.inner.clone();
+        unblock(move || file.exists()).await
+    }
+
+    /// Delete the file
+    pub async fn delete(&self) -> Result<(), Error> {
+        let file = self.inner.clone();
+        unblock(move || file.delete()).await
+    }
+
+    /// The blocking file this wraps
+    pub fn blocking(&self) -> &crate::data::DataFile {
+        &self.inner
+    }
+}
+
+impl From<crate::data::DataFile> for DataFile {
+    fn from(inner: crate::data::DataFile) -> DataFile {
+        DataFile { inner }
+    }
+}
+
+/// Async handle for a directory in the Algorithmia Data API
+#[derive(Clone)]
+pub struct DataDir {
+    inner: crate::data::DataDir,
+}
+
+impl DataDir {
+    /// List the directory as a `Stream`, fetching pages as it is polled
+    ///
+    /// See [`DataDir::list_stream`](../data/struct.DataDir.html#method.list_stream).
+    pub fn list(&self) -> impl Stream<Item = Result<DataItem, Error>> {
+        self.inner.list_stream()
+    }
+
+    /// Create the directory
+    pub async fn create<Acl: Into<DataAcl>>(&self, acl: Acl) -> Result<(), Error> {
+        let dir = self.inner.clone();
+        let acl = acl.into();
+        unblock(move || dir.create(acl)).await
+    }
+
+    /// Delete the directory, including its contents if `force` is true
+    pub async fn delete(&self, force: bool) -> Result<DirectoryDeleted, Error> {
+        let dir = self.inner.clone();
+        unblock(move || dir.delete(force)).await
+    }
+
+    /// Determine if the directory exists
+    pub async fn exists(&self) -> Result<bool, Error> {
+        let dir = self.inner.clone();
+        unblock(move || dir.exists()).await
+    }
+
+    /// Instantiate a `DataFile` in this directory
+    pub fn child_file(&self, filename: &str) -> DataFile {
+        self.inner.child::<crate::data::DataFile>(filename).into()
+    }
+
+    /// The blocking directory this wraps
+    pub fn blocking(&self) -> &crate::data::DataDir {
+        &self.inner
+    }
+}
+
+impl From<crate::data::DataDir> for DataDir {
+    fn from(inner: crate::data::DataDir) -> DataDir {
+        DataDir { inner }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::{FakeResponse, FakeServer};
+    use futures::executor::block_on;
+    use futures::future::try_join_all;
+    use futures::StreamExt;
+    use serde_json::json;
+
+    #[test]
+    fn test_async_client() {
+        let server = FakeServer::start().unwrap();
+        server.algo_result("demo/Hello", json!("Hello"));
+        server.data_file("data://.my/foo/a.txt", "contents");
+        server.route(
+            "PUT",
+            "/v1/connector/data/.my/foo/b.txt",
+            FakeResponse::status(200),
+        );
+        server.data_dir(
+            "data://.my/foo",
+            json!({ "files": [{ "filename": "a.txt", "size": 8, "last_modified": "2015-03-14T08:00:00Z" }] }),
+        );
+        let client = Algorithmia::from(server.client().unwrap());
+
+        block_on(async {
+            let algo = client.algo("demo/Hello");
+            let responses = try_join_all((0..10).map(|i| algo.pipe(i))).await.unwrap();
+            assert_eq!(responses.len(), 10);
+            assert_eq!(responses[0].result.as_string(), Some("Hello"),);
+
+            let dir = client.dir("data://.my/foo");
+            assert_eq!(dir.child_file("a.txt").get().await.unwrap(), b"contents");
+            dir.child_file("b.txt").put("uploaded").await.unwrap();
+            let items: Vec<_> = dir.list().collect().await;
+            assert_eq!(items.len(), 1);
+            assert!(client.file("data://.my/foo/a.txt").exists().await.unwrap());
+        });
+        let put = server
+            .requests()
+            .into_iter()
+            .find(|r| r.method == "PUT")
+            .unwrap();
+        assert_eq!(put.body_string(), "uploaded");
+    }
+
+    #[test]
+    fn test_async_pipe_reader() {
+        let server = FakeServer::start().unwrap();
+        server.algo_result("demo/Length", json!(200_000));
+        let client = Algorithmia::from(server.client().unwrap());
+        let input: Vec<u8> = (0..200_000).map(|i| i as u8).collect();
+
+        let response = block_on(client.algo("demo/Length").pipe_reader(
+            futures::io::Cursor::new(input.clone()),
+            mime::APPLICATION_OCTET_STREAM,
+        ))
+        .unwrap();
+        assert_eq!(response.decode::<u32>().unwrap(), 200_000);
+        let request = &server.requests()[0];
+        assert_eq!(
+            request.header("Content-Type"),
+            Some("application/octet-stream")
+        );
+        assert_eq!(request.body, input);
+    }
+}
@@ -0,0 +1,267 @@
+//! Typed wrappers for popular public algorithms [feature = "catalog"]
+//!
+//! Each wrapper pairs an algorithm with request and response types matching its JSON input
+//! and output, so callers don't need to re-derive them. Wrappers call the latest published
+//! version unless another version is selected with `with_version`.
+//!
+//! The wrappers only use the public `Algorithm` API; they double as examples of writing
+//! typed bindings for other algorithms.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use algorithmia::Algorithmia;
+//! use algorithmia::catalog::SentimentAnalysis;
+//!
+//! let client = Algorithmia::client("111112222233333444445555566")?;
+//! let sentiment = SentimentAnalysis::new(&client).analyze("I love Rust!")?;
+//! println!("Sentiment: {}", sentiment);
+//! # Ok::<(), Box<std::error::Error>>(())
+//! ```
+
+use crate::algo::Algorithm;
+use crate::error::{err_msg, Error};
+use crate::Algorithmia;
+
+use serde::{Deserialize, Serialize};
+
+/// Algorithm URI of [`SentimentAnalysis`](struct.SentimentAnalysis.html)
+pub const SENTIMENT_ANALYSIS: &str = "nlp/SentimentAnalysis";
+/// Algorithm URI of [`Summarizer`](struct.Summarizer.html)
+pub const SUMMARIZER: &str = "nlp/Summarizer";
+/// Algorithm URI of [`SmartImageDownloader`](struct.SmartImageDownloader.html)
+pub const SMART_IMAGE_DOWNLOADER: &str = "opencv/SmartImageDownloader";
+
+fn versioned(uri: &str, version: &str) -> String {
+    format!("{}/{}", uri, version)
+}
+
+/// Sentiment analysis of English text ([`nlp/SentimentAnalysis`](https://algorithmia.com/algorithms/nlp/SentimentAnalysis))
+pub struct SentimentAnalysis {
+    algo: Algorithm,
+}
+
+#[derive(Serialize)]
+struct SentimentRequest<'a> {
+    document: &'a str,
+}
+
+/// Sentiment of a single document
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Sentiment {
+    /// The analyzed text
+    pub document: String,
+    /// Sentiment from -1.0 (negative) to 1.0 (positive)
+    pub sentiment: f64,
+    // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
+    _dummy: (),
+}
+
+impl SentimentAnalysis {
+    /// Use the latest version of the algorithm
+    pub fn new(client: &Algorithmia) -> SentimentAnalysis {
+        SentimentAnalysis {
+            algo: client.algo(SENTIMENT_ANALYSIS),
+        }
+    }
+
+    /// Use a specific version of the algorithm, e.g. `"1.0.5"`
+    pub fn with_version(client: &Algorithmia, version: &str) -> SentimentAnalysis {
+        SentimentAnalysis {
+            algo: client.algo(versioned(SENTIMENT_ANALYSIS, version)),
+        }
+    }
+
+    /// The underlying algorithm, e.g. to configure call options
+    pub fn algo(&mut self) -> &mut Algorithm {
+        &mut self.algo
+    }
+
+    /// Sentiment of a single document, from -1.0 (negative) to 1.0 (positive)
+    pub fn analyze(&self, document: &str) -> Result<f64, Error> {
+        let mut results = self.analyze_all(&[document])?;
+        results
+            .pop()
+            .map(|result| result.sentiment)
+            .ok_or_else(|| err_msg("sentiment analysis returned no results"))
+    }
+
+    /// Sentiment of several documents in a single call
+    pub fn analyze_all(&self, documents: &[&str]) -> Result<Vec<Sentiment>, Error> {
+        let input: Vec<_> = documents
+            .iter()
+            .map(|document| SentimentRequest { document })
+            .collect();
+        self.algo.pipe(&input)?.decode()
+    }
+}
+
+/// Extractive summaries of English text ([`nlp/Summarizer`](https://algorithmia.com/algorithms/nlp/Summarizer))
+pub struct Summarizer {
+    algo: Algorithm,
+}
+
+impl Summarizer {
+    /// Use the latest version of the algorithm
+    pub fn new(client: &Algorithmia) -> Summarizer {
+        Summarizer {
+            algo: client.algo(SUMMARIZER),
+        }
+    }
+
+    /// Use a specific version of the algorithm, e.g. `"0.1.8"`
+    pub fn with_version(client: &Algorithmia, version: &str) -> Summarizer {
+        Summarizer {
+            algo: client.algo(versioned(SUMMARIZER, version)),
+        }
+    }
+
+    /// The underlying algorithm, e.g. to configure call options
+    pub fn algo(&mut self) -> &mut Algorithm {
+        &mut self.algo
+    }
+
+    /// Summarize text into a few of its most representative sentences
+    pub fn summarize(&self, text: &str) -> Result<String, Error> {
+        self.algo.pipe(text)?.decode()
+    }
+}
+
+/// Download, resize, and store images ([`opencv/SmartImageDownloader`](https://algorithmia.com/algorithms/opencv/SmartImageDownloader))
+pub struct SmartImageDownloader {
+    algo: Algorithm,
+}
+
+/// Input for [`SmartImageDownloader::download`](struct.SmartImageDownloader.html#method.download)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImageDownload {
+    /// URL or data URI of the image to download
+    pub image: String,
+    /// Resize the image to fit these dimensions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resize: Option<ImageSize>,
+    // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
+    _dummy: (),
+}
+
+impl ImageDownload {
+    /// Download an image without resizing it
+    pub fn new<S: Into<String>>(image: S) -> ImageDownload {
+        ImageDownload {
+            image: image.into(),
+            resize: None,
+            _dummy: (),
+        }
+    }
+}
+
+/// Image dimensions in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ImageSize {
+    /// Width in pixels
+    pub width: u32,
+    /// Height in pixels
+    pub height: u32,
+}
+
+#[derive(Deserialize)]
+struct DownloadedImage {
+    #[serde(rename = "savePath")]
+    save_path: Vec<String>,
+}
+
+impl SmartImageDownloader {
+    /// Use the latest version of the algorithm
+    pub fn new(client: &Algorithmia) -> SmartImageDownloader {
+        SmartImageDownloader {
+            algo: client.algo(SMART_IMAGE_DOWNLOADER),
+        }
+    }
+
+    /// Use a specific version of the algorithm, e.g. `"0.2.x"`
+    pub fn with_version(client: &Algorithmia, version: &str) -> SmartImageDownloader {
+        SmartImageDownloader {
+            algo: client.algo(versioned(SMART_IMAGE_DOWNLOADER, version)),
+        }
+    }
+
+    /// The underlying algorithm, e.g. to configure call options
+    pub fn algo(&mut self) -> &mut Algorithm {
+        &mut self.algo
+    }
+
+    /// Download an image, returning the data URI where it was stored
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use algorithmia::Algorithmia;
+    /// use algorithmia::catalog::{ImageDownload, ImageSize, SmartImageDownloader};
+    ///
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let mut request = ImageDownload::new("https://example.com/cat.jpg");
+    /// request.resize = Some(ImageSize { width: 256, height: 256 });
+    /// let data_uri = SmartImageDownloader::new(&client).download(&request)?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn download(&self, request: &ImageDownload) -> Result<String, Error> {
+        let result: DownloadedImage = self.algo.pipe(request)?.decode()?;
+        result
+            .save_path
+            .into_iter()
+            .next()
+            .ok_or_else(|| err_msg("image downloader returned no save path"))
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::FakeServer;
+    use serde_json::json;
+
+    #[test]
+    fn test_catalog() {
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        server.algo_result(
+            SENTIMENT_ANALYSIS,
+            json!([{ "document": "I love Rust!", "sentiment": 0.6696 }]),
+        );
+        server.algo_result("nlp/Summarizer/0.1.8", json!("Short."));
+        server.algo_result(
+            SMART_IMAGE_DOWNLOADER,
+            json!({ "savePath": ["data://.algo/temp/cat.jpg"] }),
+        );
+
+        let sentiment = SentimentAnalysis::new(&client)
+            .analyze("I love Rust!")
+            .unwrap();
+        assert!((sentiment - 0.6696).abs() < 1e-9);
+        let summary = Summarizer::with_version(&client, "0.1.8")
+            .summarize("Long. Text.")
+            .unwrap();
+        assert_eq!(summary, "Short.");
+        let mut request = ImageDownload::new("https://example.com/cat.jpg");
+        request.resize = Some(ImageSize {
+            width: 256,
+            height: 128,
+        });
+        let saved = SmartImageDownloader::new(&client)
+            .download(&request)
+            .unwrap();
+        assert_eq!(saved, "data://.algo/temp/cat.jpg");
+
+        let bodies: Vec<_> = server.requests().iter().map(|r| r.body_string()).collect();
+        assert_eq!(
+            bodies,
+            vec![
+                r#"[{"document":"I love Rust!"}]"#,
+                r#""Long. Text.""#,
+                r#"{"image":"https://example.com/cat.jpg","resize":{"height":128,"width":256}}"#,
+            ]
+        );
+    }
+}
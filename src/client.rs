@@ -4,13 +4,18 @@
 use std::str::FromStr;
 use std::sync::Arc;
 
-use headers_ext::{Authorization, authorization::Credentials, HeaderMapExt, UserAgent};
+use headers_ext::authorization::{Bearer, Credentials};
+use headers_ext::{Authorization, HeaderMapExt, UserAgent};
 use http::header::HeaderMap;
 use http::header::HeaderValue;
-use reqwest::{Client, IntoUrl, Method, RequestBuilder, Url};
-pub use reqwest::Body;
+use reqwest::{Client, IntoUrl, Method, Request, RequestBuilder, Response, Url};
+use std::error::Error as StdError;
+use std::io;
+use std::time::Instant;
 
-use crate::error::{Error, ResultExt};
+use crate::error::{err_msg, Error, ResultExt};
+use crate::hooks::Hooks;
+use crate::retry::RetryBudget;
 
 struct Simple(HeaderValue);
 impl Credentials for Simple {
@@ -37,11 +42,37 @@ impl Simple {
             .context("API key is invalid")
     }
 }
+
+/// Append `segment` to the path of `url`, percent-encoding it
+///
+/// Use this for IDs and names that are placed in a URL path, so characters such
+/// as `/` or `?` in them can't change which resource is requested.
+pub(crate) fn push_segment(mut url: Url, segment: &str) -> Result<Url, Error> {
+    if url.cannot_be_a_base() {
+        bail!("cannot append '{}' to URL '{}'", segment, url);
+    }
+    // `.` and `..` can't be escaped and would otherwise be silently dropped
+    if segment == "." || segment == ".." {
+        bail!("invalid path segment '{}'", segment);
+    }
+    url.path_segments_mut()
+        .expect("URL can be a base")
+        .push(segment);
+    Ok(url)
+}
+
+/// Try to create a `Bearer` authorization header
+pub(crate) fn bearer_auth(token: &str) -> Result<Authorization<Bearer>, Error> {
+    Authorization::bearer(token).map_err(|_| err_msg("bearer token is invalid"))
+}
+
 /// Represent the different ways to auth with the API
 #[derive(Clone)]
 pub enum ApiAuth {
     /// Algorithmia API key to use for authentication
     ApiKey(String),
+    /// Bearer token (e.g. an OIDC-issued JWT) to use for authentication
+    Bearer(String),
     /// Use unauthenticated request (common for on-platform algorithms)
     None,
 }
@@ -50,18 +81,38 @@ pub enum ApiAuth {
 #[derive(Clone)]
 pub struct HttpClient {
     pub base_url: Url,
+    pub(crate) algo_base_url: Option<Url>,
+    pub(crate) data_base_url: Option<Url>,
     api_auth: ApiAuth,
-    inner_client: Arc<Client>,
+    pub(crate) inner_client: Arc<Client>,
     user_agent: String,
+    pub(crate) default_headers: Arc<HeaderMap>,
+    pub(crate) transfer_rate_limit: Option<u64>,
+    pub(crate) custom_connectors: Arc<Vec<String>>,
+    pub(crate) sniff_content_type: bool,
+    pub(crate) retry_budget: RetryBudget,
+    pub(crate) hooks: Arc<Hooks>,
 }
 
 impl HttpClient {
     /// Instantiate an `HttpClient` - creates a new `reqwest` client
-    pub fn new<U: IntoUrl>(api_auth: ApiAuth, base_url: U) -> Result<HttpClient, Error> {
+    pub fn new<U: IntoUrl>(
+        api_auth: ApiAuth,
+        base_url: U,
+        default_headers: HeaderMap,
+    ) -> Result<HttpClient, Error> {
         Ok(HttpClient {
-            api_auth: api_auth,
+            api_auth,
             base_url: base_url.into_url().context("Invalid base URL")?,
+            algo_base_url: None,
+            data_base_url: None,
             inner_client: Self::inner_client(),
+            default_headers: Arc::new(default_headers),
+            transfer_rate_limit: None,
+            custom_connectors: Arc::new(Vec::new()),
+            sniff_content_type: false,
+            retry_budget: RetryBudget::default(),
+            hooks: Arc::new(Hooks::default()),
             user_agent: format!(
                 "algorithmia-rust/{} (Rust {}",
                 option_env!("CARGO_PKG_VERSION").unwrap_or("unknown"),
@@ -69,6 +120,22 @@ impl HttpClient {
             ),
         })
     }
+    /// Base URL for calling algorithms, which defaults to `base_url`
+    pub(crate) fn algo_base_url(&self) -> &Url {
+        self.algo_base_url.as_ref().unwrap_or(&self.base_url)
+    }
+
+    /// Base URL for the Data API, which defaults to `base_url`
+    pub(crate) fn data_base_url(&self) -> &Url {
+        self.data_base_url.as_ref().unwrap_or(&self.base_url)
+    }
+
+    /// Whether data URIs may use this connector scheme, e.g. `s3`
+    pub(crate) fn supports_connector(&self, scheme: &str) -> bool {
+        crate::data::CONNECTORS.contains(&scheme)
+            || self.custom_connectors.iter().any(|c| c == scheme)
+    }
+
     /// Helper to make Algorithmia GET requests with the API key
     pub fn get(&self, url: Url) -> RequestBuilder {
         self.build_request(Method::GET, url)
@@ -89,38 +156,146 @@ impl HttpClient {
         self.build_request(Method::PUT, url)
     }
 
+    /// Helper to make Algorithmia PATCH requests with the API key
+    pub fn patch(&self, url: Url) -> RequestBuilder {
+        self.build_request(Method::PATCH, url)
+    }
+
     /// Helper to make Algorithmia POST requests with the API key
     pub fn delete(&self, url: Url) -> RequestBuilder {
         self.build_request(Method::DELETE, url)
     }
 
+    /// Send a request, retrying `GET` and `HEAD` requests once if the connection
+    /// was reset or closed before any response was received
+    ///
+    /// Pooled keep-alive connections may be closed by the server just as a request is sent,
+    /// and repeating a safe request is harmless. Retries are limited by the retry budget.
+    pub(crate) fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let request = request.build()?;
+        let retry = match *request.method() {
+            Method::GET | Method::HEAD => request.try_clone(),
+            _ => None,
+        };
+        let res = match (self.execute(request, 1), retry) {
+            (Err(ref err), Some(retry))
+                if is_connection_reset(err) && self.retry_budget.try_retry() =>
+            {
+                self.execute(retry, 2)
+            }
+            (res, _) => res,
+        };
+        if matches!(&res, Ok(res) if !res.status().is_server_error()) {
+            self.retry_budget.record_success();
+        }
+        res
+    }
+
+    /// Execute a single attempt of a request, calling any instrumentation hooks
+    ///
+    /// With the `tracing` feature, each attempt is also wrapped in an `algorithmia.request` span.
+    fn execute(&self, request: Request, attempt: u32) -> reqwest::Result<Response> {
+        if self.hooks.is_empty() && !cfg!(feature = "tracing") {
+            return self.inner_client.execute(request);
+        }
+        let method = request.method().clone();
+        let url = request.url().clone();
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "algorithmia.request",
+            method = %method,
+            path = url.path(),
+            attempt,
+            status = tracing::field::Empty,
+            elapsed_ms = tracing::field::Empty,
+        );
+        #[cfg(feature = "tracing")]
+        let _entered = span.enter();
+
+        self.hooks.request(&method, &url, attempt);
+        let start = Instant::now();
+        let res = self.inner_client.execute(request);
+        let status = res.as_ref().ok().map(Response::status);
+        let elapsed = start.elapsed();
+
+        #[cfg(feature = "tracing")]
+        {
+            span.record("elapsed_ms", elapsed.as_millis() as u64);
+            match &res {
+                Ok(res) => {
+                    span.record("status", res.status().as_u16());
+                }
+                Err(err) => tracing::debug!(error = %err, "request failed"),
+            }
+        }
+        self.hooks.response(method, url, attempt, status, elapsed);
+        res
+    }
+
     fn build_request(&self, verb: Method, url: Url) -> RequestBuilder {
-        let mut headers = HeaderMap::new();
+        self.inner_client
+            .request(verb, url.clone())
+            .headers(self.request_headers())
+    }
+
+    /// Headers sent with every request: defaults, user agent, and authorization
+    pub(crate) fn request_headers(&self) -> HeaderMap {
+        let mut headers = (*self.default_headers).clone();
         headers.typed_insert(
             UserAgent::from_str(&self.user_agent).expect("User Agent not valid ASCII"),
         );
-        if let ApiAuth::ApiKey(ref api_key) = self.api_auth {
-            headers.typed_insert(Authorization(
+        match self.api_auth {
+            ApiAuth::ApiKey(ref api_key) => headers.typed_insert(Authorization(
                 Simple::new(api_key).expect("API Key not valid ASCII"),
-            ));
+            )),
+            ApiAuth::Bearer(ref token) => {
+                headers.typed_insert(bearer_auth(token).expect("Bearer token not valid ASCII"))
+            }
+            ApiAuth::None => (),
         }
+        headers
+    }
 
-        self.inner_client
-            .request(verb, url.clone())
-            .headers(headers)
+    fn inner_client() -> Arc<Client> {
+        Arc::new(Self::inner_client_builder().build().unwrap())
     }
 
+    /// Builder for the `reqwest` client with the configured TLS backend
     #[cfg(not(feature = "rust-tls"))]
-    fn inner_client() -> Arc<Client> {
-        Arc::new(Client::new())
+    pub(crate) fn inner_client_builder() -> reqwest::ClientBuilder {
+        Client::builder()
     }
 
+    /// Builder for the `reqwest` client with the configured TLS backend
     #[cfg(feature = "rust-tls")]
-    fn inner_client() -> Arc<Client> {
-        Arc::new(Client::builder().use_rustls_tls().build().unwrap())
+    pub(crate) fn inner_client_builder() -> reqwest::ClientBuilder {
+        Client::builder().use_rustls_tls()
     }
 }
 
+/// Whether a request failed because the connection was dropped before a response was received
+fn is_connection_reset(err: &reqwest::Error) -> bool {
+    let mut source: Option<&(dyn StdError + 'static)> = err.get_ref().map(|e| e as _);
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<hyper::Error>() {
+            if err.is_incomplete_message() || err.is_canceled() || err.is_closed() {
+                return true;
+            }
+        }
+        if let Some(err) = err.downcast_ref::<io::Error>() {
+            return matches!(
+                err.kind(),
+                io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::UnexpectedEof
+            );
+        }
+        source = err.source();
+    }
+    false
+}
+
 impl<'a> From<&'a str> for ApiAuth {
     fn from(api_key: &'a str) -> Self {
         match api_key.len() {
@@ -148,9 +323,179 @@ impl From<()> for ApiAuth {
 pub(crate) mod header {
     use http::header::HeaderValue;
 
-    pub const X_DATA_TYPE: &'static str = "x-data-type";
-    pub const X_ERROR_MESSAGE: &'static str = "x-error-message";
+    pub const X_DATA_TYPE: &str = "x-data-type";
+    pub const X_ERROR_MESSAGE: &str = "x-error-message";
+    pub const X_REQUEST_ID: &str = "x-request-id";
     pub(crate) fn lossy_header(val: &HeaderValue) -> String {
         String::from_utf8_lossy(val.as_bytes()).to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serve connections that are closed without a response, then a successful response
+    fn flaky_server(resets: usize) -> (Url, thread::JoinHandle<usize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        let handle = thread::spawn(move || {
+            let mut connections = 0;
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                connections += 1;
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf);
+                if connections <= resets {
+                    continue;
+                }
+                let _ = stream.write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+                );
+                break;
+            }
+            connections
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_push_segment() {
+        let url = Url::parse("https://api.algorithmia.com/v1/algorithms/a/b/builds").unwrap();
+        assert_eq!(
+            push_segment(url.clone(), "../x?y#z").unwrap().as_str(),
+            "https://api.algorithmia.com/v1/algorithms/a/b/builds/..%2Fx%3Fy%23z"
+        );
+        for dots in &[".", ".."] {
+            assert!(push_segment(url.clone(), dots).is_err(), "{}", dots);
+        }
+        let url = Url::parse("mailto:someone@example.com").unwrap();
+        assert!(push_segment(url, "x").is_err());
+    }
+
+    #[test]
+    fn test_retry_connection_reset() {
+        let client = HttpClient::new(ApiAuth::None, "http://localhost", HeaderMap::new()).unwrap();
+
+        let (url, server) = flaky_server(1);
+        let res = client.send(client.get(url)).unwrap();
+        assert_eq!(res.status().as_u16(), 200);
+        assert_eq!(server.join().unwrap(), 2);
+
+        // Only retried once
+        let (url, _server) = flaky_server(2);
+        assert!(client.send(client.head(url)).is_err());
+
+        // Unsafe methods aren't retried
+        let (url, _server) = flaky_server(1);
+        assert!(client.send(client.post(url)).is_err());
+
+        // Nor are any requests once the retry budget is spent
+        let mut client = client;
+        client.retry_budget = RetryBudget::new(1, 0.0);
+        let (url, _server) = flaky_server(1);
+        assert!(client.send(client.get(url)).is_ok());
+        let (url, _server) = flaky_server(1);
+        assert!(client.send(client.get(url)).is_err());
+    }
+
+    #[test]
+    fn test_hooks() {
+        use std::sync::Mutex;
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let responses = Arc::new(Mutex::new(Vec::new()));
+        let mut hooks = Hooks::default();
+        let recorded = requests.clone();
+        hooks.add_request_hook(move |event| recorded.lock().unwrap().push(event.attempt));
+        let recorded = responses.clone();
+        hooks.add_response_hook(move |event| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((event.method.clone(), event.attempt, event.status))
+        });
+        let mut client =
+            HttpClient::new(ApiAuth::None, "http://localhost", HeaderMap::new()).unwrap();
+        client.hooks = Arc::new(hooks);
+
+        let (url, server) = flaky_server(1);
+        client.send(client.get(url)).unwrap();
+        server.join().unwrap();
+        assert_eq!(*requests.lock().unwrap(), vec![1, 2]);
+        assert_eq!(
+            *responses.lock().unwrap(),
+            vec![
+                (Method::GET, 1, None),
+                (Method::GET, 2, Some(http::StatusCode::OK))
+            ]
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_spans() {
+        use std::fmt::Debug;
+        use std::sync::Mutex;
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        /// Records the fields of every span as `name=value` strings
+        #[derive(Default)]
+        struct Recorder {
+            spans: Mutex<Vec<(String, Vec<String>)>>,
+        }
+
+        struct Fields<'a>(&'a mut Vec<String>);
+
+        impl<'a> Visit for Fields<'a> {
+            fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+                self.0.push(format!("{}={:?}", field.name(), value));
+            }
+        }
+
+        impl Subscriber for &'static Recorder {
+            fn enabled(&self, _: &Metadata) -> bool {
+                true
+            }
+            fn new_span(&self, attrs: &Attributes) -> Id {
+                let mut fields = Vec::new();
+                attrs.record(&mut Fields(&mut fields));
+                let mut spans = self.spans.lock().unwrap();
+                spans.push((attrs.metadata().name().to_owned(), fields));
+                Id::from_u64(spans.len() as u64)
+            }
+            fn record(&self, span: &Id, values: &Record) {
+                let mut spans = self.spans.lock().unwrap();
+                values.record(&mut Fields(&mut spans[span.into_u64() as usize - 1].1));
+            }
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let recorder: &'static Recorder = Box::leak(Box::default());
+        let client = HttpClient::new(ApiAuth::None, "http://localhost", HeaderMap::new()).unwrap();
+        let (url, server) = flaky_server(0);
+        tracing::subscriber::with_default(recorder, || {
+            client.send(client.get(url.join("v1/algo/demo/Hello").unwrap()))
+        })
+        .unwrap();
+        server.join().unwrap();
+
+        let spans = recorder.spans.lock().unwrap();
+        assert_eq!(spans.len(), 1);
+        let (name, fields) = &spans[0];
+        assert_eq!(name, "algorithmia.request");
+        assert!(fields.contains(&"method=GET".to_owned()));
+        assert!(fields.contains(&"path=\"/v1/algo/demo/Hello\"".to_owned()));
+        assert!(fields.contains(&"attempt=1".to_owned()));
+        assert!(fields.contains(&"status=200".to_owned()));
+        assert!(fields.iter().any(|f| f.starts_with("elapsed_ms=")));
+    }
+}
@@ -0,0 +1,185 @@
+use crate::client::HttpClient;
+use crate::data::{DataFile, HasDataPath, UploadSource};
+use crate::error::Error;
+
+use std::mem;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// Handle for uploading data files from background threads
+///
+/// `put` queues an upload and returns immediately, so code that emits many small artifacts
+/// (e.g. a service writing logs or an algorithm saving intermediate results) doesn't wait
+/// on upload latency. Failed uploads are collected, paired with their data URI,
+/// and returned by [`flush`](#method.flush) or [`join`](#method.join).
+///
+/// Dropping the writer waits for queued uploads to finish but discards their errors,
+/// so call `join` to check them.
+///
+/// Created with [`Algorithmia::background_writer`](../struct.Algorithmia.html#method.background_writer).
+///
+/// # Examples
+/// ```no_run
+/// # use algorithmia::Algorithmia;
+/// let client = Algorithmia::client("111112222233333444445555566")?;
+/// let writer = client.background_writer(4);
+/// for step in 0..100 {
+///     let metrics = format!("{{\"step\": {}}}", step).into_bytes();
+///     writer.put(&format!(".my/run/metrics_{}.json", step), metrics);
+/// }
+/// for (uri, err) in writer.join() {
+///     println!("Failed to write {}: {}", uri, err);
+/// }
+/// # Ok::<(), Box<std::error::Error>>(())
+/// ```
+pub struct BackgroundWriter {
+    client: HttpClient,
+    sender: Option<Sender<(DataFile, UploadSource)>>,
+    workers: Vec<JoinHandle<()>>,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    progress: Mutex<Progress>,
+    idle: Condvar,
+}
+
+#[derive(Default)]
+struct Progress {
+    pending: usize,
+    errors: Vec<(String, Error)>,
+}
+
+impl BackgroundWriter {
+    pub(crate) fn new(client: HttpClient, workers: usize) -> BackgroundWriter {
+        let (sender, receiver) = mpsc::channel::<(DataFile, UploadSource)>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let shared = Arc::new(Shared {
+            progress: Mutex::new(Progress::default()),
+            idle: Condvar::new(),
+        });
+
+        let workers = (0..workers.max(1))
+            .map(|_| {
+                let receiver = receiver.clone();
+                let shared = shared.clone();
+                thread::spawn(move || loop {
+                    // Holding the lock while waiting is fine: only one idle worker can take a job
+                    let (file, source) = match receiver.lock().unwrap().recv() {
+                        Ok(job) => job,
+                        Err(_) => break,
+                    };
+                    let result = file.put_source(source);
+
+                    let mut progress = shared.progress.lock().unwrap();
+                    if let Err(err) = result {
+                        progress.errors.push((file.to_data_uri(), err));
+                    }
+                    progress.pending -= 1;
+                    if progress.pending == 0 {
+                        shared.idle.notify_all();
+                    }
+                })
+            })
+            .collect();
+
+        BackgroundWriter {
+            client,
+            sender: Some(sender),
+            workers,
+            shared,
+        }
+    }
+
+    /// Queue an upload of a local file or bytes to `data_uri`, returning immediately
+    ///
+    /// The content type of bytes is inferred from the file name's extension.
+    pub fn put<S: Into<UploadSource>>(&self, data_uri: &str, source: S) {
+        let file = DataFile::new(self.client.clone(), data_uri);
+        self.shared.progress.lock().unwrap().pending += 1;
+        if let Some(sender) = &self.sender {
+            // Workers only stop once the sender is dropped
+            let _ = sender.send((file, source.into()));
+        }
+    }
+
+    /// Number of queued or in-progress uploads
+    pub fn pending(&self) -> usize {
+        self.shared.progress.lock().unwrap().pending
+    }
+
+    /// Wait for every queued upload to finish, returning the errors of any that failed
+    ///
+    /// The writer can continue to be used afterwards.
+    pub fn flush(&self) -> Vec<(String, Error)> {
+        let mut progress = self.shared.progress.lock().unwrap();
+        while progress.pending > 0 {
+            progress = self.shared.idle.wait(progress).unwrap();
+        }
+        mem::take(&mut progress.errors)
+    }
+
+    /// Wait for every queued upload to finish and stop the background threads,
+    /// returning the errors of any uploads that failed
+    pub fn join(mut self) -> Vec<(String, Error)> {
+        self.stop();
+        mem::take(&mut self.shared.progress.lock().unwrap().errors)
+    }
+
+    fn stop(&mut self) {
+        // Workers exit once the queue is drained and the sender is dropped
+        self.sender = None;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for BackgroundWriter {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use crate::testing::{FakeResponse, FakeServer};
+
+    #[test]
+    fn test_background_writer() {
+        let server = FakeServer::start().unwrap();
+        server.route(
+            "PUT",
+            "/v1/connector/data/.my/out/a.txt",
+            FakeResponse::status(200),
+        );
+        server.route(
+            "PUT",
+            "/v1/connector/data/.my/out/b.json",
+            FakeResponse::status(200),
+        );
+        let client = server.client().unwrap();
+
+        let writer = client.background_writer(2);
+        writer.put(".my/out/a.txt", b"first".to_vec());
+        writer.put(".my/out/b.json", b"{}".to_vec());
+        writer.put(".my/out/missing/c.txt", b"third".to_vec());
+
+        let errors = writer.flush();
+        assert_eq!(writer.pending(), 0);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "data://.my/out/missing/c.txt");
+        assert_eq!(server.requests().len(), 3);
+        let json_put = server
+            .requests()
+            .into_iter()
+            .find(|r| r.url.ends_with("b.json"))
+            .unwrap();
+        assert_eq!(json_put.header("Content-Type"), Some("application/json"));
+
+        writer.put(".my/out/a.txt", b"again".to_vec());
+        assert!(writer.join().is_empty());
+        assert_eq!(server.requests().len(), 4);
+    }
+}
@@ -0,0 +1,183 @@
+//! On-disk cache of data files, revalidated with ETags
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use algorithmia::Algorithmia;
+//! use algorithmia::data::FileCache;
+//!
+//! let client = Algorithmia::client("111112222233333444445555566")?;
+//! let cache = FileCache::new("/tmp/algorithmia-cache")?;
+//!
+//! // Only downloads the model if it changed since the last run
+//! let model = cache.get(&client.file(".my/models/model.bin"))?;
+//! println!("Using {} (cached: {})", model.path.display(), model.from_cache);
+//! # Ok::<(), Box<std::error::Error>>(())
+//! ```
+
+use super::file::ConditionalData;
+use super::{DataFile, HasDataPath};
+use crate::error::{Error, ResultExt};
+use std::fs::{self, File};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+/// A local copy of a data file, returned by [`FileCache::get`](struct.FileCache.html#method.get)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedFile {
+    /// Path of the cached contents
+    pub path: PathBuf,
+    /// ETag of the cached contents, if provided by the API
+    pub etag: Option<String>,
+    /// Whether the cached copy was still current, so nothing was downloaded
+    pub from_cache: bool,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+/// Directory of downloaded data files, each stored with the ETag it was downloaded with
+///
+/// [`get`](#method.get) revalidates a cached copy with
+/// [`DataFile::get_if_none_match`](struct.DataFile.html#method.get_if_none_match) and only
+/// downloads the file again if it changed. Files downloaded without an ETag are always
+/// downloaded again.
+///
+/// Each data URI is stored under a name derived from its MD5 digest. A cache directory can
+/// be shared between runs and processes, but concurrent `get`s of the same data URI may
+/// replace each other's downloads.
+#[derive(Debug, Clone)]
+pub struct FileCache {
+    dir: PathBuf,
+}
+
+impl FileCache {
+    /// Use `dir` as a cache directory, creating it if it doesn't exist
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Result<FileCache, Error> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("creating cache directory '{}'", dir.display()))?;
+        Ok(FileCache { dir })
+    }
+
+    /// Get a local copy of a file, downloading it only if the cached copy is missing or stale
+    pub fn get(&self, file: &DataFile) -> Result<CachedFile, Error> {
+        let (path, etag_path) = self.paths(file);
+        let cached_etag = match fs::read_to_string(&etag_path) {
+            Ok(etag) if path.is_file() => Some(etag),
+            _ => None,
+        };
+
+        let mut data = match &cached_etag {
+            Some(etag) => match file.get_if_none_match(etag)? {
+                ConditionalData::Modified(data) => data,
+                ConditionalData::NotModified => {
+                    return Ok(CachedFile {
+                        path,
+                        etag: cached_etag,
+                        from_cache: true,
+                        _dummy: (),
+                    });
+                }
+            },
+            None => file.get()?,
+        };
+
+        // Forget the old ETag first so an interrupted download is never considered current
+        remove_if_exists(&etag_path)?;
+        let etag = data.etag.clone();
+        let part_path = path.with_extension("part");
+        let written = File::create(&part_path).and_then(|mut out| io::copy(&mut data, &mut out));
+        if let Err(err) = written {
+            let _ = fs::remove_file(&part_path);
+            return Err(err).with_context(|| {
+                format!(
+                    "caching '{}' in '{}'",
+                    file.to_data_uri(),
+                    part_path.display()
+                )
+            });
+        }
+        fs::rename(&part_path, &path)
+            .with_context(|| format!("moving download to '{}'", path.display()))?;
+        if let Some(etag) = &etag {
+            fs::write(&etag_path, etag)
+                .with_context(|| format!("writing ETag to '{}'", etag_path.display()))?;
+        }
+
+        Ok(CachedFile {
+            path,
+            etag,
+            from_cache: false,
+            _dummy: (),
+        })
+    }
+
+    /// Remove the cached copy of a file, if any
+    pub fn remove(&self, file: &DataFile) -> Result<(), Error> {
+        let (path, etag_path) = self.paths(file);
+        remove_if_exists(&etag_path)?;
+        remove_if_exists(&path)
+    }
+
+    /// Paths of the cached contents and ETag of a file
+    fn paths(&self, file: &DataFile) -> (PathBuf, PathBuf) {
+        let key = format!("{:x}", md5::compute(file.to_data_uri()));
+        (self.dir.join(&key), self.dir.join(key + ".etag"))
+    }
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), Error> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != ErrorKind::NotFound => {
+            Err(err).with_context(|| format!("removing '{}'", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::{FakeResponse, FakeServer};
+
+    #[test]
+    fn test_file_cache() {
+        let server = FakeServer::start().unwrap();
+        let path = "/v1/connector/data/.my/models/model.bin";
+        let download = |etag: &str, body: &str| {
+            FakeResponse::status(200)
+                .with_header("X-Data-Type", "file")
+                .with_header("ETag", etag)
+                .with_body(body)
+        };
+        server.route("GET", path, download("\"v1\"", "first"));
+        let client = server.client().unwrap();
+        let file = client.file("data://.my/models/model.bin");
+
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let cache = FileCache::new(dir).unwrap();
+
+        let first = cache.get(&file).unwrap();
+        assert!(!first.from_cache);
+        assert_eq!(first.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(fs::read_to_string(&first.path).unwrap(), "first");
+        assert_eq!(server.requests()[0].header("If-None-Match"), None);
+
+        server.route("GET", path, FakeResponse::status(304));
+        let cached = cache.get(&file).unwrap();
+        assert!(cached.from_cache);
+        assert_eq!(cached.path, first.path);
+        assert_eq!(server.requests()[1].header("If-None-Match"), Some("\"v1\""));
+
+        server.route("GET", path, download("\"v2\"", "second"));
+        let updated = cache.get(&file).unwrap();
+        assert!(!updated.from_cache);
+        assert_eq!(updated.etag.as_deref(), Some("\"v2\""));
+        assert_eq!(fs::read_to_string(&updated.path).unwrap(), "second");
+
+        cache.remove(&file).unwrap();
+        assert!(!updated.path.exists());
+        cache.remove(&file).unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+use mime::Mime;
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Number of leading bytes read when sniffing a file's content type
+const SNIFF_LEN: u64 = 512;
+
+/// Magic byte signatures of common file formats, checked in order
+const SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"BM", "image/bmp"),
+    (b"II*\x00", "image/tiff"),
+    (b"MM\x00*", "image/tiff"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x1f\x8b", "application/gzip"),
+    (b"BZh", "application/x-bzip2"),
+    (b"\xfd7zXZ\x00", "application/x-xz"),
+    (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    (b"PAR1", "application/vnd.apache.parquet"),
+    (b"\x93NUMPY", "application/x-npy"),
+    (b"OggS", "audio/ogg"),
+    (b"fLaC", "audio/flac"),
+    (b"ID3", "audio/mpeg"),
+    (b"\x1aE\xdf\xa3", "video/webm"),
+];
+
+/// Content type of a local file, from its extension or else (if `sniff`) its leading bytes
+///
+/// The file is left positioned at its start.
+pub(crate) fn detect(path: &Path, file: &mut File, sniff: bool) -> io::Result<Option<Mime>> {
+    if let Some(mime) = mime_guess::from_path(path).first() {
+        return Ok(Some(mime));
+    }
+    if !sniff {
+        return Ok(None);
+    }
+
+    let mut head = Vec::with_capacity(SNIFF_LEN as usize);
+    file.take(SNIFF_LEN).read_to_end(&mut head)?;
+    file.seek(SeekFrom::Start(0))?;
+    Ok(sniff_bytes(&head))
+}
+
+/// Content type from the leading bytes of a file, if recognized
+pub(crate) fn sniff_bytes(head: &[u8]) -> Option<Mime> {
+    let found = SIGNATURES
+        .iter()
+        .find(|(magic, _)| head.starts_with(magic))
+        .map(|(_, mime)| *mime)
+        .or_else(|| sniff_riff(head))
+        .or_else(|| sniff_text(head))?;
+    found.parse().ok()
+}
+
+/// RIFF containers share a prefix and name their format at offset 8
+fn sniff_riff(head: &[u8]) -> Option<&'static str> {
+    if !head.starts_with(b"RIFF") || head.len() < 12 {
+        return None;
+    }
+    match &head[8..12] {
+        b"WEBP" => Some("image/webp"),
+        b"WAVE" => Some("audio/wav"),
+        b"AVI " => Some("video/x-msvideo"),
+        _ => None,
+    }
+}
+
+/// Text without control characters, distinguishing JSON by its first character
+fn sniff_text(head: &[u8]) -> Option<&'static str> {
+    let text = match std::str::from_utf8(head) {
+        Ok(text) => text,
+        // The sniffed prefix may end partway through a multi-byte character
+        Err(err) if err.error_len().is_none() => {
+            std::str::from_utf8(&head[..err.valid_up_to()]).unwrap()
+        }
+        Err(_) => return None,
+    };
+    let trimmed = text.trim_start();
+    if trimmed.is_empty() || text.chars().any(|c| c.is_control() && !c.is_whitespace()) {
+        return None;
+    }
+    let start: String = trimmed.chars().take(14).collect();
+    match start.to_ascii_lowercase() {
+        s if s.starts_with('{') || s.starts_with('[') => Some("application/json"),
+        s if s.starts_with("<?xml") => Some("application/xml"),
+        s if s.starts_with("<!doctype html") || s.starts_with("<html") => Some("text/html"),
+        _ => Some("text/plain"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_bytes() {
+        let sniff = |head: &[u8]| sniff_bytes(head).map(|m| m.to_string());
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\0\0"), Some("image/png".into()));
+        assert_eq!(sniff(b"\xff\xd8\xff\xe0"), Some("image/jpeg".into()));
+        assert_eq!(sniff(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp".into()));
+        assert_eq!(sniff(b"%PDF-1.7"), Some("application/pdf".into()));
+        assert_eq!(sniff(b"  {\"a\": 1}"), Some("application/json".into()));
+        assert_eq!(sniff(b"<!DOCTYPE html><html>"), Some("text/html".into()));
+        assert_eq!(sniff(b"hello, world\n"), Some("text/plain".into()));
+        // Truncated in the middle of a multi-byte character
+        assert_eq!(
+            sniff("héllo".as_bytes()[..2].as_ref()),
+            Some("text/plain".into())
+        );
+        assert_eq!(sniff(b"\x00\x01\x02\x03"), None);
+        assert_eq!(sniff(b""), None);
+    }
+}
@@ -17,17 +17,36 @@
 use super::parse_data_uri;
 use crate::client::header::{lossy_header, X_DATA_TYPE};
 use crate::client::HttpClient;
+use crate::data::sync::{self, Filter};
 use crate::data::{DataDirItem, DataFile, DataFileItem, DataItem, HasDataPath};
-use crate::error::{err_msg, process_http_response, Error, ResultExt};
+use crate::error::{
+    err_msg, process_http_response, DataOperation, Error, ErrorCategory, ResultExt,
+};
+use crate::RetryBudget;
+use reqwest::{StatusCode, Url};
 
-use std::fs::File;
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
 use std::vec::IntoIter;
 
+/// Result of each upload paired with the uploaded file's name
+type UploadResults = Vec<(String, Result<(), Error>)>;
+
+/// Attempts per file made by `DataDir::delete_recursive`
+const DELETE_ATTEMPTS: u32 = 3;
+/// Delay before the first retry, doubled for each subsequent retry
+const DELETE_RETRY_DELAY: Duration = Duration::from_millis(100);
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// Algorithmia Data Directory
+#[derive(Clone)]
 pub struct DataDir {
     path: String,
     client: HttpClient,
@@ -39,41 +58,130 @@ struct DeletedResponse {
 }
 
 /// Response when deleting a file form the Data API
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct DirectoryDeleted {
     /// Number of files that were deleted
     ///
     /// Note: some backing stores may indicate deletion succeeds for non-existing files
     pub deleted: u64,
     // Placeholder for API stability if additional fields are added later
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     _dummy: (),
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-struct FolderItem {
+/// Raw directory entry from a listing
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DirEntry {
+    /// Name of the directory (relative to the listed directory)
     pub name: String,
+    /// ACL of the directory, if included in the listing
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub acl: Option<DataAcl>,
+    // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
+    _dummy: (),
 }
 
-#[derive(Debug, Deserialize)]
-struct FileItem {
-    pub filename: String,
+/// Raw file entry from a listing
+///
+/// Serializes with the same field names used by the Data API (e.g. `filename`).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FileEntry {
+    /// Name of the file (relative to the listed directory)
+    #[serde(rename = "filename")]
+    pub name: String,
+    /// Size of file in bytes
     pub size: u64,
+    /// Last modified timestamp
     pub last_modified: DateTime<Utc>,
+    /// Content type of the file, if provided by the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// ETag (checksum) of the file contents, if provided by the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    // Placeholder for API stability if additional fields are added later
+    #[serde(skip)]
+    _dummy: (),
+}
+
+/// Contents of a file uploaded with [`DataDir::put_files`](struct.DataDir.html#method.put_files)
+#[derive(Debug, Clone, PartialEq)]
+pub enum UploadSource {
+    /// A local file, streamed from disk with its content type inferred like `put_file`
+    Path(PathBuf),
+    /// In-memory contents, with the content type inferred from the file name's extension
+    Bytes(Vec<u8>),
+
+    /// Non-exhaustive for API stability if sources are added
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl From<PathBuf> for UploadSource {
+    fn from(path: PathBuf) -> Self {
+        UploadSource::Path(path)
+    }
+}
+
+impl<'a> From<&'a Path> for UploadSource {
+    fn from(path: &'a Path) -> Self {
+        UploadSource::Path(path.to_owned())
+    }
+}
+
+impl From<Vec<u8>> for UploadSource {
+    fn from(bytes: Vec<u8>) -> Self {
+        UploadSource::Bytes(bytes)
+    }
+}
+
+impl DataFile {
+    /// Upload from a path or bytes, inferring the content type of bytes from the file name
+    pub(crate) fn put_source(&self, source: UploadSource) -> Result<(), Error> {
+        match source {
+            UploadSource::Path(path) => self.put_file(path),
+            UploadSource::Bytes(bytes) => {
+                let content_type = mime_guess::from_path(self.path()).first();
+                self.put_as(bytes, content_type)
+            }
+            UploadSource::__Nonexhaustive => {
+                bail!("unsupported upload source for '{}'", self.to_data_uri())
+            }
+        }
+    }
+}
+
+/// Raw entry from a directory listing, not bound to a client
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataEntry {
+    /// A file entry
+    File(FileEntry),
+    /// A directory entry
+    Dir(DirEntry),
 }
 
 /// ACL that indicates permissions for a `DataDir`
 /// See also: [`ReadAcl`](enum.ReadAcl.html) enum to construct a `DataACL`
-#[derive(Debug, Deserialize, Serialize)]
+///
+/// ACL verbs other than `read` and `write` are kept as returned by the API, and can be read
+/// with [`verb`](#method.verb) and set with [`with_verb`](#method.with_verb).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct DataAcl {
     /// Read ACL
     pub read: Vec<String>,
+    /// Write ACL, or `None` to leave it unspecified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write: Option<Vec<String>>,
+    #[serde(flatten)]
+    other: BTreeMap<String, Value>,
     // Placeholder for stability with API additions
+    #[serde(skip)]
     _dummy: (),
 }
 
 /// Read access control values
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ReadAcl {
     /// Readable only by owner
     Private,
@@ -81,12 +189,86 @@ pub enum ReadAcl {
     MyAlgorithms,
     /// Readable by any user
     Public,
+    /// Any other list of readers, e.g. `["user://*", "algo://.my/*"]`
+    Other(Vec<String>),
 
     /// Non-exhaustive for API stability if ACL types are added
     #[doc(hidden)]
     __Nonexhaustive,
 }
 
+impl DataAcl {
+    /// The `ReadAcl` this ACL corresponds to, with `ReadAcl::Other` for any other readers
+    ///
+    /// ```
+    /// # use algorithmia::data::{DataAcl, ReadAcl};
+    /// let acl: DataAcl = ReadAcl::Public.into();
+    /// assert_eq!(acl.read_acl(), ReadAcl::Public);
+    /// ```
+    pub fn read_acl(&self) -> ReadAcl {
+        match self.read.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+            [] => ReadAcl::Private,
+            ["algo://.my/*"] => ReadAcl::MyAlgorithms,
+            ["user://*"] => ReadAcl::Public,
+            _ => ReadAcl::Other(self.read.clone()),
+        }
+    }
+
+    /// Set the principals allowed to write to the directory, e.g. `user://*`
+    ///
+    /// ```
+    /// # use algorithmia::data::{DataAcl, ReadAcl};
+    /// let acl = DataAcl::from(ReadAcl::Public).with_write(vec!["algo://.my/*"]);
+    /// assert_eq!(acl.write, Some(vec!["algo://.my/*".to_string()]));
+    /// ```
+    pub fn with_write<I, S>(mut self, writers: I) -> DataAcl
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.write = Some(writers.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the principals of any ACL verb, including verbs not modeled by this crate
+    ///
+    /// `read` and `write` set the corresponding fields.
+    pub fn with_verb<I, S>(mut self, verb: &str, principals: I) -> DataAcl
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let principals: Vec<String> = principals.into_iter().map(Into::into).collect();
+        match verb {
+            "read" => self.read = principals,
+            "write" => self.write = Some(principals),
+            _ => {
+                self.other.insert(verb.to_owned(), Value::from(principals));
+            }
+        }
+        self
+    }
+
+    /// The principals of an ACL verb, if set and a list of strings
+    ///
+    /// ```
+    /// # use algorithmia::data::DataAcl;
+    /// let acl = DataAcl::default().with_verb("delete", vec!["user://alice"]);
+    /// assert_eq!(acl.verb("delete"), Some(vec!["user://alice".to_string()]));
+    /// assert_eq!(acl.verb("write"), None);
+    /// ```
+    pub fn verb(&self, verb: &str) -> Option<Vec<String>> {
+        match verb {
+            "read" => Some(self.read.clone()),
+            "write" => self.write.clone(),
+            _ => self
+                .other
+                .get(verb)
+                .and_then(|v| serde_json::from_value(v.clone()).ok()),
+        }
+    }
+}
+
 impl Default for DataAcl {
     fn default() -> Self {
         ReadAcl::MyAlgorithms.into()
@@ -95,19 +277,17 @@ impl Default for DataAcl {
 
 impl From<ReadAcl> for DataAcl {
     fn from(acl: ReadAcl) -> Self {
-        match acl {
-            ReadAcl::Private | ReadAcl::__Nonexhaustive => DataAcl {
-                read: vec![],
-                _dummy: (),
-            },
-            ReadAcl::MyAlgorithms => DataAcl {
-                read: vec!["algo://.my/*".into()],
-                _dummy: (),
-            },
-            ReadAcl::Public => DataAcl {
-                read: vec!["user://*".into()],
-                _dummy: (),
-            },
+        let read = match acl {
+            ReadAcl::Private | ReadAcl::__Nonexhaustive => vec![],
+            ReadAcl::MyAlgorithms => vec!["algo://.my/*".into()],
+            ReadAcl::Public => vec!["user://*".into()],
+            ReadAcl::Other(read) => read,
+        };
+        DataAcl {
+            read,
+            write: None,
+            other: BTreeMap::new(),
+            _dummy: (),
         }
     }
 }
@@ -116,9 +296,303 @@ impl From<ReadAcl> for DataAcl {
 #[derive(Debug, Deserialize)]
 struct DirectoryShow {
     pub acl: Option<DataAcl>,
-    pub folders: Option<Vec<FolderItem>>,
-    pub files: Option<Vec<FileItem>>,
+    pub folders: Option<Vec<DirEntry>>,
+    pub files: Option<Vec<FileEntry>>,
+    pub marker: Option<String>,
+}
+
+/// Request body when updating the ACL of a Directory
+#[derive(Debug, Serialize)]
+struct AclUpdate {
+    acl: DataAcl,
+}
+
+/// Directory details and the first page of its listing, returned by `DataDir::show`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectoryInfo {
+    /// ACL indicates permissions for this `DataDir`
+    pub acl: Option<DataAcl>,
+    /// Entries on the first page of the listing (directories before files)
+    pub entries: Vec<DataEntry>,
+    /// Marker for fetching the next page, if the listing has more entries
+    pub marker: Option<String>,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+/// Progress of a `DataDir::move_to_with_progress` call
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveProgress {
+    /// Number of files copied to the destination so far
+    pub files_copied: u64,
+    /// Total number of files being moved
+    pub total_files: u64,
+    /// Bytes copied to the destination so far
+    pub bytes_copied: u64,
+    /// Total size in bytes of the files being moved
+    pub total_bytes: u64,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+/// Outcome of a `DataDir::delete_recursive` or `DataDir::delete_files` call
+#[derive(Debug)]
+pub struct RecursiveDeleteReport {
+    /// Number of files that were deleted
+    pub deleted: u64,
+    /// Data URIs of files and directories that could not be deleted, with the last error for each
+    pub failures: Vec<(String, Error)>,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+impl RecursiveDeleteReport {
+    /// Returns true if everything was deleted
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Entries gathered by `DirectoryListing::collect_partial`, with the page that failed if any
+#[derive(Debug)]
+pub struct PartialListing {
+    /// ACL indicates permissions for this `DataDir`, if any page was listed
+    pub acl: Option<DataAcl>,
+    /// Entries from every page that was listed successfully
+    pub items: Vec<DataItem>,
+    /// Pages that could not be listed
+    pub errors: Vec<PageError>,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+impl PartialListing {
+    /// Returns true if every page was listed
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Failure to list one page of a directory
+#[derive(Debug)]
+pub struct PageError {
+    /// Marker of the page that failed, or `None` for the first page
+    ///
+    /// Pass it to [`ListOptions::marker`](struct.ListOptions.html#method.marker)
+    /// to resume listing from this page.
     pub marker: Option<String>,
+    /// Error listing the page
+    pub error: Error,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+/// Field used to sort directory listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// Sort by name
+    Name,
+    /// Sort files by size
+    Size,
+    /// Sort files by last modified timestamp
+    LastModified,
+
+    /// Non-exhaustive for API stability if sort keys are added
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// Direction to sort directory listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Smallest, oldest, or alphabetically first entries first
+    Ascending,
+    /// Largest, newest, or alphabetically last entries first
+    Descending,
+}
+
+/// Options for listing a `DataDir`
+///
+/// The Data API returns listings unsorted, so sorting is done by the client.
+/// By default the whole listing is fetched before the first entry is returned;
+/// use [`per_page`](#method.per_page) to only sort within each page and keep iteration lazy.
+///
+/// Directories are always returned before files. When sorting by `Size` or `LastModified`,
+/// directories (which have neither) are sorted by name in the same order.
+///
+/// # Examples
+/// ```no_run
+/// # use algorithmia::Algorithmia;
+/// # use algorithmia::data::{DataEntry, ListOptions, SortKey, SortOrder};
+/// let client = Algorithmia::client("111112222233333444445555566")?;
+/// let options = ListOptions::new().sort_by(SortKey::LastModified, SortOrder::Descending);
+/// for entry in client.dir(".my/my_dir").list_entries_with(options) {
+///     if let DataEntry::File(f) = entry? {
+///         println!("{} ({})", f.name, f.last_modified);
+///     }
+/// }
+/// # Ok::<(), Box<std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    sort: Option<(SortKey, SortOrder)>,
+    per_page: bool,
+    marker: Option<String>,
+}
+
+impl ListOptions {
+    /// Options for an unsorted listing
+    pub fn new() -> ListOptions {
+        ListOptions::default()
+    }
+
+    /// Sort entries by `key` in `order`
+    pub fn sort_by(mut self, key: SortKey, order: SortOrder) -> ListOptions {
+        self.sort = Some((key, order));
+        self
+    }
+
+    /// Sort each page of the listing separately instead of the whole listing (default: `false`)
+    pub fn per_page(mut self, per_page: bool) -> ListOptions {
+        self.per_page = per_page;
+        self
+    }
+
+    /// Start listing at the page identified by `marker`, e.g. from a `PageError`
+    pub fn marker(mut self, marker: &str) -> ListOptions {
+        self.marker = Some(marker.to_owned());
+        self
+    }
+
+    fn sort(&self, folders: &mut [DirEntry], files: &mut [FileEntry]) {
+        let (key, order) = match self.sort {
+            Some(sort) => sort,
+            None => return,
+        };
+        folders.sort_by(|a, b| a.name.cmp(&b.name));
+        files.sort_by(|a, b| match key {
+            SortKey::Size => a.size.cmp(&b.size),
+            SortKey::LastModified => a.last_modified.cmp(&b.last_modified),
+            SortKey::Name | SortKey::__Nonexhaustive => a.name.cmp(&b.name),
+        });
+        if order == SortOrder::Descending {
+            folders.reverse();
+            files.reverse();
+        }
+    }
+}
+
+/// Options for uploading a local directory with `DataDir::put_dir`
+///
+/// # Examples
+/// ```
+/// # use algorithmia::data::UploadOptions;
+/// use algorithmia::data::sync::Filter;
+///
+/// let options = UploadOptions::new()
+///     .parallelism(8)
+///     .filter(Filter::new().exclude("*.tmp"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct UploadOptions {
+    parallelism: usize,
+    filter: Filter,
+}
+
+impl Default for UploadOptions {
+    fn default() -> UploadOptions {
+        UploadOptions {
+            parallelism: 4,
+            filter: Filter::default(),
+        }
+    }
+}
+
+impl UploadOptions {
+    /// Options that upload every file not skipped by `.algoignore` with 4 concurrent requests
+    pub fn new() -> UploadOptions {
+        UploadOptions::default()
+    }
+
+    /// Maximum number of files uploaded concurrently (default: 4)
+    pub fn parallelism(mut self, parallelism: usize) -> UploadOptions {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    /// Skip files according to `filter` (default: only the `.algoignore` file)
+    ///
+    /// See [`Filter`](sync/struct.Filter.html) for the include and exclude pattern syntax.
+    pub fn filter(mut self, filter: Filter) -> UploadOptions {
+        self.filter = filter;
+        self
+    }
+}
+
+/// Options for downloading a directory with `DataDir::download_to`
+///
+/// # Examples
+/// ```
+/// # use algorithmia::data::DownloadOptions;
+/// let options = DownloadOptions::new().parallelism(8).skip_unchanged(true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    parallelism: usize,
+    skip_unchanged: bool,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> DownloadOptions {
+        DownloadOptions {
+            parallelism: 4,
+            skip_unchanged: false,
+        }
+    }
+}
+
+impl DownloadOptions {
+    /// Options that download every file with 4 concurrent requests
+    pub fn new() -> DownloadOptions {
+        DownloadOptions::default()
+    }
+
+    /// Maximum number of files downloaded concurrently (default: 4)
+    pub fn parallelism(mut self, parallelism: usize) -> DownloadOptions {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    /// Skip files whose local copy appears up to date (default: `false`)
+    ///
+    /// A local copy is up to date if its size matches and either its MD5 checksum matches
+    /// the remote ETag or, when the ETag isn't a checksum, it was modified after the remote file.
+    pub fn skip_unchanged(mut self, skip_unchanged: bool) -> DownloadOptions {
+        self.skip_unchanged = skip_unchanged;
+        self
+    }
+}
+
+/// Outcome of a `DataDir::download_to` call
+///
+/// Paths are relative to the downloaded directory and use `/` as the separator.
+#[derive(Debug)]
+pub struct DownloadReport {
+    /// Files that were downloaded
+    pub downloaded: Vec<String>,
+    /// Files that were skipped because the local copy was up to date
+    pub skipped: Vec<String>,
+    /// Files that could not be downloaded, with the error for each
+    pub failures: Vec<(String, Error)>,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+impl DownloadReport {
+    /// Returns true if every file was downloaded or skipped
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
 }
 
 /// Iterator over the listing of a `DataDir`
@@ -126,53 +600,186 @@ pub struct DirectoryListing<'a> {
     /// ACL indicates permissions for this `DataDir`
     pub acl: Option<DataAcl>,
     dir: &'a DataDir,
-    folders: IntoIter<FolderItem>,
-    files: IntoIter<FileItem>,
-    marker: Option<String>,
-    query_count: u32,
+    entries: DirectoryEntries<'a>,
 }
 
 impl<'a> DirectoryListing<'a> {
-    fn new(dir: &'a DataDir) -> DirectoryListing<'a> {
+    /// The `ReadAcl` of this `DataDir`, once a page with its ACL has been listed
+    pub fn read_acl(&self) -> Option<ReadAcl> {
+        self.acl.as_ref().map(DataAcl::read_acl)
+    }
+
+    fn new(dir: &'a DataDir, options: ListOptions) -> DirectoryListing<'a> {
         DirectoryListing {
             acl: None,
-            dir: dir,
+            dir,
+            entries: DirectoryEntries::new(dir, options),
+        }
+    }
+
+    /// Collect the remaining entries, stopping at the first page that fails to list
+    ///
+    /// Unlike collecting the iterator into a `Result`, entries listed before a failure are kept.
+    /// Each page's marker comes from the page before it, so listing cannot continue past a
+    /// failed page; retry it later with [`ListOptions::marker`](struct.ListOptions.html#method.marker).
+    /// When sorting the whole listing, the entries that were listed are sorted together.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::data::ListOptions;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let dir = client.dir(".my/my_dir");
+    /// let mut listing = dir.list().collect_partial();
+    /// while let Some(failed) = listing.errors.pop() {
+    ///     println!("Retrying after error: {}", failed.error);
+    ///     let options = failed.marker.as_ref().map_or(ListOptions::new(), |m| ListOptions::new().marker(m));
+    ///     let retried = dir.list_with(options).collect_partial();
+    ///     listing.items.extend(retried.items);
+    ///     listing.errors.extend(retried.errors);
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn collect_partial(mut self) -> PartialListing {
+        // Sort once at the end, so entries from pages before a failure aren't discarded
+        let options = self.entries.options.clone();
+        self.entries.options.per_page = true;
+
+        let mut folders = Vec::new();
+        let mut files = Vec::new();
+        let mut errors = Vec::new();
+        while let Some(entry) = self.entries.next() {
+            match entry {
+                Ok(DataEntry::Dir(d)) => folders.push(d),
+                Ok(DataEntry::File(f)) => files.push(f),
+                Err(error) => {
+                    errors.push(PageError {
+                        marker: self.entries.marker.clone(),
+                        error,
+                        _dummy: (),
+                    });
+                    break;
+                }
+            }
+        }
+
+        options.sort(&mut folders, &mut files);
+        let items = folders
+            .into_iter()
+            .map(DataEntry::Dir)
+            .chain(files.into_iter().map(DataEntry::File))
+            .map(|entry| entry_to_item(self.dir, entry))
+            .collect();
+        PartialListing {
+            acl: self.acl.or(self.entries.acl),
+            items,
+            errors,
+            _dummy: (),
+        }
+    }
+}
+
+impl<'a> Iterator for DirectoryListing<'a> {
+    type Item = Result<DataItem, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.entries.next();
+        if self.acl.is_none() {
+            self.acl = self.entries.acl.clone();
+        }
+        next.map(|entry| entry.map(|entry| entry_to_item(self.dir, entry)))
+    }
+}
+
+fn entry_to_item(dir: &DataDir, entry: DataEntry) -> DataItem {
+    match entry {
+        DataEntry::Dir(d) => DataItem::Dir(DataDirItem {
+            dir: dir.child(&d.name),
+        }),
+        DataEntry::File(f) => DataItem::File(DataFileItem {
+            size: f.size,
+            last_modified: f.last_modified,
+            content_type: f.content_type,
+            etag: f.etag,
+            file: dir.child(&f.name),
+        }),
+    }
+}
+
+/// Iterator over the raw entries of a `DataDir` listing
+///
+/// Directories are yielded before files on each page.
+pub struct DirectoryEntries<'a> {
+    /// ACL indicates permissions for this `DataDir`
+    pub acl: Option<DataAcl>,
+    dir: &'a DataDir,
+    options: ListOptions,
+    folders: IntoIter<DirEntry>,
+    files: IntoIter<FileEntry>,
+    // Pages listed so far when sorting the whole listing, kept if a later page fails
+    listed_folders: Vec<DirEntry>,
+    listed_files: Vec<FileEntry>,
+    marker: Option<String>,
+    query_count: u32,
+}
+
+impl<'a> DirectoryEntries<'a> {
+    fn new(dir: &'a DataDir, options: ListOptions) -> DirectoryEntries<'a> {
+        DirectoryEntries {
+            acl: None,
+            dir,
+            marker: options.marker.clone(),
+            options,
             folders: Vec::new().into_iter(),
             files: Vec::new().into_iter(),
-            marker: None,
+            listed_folders: Vec::new(),
+            listed_files: Vec::new(),
             query_count: 0,
         }
     }
+
+    /// Fetch the next page, or every remaining page when sorting the whole listing
+    ///
+    /// The marker only advances past pages that were listed, so calling `next` again
+    /// after an error retries the failed page.
+    fn fetch(&mut self) -> Result<(), Error> {
+        let whole_listing = self.options.sort.is_some() && !self.options.per_page;
+        loop {
+            let ds = get_directory(self.dir, self.marker.clone())?;
+            self.query_count += 1;
+            self.acl = ds.acl;
+            self.listed_folders.extend(ds.folders.unwrap_or_default());
+            self.listed_files.extend(ds.files.unwrap_or_default());
+            self.marker = ds.marker;
+            if !whole_listing || self.marker.is_none() {
+                break;
+            }
+        }
+        let mut folders = mem::take(&mut self.listed_folders);
+        let mut files = mem::take(&mut self.listed_files);
+        self.options.sort(&mut folders, &mut files);
+        self.folders = folders.into_iter();
+        self.files = files.into_iter();
+        Ok(())
+    }
 }
 
-impl<'a> Iterator for DirectoryListing<'a> {
-    type Item = Result<DataItem, Error>;
+impl<'a> Iterator for DirectoryEntries<'a> {
+    type Item = Result<DataEntry, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.folders.next() {
             // Return folders first
-            Some(d) => Some(Ok(DataItem::Dir(DataDirItem {
-                dir: self.dir.child(&d.name),
-            }))),
+            Some(d) => Some(Ok(DataEntry::Dir(d))),
             None => {
                 match self.files.next() {
                     // Return files second
-                    Some(f) => Some(Ok(DataItem::File(DataFileItem {
-                        size: f.size,
-                        last_modified: f.last_modified,
-                        file: self.dir.child(&f.filename),
-                    }))),
+                    Some(f) => Some(Ok(DataEntry::File(f))),
                     None => {
                         // Query if there is another page of files/folders
                         if self.query_count == 0 || self.marker.is_some() {
-                            self.query_count += 1;
-                            match get_directory(self.dir, self.marker.clone()) {
-                                Ok(ds) => {
-                                    self.folders = ds.folders.unwrap_or_else(Vec::new).into_iter();
-                                    self.files = ds.files.unwrap_or_else(Vec::new).into_iter();
-                                    self.marker = ds.marker;
-                                    self.next()
-                                }
+                            match self.fetch() {
+                                Ok(()) => self.next(),
                                 Err(err) => Some(Err(err)),
                             }
                         } else {
@@ -190,14 +797,17 @@ fn get_directory(dir: &DataDir, marker: Option<String>) -> Result<DirectoryShow,
     if let Some(ref m) = marker {
         url.query_pairs_mut().append_pair("marker", m);
     }
+    fetch_directory(dir, url)
+}
 
+fn fetch_directory(dir: &DataDir, url: Url) -> Result<DirectoryShow, Error> {
     let mut res = dir
         .client
-        .get(url)
-        .send()
+        .send(dir.client.get(url))
         .with_context(|| format!("request error listing directory '{}'", dir.to_data_uri()))
         .and_then(process_http_response)
-        .with_context(|| format!("response error listing directory '{}'", dir.to_data_uri()))?;
+        .with_context(|| format!("response error listing directory '{}'", dir.to_data_uri()))
+        .map_err(|err| err.with_data_error(DataOperation::List, dir.to_data_uri()))?;
 
     match res.headers().get(X_DATA_TYPE).map(lossy_header) {
         Some(ref dt) if dt == "directory" => (),
@@ -215,11 +825,17 @@ fn get_directory(dir: &DataDir, marker: Option<String>) -> Result<DirectoryShow,
     })
 }
 
+impl std::fmt::Debug for DataDir {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("DataDir").field(&self.to_data_uri()).finish()
+    }
+}
+
 impl HasDataPath for DataDir {
     #[doc(hidden)]
     fn new(client: HttpClient, path: &str) -> Self {
         DataDir {
-            client: client,
+            client,
             path: parse_data_uri(path).to_string(),
         }
     }
@@ -252,92 +868,378 @@ impl DataDir {
     /// };
     /// # Ok::<(), Box<std::error::Error>>(())
     /// ```
-    pub fn list(&self) -> DirectoryListing {
-        DirectoryListing::new(self)
+    pub fn list(&self) -> DirectoryListing<'_> {
+        DirectoryListing::new(self, ListOptions::default())
     }
 
-    /// Create a Directory
+    /// List a Directory with sorting options
     ///
-    /// Use `DataAcl::default()` or the `ReadAcl` enum to set the ACL
+    /// See [`ListOptions`](struct.ListOptions.html) for details.
     ///
     /// # Examples
     /// ```no_run
     /// # use algorithmia::Algorithmia;
-    /// # use algorithmia::data::DataAcl;
+    /// # use algorithmia::data::{DataItem, HasDataPath, ListOptions, SortKey, SortOrder};
     /// let client = Algorithmia::client("111112222233333444445555566")?;
-    /// let my_dir = client.dir(".my/my_dir");
-    /// match my_dir.create(DataAcl::default()) {
-    ///   Ok(_) => println!("Successfully created Directory"),
-    ///   Err(e) => println!("Error created directory: {}", e),
-    /// };
+    /// let options = ListOptions::new().sort_by(SortKey::Size, SortOrder::Descending);
+    /// for entry in client.dir(".my/my_dir").list_with(options) {
+    ///     if let DataItem::File(f) = entry? {
+    ///         println!("{}: {} bytes", f.to_data_uri(), f.size);
+    ///     }
+    /// }
     /// # Ok::<(), Box<std::error::Error>>(())
     /// ```
-    pub fn create<Acl: Into<DataAcl>>(&self, acl: Acl) -> Result<(), Error> {
-        let parent = self.parent().ok_or_else(|| {
-            err_msg(format!(
-                "URI {} does not have a valid parent",
-                self.to_data_uri()
-            ))
-        })?;
-        let parent_url = parent.to_url()?;
-
-        let input_data = FolderItem {
-            name: self
-                .basename()
-                .ok_or_else(|| {
-                    err_msg(format!(
-                        "Data URI {} does not have a valid basename",
-                        self.to_data_uri()
-                    ))
-                })?
-                .into(),
-            acl: Some(acl.into()),
-        };
+    pub fn list_with(&self, options: ListOptions) -> DirectoryListing<'_> {
+        DirectoryListing::new(self, options)
+    }
 
-        // POST request
-        self.client
-            .post(parent_url)
-            .json(&input_data)
-            .send()
-            .with_context(|| format!("request error creating directory '{}'", self.to_data_uri()))
-            .and_then(process_http_response)
-            .with_context(|| {
-                format!("response error creating directory '{}'", self.to_data_uri())
-            })?;
+    /// List a Directory as an async `Stream` [feature = "async"]
+    ///
+    /// Pages are fetched lazily as the stream is polled, each by the blocking client on a
+    /// worker thread (see [`r#async`](../async/index.html)) so the executor isn't blocked.
+    /// Like `list`, directories are yielded before files on each page,
+    /// and the stream ends after yielding an error.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::data::{DataItem, HasDataPath};
+    /// use futures::StreamExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// client.dir(".my/my_dir")
+    ///     .list_stream()
+    ///     .for_each_concurrent(4, |entry| async move {
+    ///         if let Ok(DataItem::File(f)) = entry {
+    ///             println!("File: {}", f.to_data_uri());
+    ///         }
+    ///     })
+    ///     .await;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn list_stream(&self) -> impl futures::Stream<Item = Result<DataItem, Error>> {
+        use crate::unblock::unblock;
+        use futures::stream::{self, StreamExt};
 
-        Ok(())
+        // State is the directory and the marker of the next page, or None when done
+        let state = Some((self.clone(), None));
+        stream::unfold(state, |state| async move {
+            let (dir, marker) = state?;
+            let page_dir = dir.clone();
+            let page = unblock(move || get_directory(&page_dir, marker)).await;
+            match page {
+                Ok(ds) => {
+                    let folders = ds
+                        .folders
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(DataEntry::Dir);
+                    let files = ds
+                        .files
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(DataEntry::File);
+                    let items: Vec<_> = folders
+                        .chain(files)
+                        .map(|entry| Ok(entry_to_item(&dir, entry)))
+                        .collect();
+                    let next = ds.marker.map(|marker| (dir, Some(marker)));
+                    Some((items, next))
+                }
+                Err(err) => Some((vec![Err(err)], None)),
+            }
+        })
+        .flat_map(stream::iter)
     }
 
-    /// Delete a Directory
+    /// Fetch Directory details (including its ACL) with a single request
+    ///
+    /// Unlike `list`, this does not require iterating to inspect the ACL,
+    /// and only the first page of entries is returned.
     ///
     /// # Examples
     /// ```no_run
     /// # use algorithmia::Algorithmia;
     /// let client = Algorithmia::client("111112222233333444445555566")?;
-    /// let my_dir = client.dir(".my/my_dir");
-    /// match my_dir.delete(false) {
-    ///   Ok(_) => println!("Successfully deleted Directory"),
-    ///   Err(err) => println!("Error deleting directory: {}", err),
-    /// };
+    /// let info = client.dir(".my/my_dir").show()?;
+    /// println!("Readable by: {:?}", info.acl.map(|acl| acl.read));
     /// # Ok::<(), Box<std::error::Error>>(())
     /// ```
-    pub fn delete(&self, force: bool) -> Result<DirectoryDeleted, Error> {
-        // DELETE request
-        let mut url = self.to_url()?;
-        if force {
-            url.query_pairs_mut().append_pair("force", "true");
+    pub fn show(&self) -> Result<DirectoryInfo, Error> {
+        let ds = get_directory(self, None)?;
+        let folders = ds
+            .folders
+            .unwrap_or_default()
+            .into_iter()
+            .map(DataEntry::Dir);
+        let files = ds
+            .files
+            .unwrap_or_default()
+            .into_iter()
+            .map(DataEntry::File);
+        Ok(DirectoryInfo {
+            acl: ds.acl,
+            entries: folders.chain(files).collect(),
+            marker: ds.marker,
+            _dummy: (),
+        })
+    }
+
+    /// Get the ACL of an existing Directory
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::data::ReadAcl;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let acl = client.dir(".my/my_dir").get_acl()?;
+    /// if acl.read_acl() == ReadAcl::Public {
+    ///     println!("Directory is public");
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn get_acl(&self) -> Result<DataAcl, Error> {
+        let mut url = self.to_url()?;
+        url.query_pairs_mut().append_pair("acl", "true");
+        fetch_directory(self, url)?.acl.ok_or_else(|| {
+            err_msg(format!(
+                "API response missing ACL for directory '{}'",
+                self.to_data_uri()
+            ))
+        })
+    }
+
+    /// Replace the ACL of an existing Directory
+    ///
+    /// Use `DataAcl::default()` or the `ReadAcl` enum to set the ACL
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::data::ReadAcl;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// client.dir(".my/my_dir").update_acl(ReadAcl::Private)?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn update_acl<Acl: Into<DataAcl>>(&self, acl: Acl) -> Result<(), Error> {
+        let url = self.to_url()?;
+        let body = AclUpdate { acl: acl.into() };
+        self.client
+            .send(self.client.patch(url).json(&body))
+            .with_context(|| format!("request error updating ACL of '{}'", self.to_data_uri()))
+            .and_then(process_http_response)
+            .with_context(|| format!("response error updating ACL of '{}'", self.to_data_uri()))
+            .map_err(|err| err.with_data_error(DataOperation::UpdateAcl, self.to_data_uri()))?;
+
+        Ok(())
+    }
+
+    /// List the raw entries of a Directory without constructing `DataFile`/`DataDir` handles
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::data::DataEntry;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// for entry in client.dir(".my/my_dir").list_entries() {
+    ///     match entry? {
+    ///         DataEntry::File(f) => println!("File: {} ({} bytes)", f.name, f.size),
+    ///         DataEntry::Dir(d) => println!("Dir: {}", d.name),
+    ///     }
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn list_entries(&self) -> DirectoryEntries<'_> {
+        DirectoryEntries::new(self, ListOptions::default())
+    }
+
+    /// List the raw entries of a Directory with sorting options
+    ///
+    /// See [`ListOptions`](struct.ListOptions.html) for details.
+    pub fn list_entries_with(&self, options: ListOptions) -> DirectoryEntries<'_> {
+        DirectoryEntries::new(self, options)
+    }
+
+    /// Create a Directory
+    ///
+    /// Use `DataAcl::default()` or the `ReadAcl` enum to set the ACL
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::data::DataAcl;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let my_dir = client.dir(".my/my_dir");
+    /// match my_dir.create(DataAcl::default()) {
+    ///   Ok(_) => println!("Successfully created Directory"),
+    ///   Err(e) => println!("Error created directory: {}", e),
+    /// };
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn create<Acl: Into<DataAcl>>(&self, acl: Acl) -> Result<(), Error> {
+        let parent = self.parent().ok_or_else(|| {
+            err_msg(format!(
+                "URI {} does not have a valid parent",
+                self.to_data_uri()
+            ))
+        })?;
+        let parent_url = parent.to_url()?;
+
+        let input_data = DirEntry {
+            name: self.basename().ok_or_else(|| {
+                err_msg(format!(
+                    "Data URI {} does not have a valid basename",
+                    self.to_data_uri()
+                ))
+            })?,
+            acl: Some(acl.into()),
+            _dummy: (),
+        };
+
+        // POST request
+        self.client
+            .send(self.client.post(parent_url).json(&input_data))
+            .with_context(|| format!("request error creating directory '{}'", self.to_data_uri()))
+            .and_then(process_http_response)
+            .with_context(|| format!("response error creating directory '{}'", self.to_data_uri()))
+            .map_err(|err| err.with_data_error(DataOperation::Create, self.to_data_uri()))?;
+
+        Ok(())
+    }
+
+    /// Create a Directory unless it already exists
+    ///
+    /// Returns `true` if the directory was created, or `false` if it already existed.
+    /// The ACL of an existing directory is left unchanged. Fails if a file exists at this path.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::data::DataAcl;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let my_dir = client.dir(".my/my_dir");
+    /// if my_dir.ensure_created(DataAcl::default())? {
+    ///     println!("Created directory");
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn ensure_created<Acl: Into<DataAcl>>(&self, acl: Acl) -> Result<bool, Error> {
+        match self.create(acl) {
+            Ok(()) => Ok(true),
+            Err(err) => {
+                // Conflicts are also reported for files, and not all backends report them,
+                // so check whether a directory exists before reporting the original error
+                match self.exists_as_dir() {
+                    Ok(true) => Ok(false),
+                    Ok(false) => Err(err),
+                    Err(type_err) if err.status() == Some(StatusCode::CONFLICT) => Err(type_err),
+                    Err(_) => Err(err),
+                }
+            }
+        }
+    }
+
+    /// Create a Directory and any missing parent directories
+    ///
+    /// Analogous to `std::fs::create_dir_all`, existing directories are left unchanged,
+    /// and the ACL is applied to every directory that gets created.
+    /// Fails if a file exists at this path or at any of its parents.
+    /// The connector root and top-level directory (e.g. `data://.my`) are assumed to exist.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::data::DataAcl;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// client.dir(".my/a/b/c").create_all(DataAcl::default())?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn create_all<Acl: Into<DataAcl>>(&self, acl: Acl) -> Result<(), Error> {
+        self.create_missing(acl.into(), &mut Vec::new())
+    }
+
+    /// Create this Directory and any missing parents, recording each created directory
+    ///
+    /// `created` is ordered from the top down and includes directories created before a failure.
+    fn create_missing(&self, acl: DataAcl, created: &mut Vec<DataDir>) -> Result<(), Error> {
+        // Walk up until reaching a directory that exists
+        let mut missing = Vec::new();
+        let mut current = Some(self.clone());
+        while let Some(dir) = current {
+            if dir.path.split_terminator('/').count() <= 2 || dir.exists_as_dir()? {
+                break;
+            }
+            current = dir.parent();
+            missing.push(dir);
+        }
+
+        // Then create the missing directories from the top down
+        for dir in missing.into_iter().rev() {
+            if dir.ensure_created(acl.clone())? {
+                created.push(dir);
+            }
+        }
+        Ok(())
+    }
+
+    /// Check whether a directory exists at this path, failing if it is a file
+    fn exists_as_dir(&self) -> Result<bool, Error> {
+        let url = self.to_url()?;
+        let res = self
+            .client
+            .send(self.client.head(url))
+            .with_context(|| format!("checking existence of '{}'", self.to_data_uri()))
+            .map_err(|err| err.with_data_error(DataOperation::Inspect, self.to_data_uri()))?;
+        if res.status() == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        let res = process_http_response(res)
+            .with_context(|| format!("checking existence of '{}'", self.to_data_uri()))
+            .map_err(|err| err.with_data_error(DataOperation::Inspect, self.to_data_uri()))?;
+
+        match res.headers().get(X_DATA_TYPE).map(lossy_header) {
+            Some(ref dt) if dt == "directory" => Ok(true),
+            Some(ref dt) if dt == "file" => {
+                bail!("'{}' already exists as a file", self.to_data_uri())
+            }
+            data_type => {
+                let dt = data_type.unwrap_or_else(|| "unknown".to_string());
+                bail!("expected content type '{}', received '{}'", "directory", dt)
+            }
+        }
+    }
+
+    /// Delete a Directory
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let my_dir = client.dir(".my/my_dir");
+    /// match my_dir.delete(false) {
+    ///   Ok(_) => println!("Successfully deleted Directory"),
+    ///   Err(err) => println!("Error deleting directory: {}", err),
+    /// };
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn delete(&self, force: bool) -> Result<DirectoryDeleted, Error> {
+        // DELETE request
+        let mut url = self.to_url()?;
+        if force {
+            url.query_pairs_mut().append_pair("force", "true");
         }
 
         // Parse response
         let mut res = self
             .client
-            .delete(url)
-            .send()
+            .send(self.client.delete(url))
             .with_context(|| format!("request error deleting directory '{}'", self.to_data_uri()))
             .and_then(process_http_response)
-            .with_context(|| {
-                format!("response error deleting directory '{}'", self.to_data_uri())
-            })?;
+            .with_context(|| format!("response error deleting directory '{}'", self.to_data_uri()))
+            .map_err(|err| err.with_data_error(DataOperation::Delete, self.to_data_uri()))?;
 
         res.json::<DeletedResponse>()
             .map(|res| res.result)
@@ -349,6 +1251,129 @@ impl DataDir {
             })
     }
 
+    /// Delete a Directory by listing it and deleting its files individually
+    ///
+    /// Intended for connectors (e.g. `s3://` or `dropbox://`) where `delete(true)` is limited.
+    /// Files are deleted by up to `parallelism` concurrent requests, and each file is retried
+    /// a few times on network, rate limiting, or server errors. Files that no longer exist
+    /// count as deleted. Once every file is deleted, the (now empty) directories are deleted
+    /// from the deepest up.
+    ///
+    /// Failures don't stop the deletion of other files; they are collected in the returned
+    /// report. An `Err` is only returned if the directory can't be listed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let report = client.dir("s3://my-bucket/checkpoints").delete_recursive(8)?;
+    /// for (uri, err) in &report.failures {
+    ///     println!("Failed to delete {}: {}", uri, err);
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn delete_recursive(&self, parallelism: usize) -> Result<RecursiveDeleteReport, Error> {
+        let (mut dirs, files) = self.walk()?;
+        let mut report = self.delete_each(files.into_iter().map(|f| f.name), parallelism);
+        if !report.is_complete() {
+            return Ok(report);
+        }
+
+        // Parents are listed before their children, so deleting in reverse removes the deepest first
+        dirs.reverse();
+        let dirs = dirs
+            .into_iter()
+            .map(|d| self.child::<DataDir>(&d.name))
+            .chain(Some(self.clone()));
+        for dir in dirs {
+            let budget = &self.client.retry_budget;
+            if let Err(err) = with_retries(budget, || dir.delete(false).map(|_| ())) {
+                report.failures.push((dir.to_data_uri(), err));
+            }
+        }
+        Ok(report)
+    }
+
+    /// Delete every file below this Directory whose relative path matches one of `patterns`
+    ///
+    /// Patterns use gitignore syntax, as in [`sync::Filter`](../sync/struct.Filter.html):
+    /// `*.tmp` matches at any depth, `/scratch/` only at the top, and `!keep.tmp` excludes
+    /// files matched by an earlier pattern. The Directory is listed with
+    /// [`list_all`](#method.list_all), then matching files are deleted by up to `parallelism`
+    /// concurrent requests with the same retries as
+    /// [`delete_recursive`](#method.delete_recursive). Directories are left in place.
+    ///
+    /// The Data API deletes one file per request, so this overlaps requests rather than
+    /// batching them. Failures don't stop the deletion of other files; they are collected in
+    /// the returned report. An `Err` is only returned for invalid patterns or if the directory
+    /// can't be listed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let report = client.dir(".my/jobs").delete_files(["*.tmp", "/scratch/"], 16)?;
+    /// println!("Deleted {} temp files", report.deleted);
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn delete_files<I, S>(
+        &self,
+        patterns: I,
+        parallelism: usize,
+    ) -> Result<RecursiveDeleteReport, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let matcher = patterns
+            .into_iter()
+            .fold(Filter::new().ignore_file(false), |filter, pattern| {
+                filter.exclude(pattern.as_ref())
+            })
+            .matcher(Path::new(""))?;
+        let names = self
+            .list_all(parallelism)?
+            .into_iter()
+            .filter_map(|entry| match entry {
+                DataEntry::File(f) if matcher.is_ignored(&f.name) => Some(f.name),
+                _ => None,
+            });
+        Ok(self.delete_each(names, parallelism))
+    }
+
+    /// Delete the named files, relative to this Directory, by up to `parallelism` requests
+    fn delete_each<I>(&self, names: I, parallelism: usize) -> RecursiveDeleteReport
+    where
+        I: Iterator<Item = String> + Send,
+    {
+        let queue = Mutex::new(names);
+        let results = Mutex::new(RecursiveDeleteReport {
+            deleted: 0,
+            failures: Vec::new(),
+            _dummy: (),
+        });
+
+        thread::scope(|scope| {
+            for _ in 0..parallelism.max(1) {
+                scope.spawn(|| loop {
+                    let name = match queue.lock().unwrap().next() {
+                        Some(name) => name,
+                        None => break,
+                    };
+                    let file: DataFile = self.child(&name);
+                    let res = with_retries(&self.client.retry_budget, || file.delete());
+                    let mut results = results.lock().unwrap();
+                    match res {
+                        Ok(()) => results.deleted += 1,
+                        Err(err) => results.failures.push((file.to_data_uri(), err)),
+                    }
+                });
+            }
+        });
+
+        results.into_inner().unwrap()
+    }
+
     /// Upload a file to an existing Directory
     ///
     /// # Examples
@@ -365,13 +1390,436 @@ impl DataDir {
     /// ```
     pub fn put_file<P: AsRef<Path>>(&self, file_path: P) -> Result<(), Error> {
         let path_ref = file_path.as_ref();
-        let file = File::open(path_ref)
-            .with_context(|| format!("opening file for upload '{}'", path_ref.display()))?;
-
-        // Safe to unwrap: we've already opened the file or returned an error
-        let filename = path_ref.file_name().unwrap().to_string_lossy();
+        let filename = path_ref
+            .file_name()
+            .ok_or_else(|| err_msg(format!("invalid file path '{}'", path_ref.display())))?
+            .to_string_lossy();
         let data_file: DataFile = self.child(&filename);
-        data_file.put(file)
+        data_file.put_file(path_ref)
+    }
+
+    /// Upload many files to this Directory, returning the result of each upload
+    ///
+    /// Files are uploaded by up to `parallelism` concurrent requests. A failed upload doesn't
+    /// stop the others: results are returned in input order, paired with each file's name.
+    /// Names may include `/` to upload into existing subdirectories.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// use std::path::PathBuf;
+    ///
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let files = vec![
+    ///     ("model.bin", PathBuf::from("/path/to/model.bin").into()),
+    ///     ("labels.txt", b"cat\ndog\n".to_vec().into()),
+    /// ];
+    /// for (name, result) in client.dir(".my/models").put_files(files, 4) {
+    ///     if let Err(err) = result {
+    ///         println!("Failed to upload {}: {}", name, err);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn put_files<I, N>(&self, files: I, parallelism: usize) -> Vec<(String, Result<(), Error>)>
+    where
+        I: IntoIterator<Item = (N, UploadSource)>,
+        N: Into<String>,
+    {
+        let files: Vec<(String, UploadSource)> = files
+            .into_iter()
+            .map(|(name, source)| (name.into(), source))
+            .collect();
+        let queue = Mutex::new(files.into_iter().enumerate());
+        let results = Mutex::new(Vec::new());
+
+        thread::scope(|scope| {
+            for _ in 0..parallelism.max(1) {
+                scope.spawn(|| loop {
+                    let (index, (name, source)) = match queue.lock().unwrap().next() {
+                        Some(next) => next,
+                        None => break,
+                    };
+                    let res = self.child::<DataFile>(&name).put_source(source);
+                    results.lock().unwrap().push((index, name, res));
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap();
+        results.sort_by_key(|(index, _, _)| *index);
+        results
+            .into_iter()
+            .map(|(_, name, res)| (name, res))
+            .collect()
+    }
+
+    /// Recursively upload a local directory into this Directory
+    ///
+    /// This Directory and the subdirectories of uploaded files are created as needed,
+    /// then files are uploaded as with [`put_files`](#method.put_files), returning the result
+    /// of each upload paired with its path relative to `local_dir`. Files matching patterns
+    /// in a `.algoignore` file at the root of `local_dir` or the
+    /// [`filter`](struct.UploadOptions.html#method.filter) are skipped.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// use algorithmia::data::sync::Filter;
+    /// use algorithmia::data::UploadOptions;
+    ///
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let options = UploadOptions::new().filter(Filter::new().exclude("*.tmp"));
+    /// for (path, result) in client.dir(".my/models").put_dir("./models", options)? {
+    ///     if let Err(err) = result {
+    ///         println!("Failed to upload {}: {}", path, err);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn put_dir<P: AsRef<Path>>(
+        &self,
+        local_dir: P,
+        options: UploadOptions,
+    ) -> Result<UploadResults, Error> {
+        let files = sync::local_files(local_dir.as_ref(), &options.filter)?;
+
+        // Sorted so parents are created before their subdirectories
+        let subdirs: BTreeSet<&str> = files
+            .keys()
+            .flat_map(|path| path.match_indices('/').map(move |(i, _)| &path[..i]))
+            .collect();
+        self.create_all(DataAcl::default())?;
+        for subdir in subdirs {
+            self.child::<DataDir>(subdir)
+                .ensure_created(DataAcl::default())?;
+        }
+
+        let uploads = files
+            .into_iter()
+            .map(|(path, local_path)| (path, UploadSource::from(local_path)));
+        Ok(self.put_files(uploads, options.parallelism))
+    }
+
+    /// Recursively download this Directory into a local directory
+    ///
+    /// Every page of every subdirectory is listed, the directory structure (including empty
+    /// directories) is recreated under `local_dir`, and files are downloaded by up to
+    /// [`parallelism`](struct.DownloadOptions.html#method.parallelism) concurrent requests.
+    /// Each file is streamed to disk and only replaces an existing local file once its
+    /// download completes.
+    ///
+    /// Failed downloads don't stop the others; they are collected in the returned report.
+    /// An `Err` is only returned if the directory can't be listed or a local directory
+    /// can't be created.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// use algorithmia::data::DownloadOptions;
+    ///
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let options = DownloadOptions::new().skip_unchanged(true);
+    /// let report = client.dir(".my/models").download_to("./models", options)?;
+    /// println!("{} downloaded, {} up to date", report.downloaded.len(), report.skipped.len());
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn download_to<P: AsRef<Path>>(
+        &self,
+        local_dir: P,
+        options: DownloadOptions,
+    ) -> Result<DownloadReport, Error> {
+        let local_dir = local_dir.as_ref();
+        let (dirs, files) = self.walk()?;
+
+        let local_path = |name: &str| -> Result<PathBuf, Error> {
+            let relative = Path::new(name);
+            if !relative
+                .components()
+                .all(|c| matches!(c, std::path::Component::Normal(_)))
+            {
+                bail!(
+                    "refusing to download '{}' outside of the local directory",
+                    name
+                );
+            }
+            Ok(local_dir.join(relative))
+        };
+        let dir_paths = dirs
+            .iter()
+            .map(|d| local_path(&d.name))
+            .collect::<Result<Vec<_>, _>>()?;
+        for path in Some(local_dir.to_owned()).into_iter().chain(dir_paths) {
+            std::fs::create_dir_all(&path)
+                .with_context(|| format!("creating local directory '{}'", path.display()))?;
+        }
+
+        let queue = Mutex::new(files.into_iter());
+        let report = Mutex::new(DownloadReport {
+            downloaded: Vec::new(),
+            skipped: Vec::new(),
+            failures: Vec::new(),
+            _dummy: (),
+        });
+        thread::scope(|scope| {
+            for _ in 0..options.parallelism {
+                scope.spawn(|| loop {
+                    let entry = match queue.lock().unwrap().next() {
+                        Some(entry) => entry,
+                        None => break,
+                    };
+                    let res = local_path(&entry.name).and_then(|path| {
+                        if options.skip_unchanged
+                            && sync::is_current_copy(&path, &entry).unwrap_or(false)
+                        {
+                            return Ok(false);
+                        }
+                        self.child::<DataFile>(&entry.name).get_to_path(&path)?;
+                        Ok(true)
+                    });
+                    let mut report = report.lock().unwrap();
+                    match res {
+                        Ok(true) => report.downloaded.push(entry.name),
+                        Ok(false) => report.skipped.push(entry.name),
+                        Err(err) => report.failures.push((entry.name, err)),
+                    }
+                });
+            }
+        });
+
+        let mut report = report.into_inner().unwrap();
+        report.downloaded.sort();
+        report.skipped.sort();
+        report.failures.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(report)
+    }
+
+    /// Move this Directory and everything in it to `dest`
+    ///
+    /// `dest` must not exist yet; its missing parents are created.
+    /// The Data API has no rename operation, so the contents are copied file by file
+    /// (preserving directory ACLs and file content types) and the source is deleted once
+    /// every file has been copied.
+    ///
+    /// Files are streamed from the source to the destination without buffering them in memory.
+    ///
+    /// If copying fails, the partially copied destination and any parents created for it are
+    /// deleted, and the source is left untouched. If only deleting the source fails, the complete copy at `dest` is kept
+    /// and the error says so, since the source may have been partially deleted.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// client.dir(".my/models").move_to(&client.dir(".my/archive/models"))?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn move_to(&self, dest: &DataDir) -> Result<(), Error> {
+        self.move_to_with_progress(dest, |_| {})
+    }
+
+    /// Move this Directory to `dest`, reporting progress after each file is copied
+    ///
+    /// See [`move_to`](#method.move_to) for details.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let dest = client.dir(".my/archive/models");
+    /// client.dir(".my/models").move_to_with_progress(&dest, |p| {
+    ///     println!("{}/{} files", p.files_copied, p.total_files);
+    /// })?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn move_to_with_progress<F>(&self, dest: &DataDir, mut progress: F) -> Result<(), Error>
+    where
+        F: FnMut(&MoveProgress),
+    {
+        let src_path = self.path.trim_end_matches('/');
+        let dest_path = dest.path.trim_end_matches('/');
+        if dest_path == src_path || dest_path.starts_with(&format!("{}/", src_path)) {
+            bail!(
+                "cannot move directory '{}' into itself ('{}')",
+                self.to_data_uri(),
+                dest.to_data_uri()
+            );
+        }
+        if dest.exists()? {
+            bail!(
+                "cannot move directory '{}': destination '{}' already exists",
+                self.to_data_uri(),
+                dest.to_data_uri()
+            );
+        }
+
+        let acl = self.show()?.acl.unwrap_or_default();
+        let (dirs, files) = self.walk()?;
+        let mut state = MoveProgress {
+            files_copied: 0,
+            total_files: files.len() as u64,
+            bytes_copied: 0,
+            total_bytes: files.iter().map(|f| f.size).sum(),
+            _dummy: (),
+        };
+
+        let mut created = Vec::new();
+        let copied = dest.create_missing(acl, &mut created).and_then(|_| {
+            for d in dirs {
+                dest.child::<DataDir>(&d.name)
+                    .create(d.acl.unwrap_or_default())?;
+            }
+            for f in &files {
+                let src_file: DataFile = self.child(&f.name);
+                let (body, _, content_type) = src_file.get_body()?;
+                let content_type = content_type.and_then(|ct| ct.parse().ok());
+                dest.child::<DataFile>(&f.name).put_as(body, content_type)?;
+
+                state.files_copied += 1;
+                state.bytes_copied += f.size;
+                progress(&state);
+            }
+            Ok(())
+        });
+
+        if let Err(err) = copied {
+            // Remove the copy first, then the (otherwise empty) parents that were created for it
+            let rollback = created.iter().rev().try_for_each(|dir| {
+                dir.delete(dir.path == dest.path)?;
+                Ok::<_, Error>(())
+            });
+            let context = match rollback {
+                Ok(_) => format!(
+                    "moving directory '{}' to '{}' (partial copy was removed)",
+                    self.to_data_uri(),
+                    dest.to_data_uri()
+                ),
+                Err(rollback) => format!(
+                    "moving directory '{}' to '{}' (failed to remove partial copy: {})",
+                    self.to_data_uri(),
+                    dest.to_data_uri(),
+                    rollback
+                ),
+            };
+            return Err(err).context(context);
+        }
+
+        self.delete(true).with_context(|| {
+            format!(
+                "copied directory '{}' to '{}', but failed to delete the source",
+                self.to_data_uri(),
+                dest.to_data_uri()
+            )
+        })?;
+        Ok(())
+    }
+
+    /// Recursively list every file and subdirectory below this Directory
+    ///
+    /// Entry names are paths relative to this Directory, e.g. `sub/file.txt`. Subdirectories
+    /// are listed by up to `parallelism` concurrent requests, one depth at a time, so large
+    /// trees take about one round-trip per level instead of one per directory. Pages of a
+    /// single directory are linked by markers and are still fetched in order.
+    ///
+    /// Entries are returned level by level (parents before their children) and in listing
+    /// order within each directory. Unlike [`list_entries`](#method.list_entries), the whole
+    /// tree is collected before returning; any failed page fails the call.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// use algorithmia::data::DataEntry;
+    ///
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// for entry in client.dir(".my/datasets").list_all(8)? {
+    ///     if let DataEntry::File(file) = entry {
+    ///         println!("{} ({} bytes)", file.name, file.size);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn list_all(&self, parallelism: usize) -> Result<Vec<DataEntry>, Error> {
+        let mut entries = Vec::new();
+        let mut level = vec![String::new()];
+        while !level.is_empty() {
+            let queue = Mutex::new(level.into_iter());
+            let listings = Mutex::new(Vec::new());
+            thread::scope(|scope| {
+                for _ in 0..parallelism.max(1) {
+                    scope.spawn(|| loop {
+                        let prefix = match queue.lock().unwrap().next() {
+                            Some(prefix) => prefix,
+                            None => break,
+                        };
+                        let dir = if prefix.is_empty() {
+                            self.clone()
+                        } else {
+                            self.child(&prefix)
+                        };
+                        let listed = dir.list_entries().collect::<Result<Vec<_>, Error>>();
+                        listings.lock().unwrap().push((prefix, listed));
+                    });
+                }
+            });
+
+            let mut listings = listings.into_inner().unwrap();
+            listings.sort_by(|a, b| a.0.cmp(&b.0));
+            level = Vec::new();
+            for (prefix, listed) in listings {
+                let join = |name: &str| match prefix.as_str() {
+                    "" => name.to_owned(),
+                    prefix => format!("{}/{}", prefix, name),
+                };
+                for entry in listed? {
+                    entries.push(match entry {
+                        DataEntry::Dir(mut d) => {
+                            d.name = join(&d.name);
+                            level.push(d.name.clone());
+                            DataEntry::Dir(d)
+                        }
+                        DataEntry::File(mut f) => {
+                            f.name = join(&f.name);
+                            DataEntry::File(f)
+                        }
+                    });
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Recursively list subdirectories (parents first) and files
+    ///
+    /// Entry names are paths relative to this Directory, e.g. `sub/file.txt`.
+    pub(crate) fn walk(&self) -> Result<(Vec<DirEntry>, Vec<FileEntry>), Error> {
+        let mut dirs = Vec::new();
+        let mut files = Vec::new();
+        let mut pending = vec![String::new()];
+        while let Some(prefix) = pending.pop() {
+            let dir = if prefix.is_empty() {
+                self.clone()
+            } else {
+                self.child(&prefix)
+            };
+            let join = |name: &str| match prefix.as_str() {
+                "" => name.to_owned(),
+                prefix => format!("{}/{}", prefix, name),
+            };
+            for entry in dir.list_entries() {
+                match entry? {
+                    DataEntry::Dir(mut d) => {
+                        d.name = join(&d.name);
+                        pending.push(d.name.clone());
+                        dirs.push(d);
+                    }
+                    DataEntry::File(mut f) => {
+                        f.name = join(&f.name);
+                        files.push(f);
+                    }
+                }
+            }
+        }
+        Ok((dirs, files))
     }
 
     /// Instantiate `DataFile` or `DataDir` as a child of this `DataDir`
@@ -384,6 +1832,37 @@ impl DataDir {
     }
 }
 
+/// Run a delete request, retrying transient failures and treating missing items as deleted
+///
+/// Retries are limited by the client's retry budget.
+fn with_retries<F: Fn() -> Result<(), Error>>(
+    budget: &RetryBudget,
+    delete: F,
+) -> Result<(), Error> {
+    let mut delay = DELETE_RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        let err = match delete() {
+            Ok(()) => {
+                budget.record_success();
+                return Ok(());
+            }
+            Err(err) => err,
+        };
+        let retryable = match err.status() {
+            Some(StatusCode::NOT_FOUND) => return Ok(()),
+            Some(status) => status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+            None => err.category() == ErrorCategory::Network,
+        };
+        if !retryable || attempt >= DELETE_ATTEMPTS || !budget.try_retry() {
+            return Err(err);
+        }
+        thread::sleep(delay);
+        delay *= 2;
+        attempt += 1;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -427,6 +1906,648 @@ mod tests {
         assert!(dir.parent().is_none());
     }
 
+    #[test]
+    fn test_put_unsupported_source() {
+        let file = mock_client().file("data://.my/up");
+        let err = file.put_source(UploadSource::__Nonexhaustive).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported upload source for 'data://.my/up'"
+        );
+    }
+
+    #[test]
+    fn test_file_entry_roundtrip() {
+        let json = r#"{"filename":"a.txt","size":3,"last_modified":"2015-03-14T08:00:00Z"}"#;
+        let entry: FileEntry = serde_json::from_str(json).unwrap();
+        assert_eq!(entry.name, "a.txt");
+        assert_eq!(serde_json::to_string(&entry).unwrap(), json);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_ensure_created() {
+        use crate::testing::{FakeResponse, FakeServer};
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let dir = client.dir("data://.my/foo");
+
+        server.route("POST", "/v1/connector/data/.my", FakeResponse::status(200));
+        assert!(dir.ensure_created(DataAcl::default()).unwrap());
+
+        server.route("POST", "/v1/connector/data/.my", FakeResponse::status(409));
+        server.data_dir("data://.my/foo", serde_json::json!({}));
+        assert!(!dir.ensure_created(DataAcl::default()).unwrap());
+
+        server.route("POST", "/v1/connector/data/.my", FakeResponse::status(500));
+        assert!(!dir.ensure_created(DataAcl::default()).unwrap());
+
+        server.data_file("data://.my/foo", "not a directory");
+        let err = dir.ensure_created(DataAcl::default()).unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::INTERNAL_SERVER_ERROR));
+
+        server.route("POST", "/v1/connector/data/.my", FakeResponse::status(409));
+        let err = dir.ensure_created(DataAcl::default()).unwrap_err();
+        assert!(err.to_string().contains("already exists as a file"));
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_create_all() {
+        use crate::testing::{FakeResponse, FakeServer};
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        server.data_dir("data://.my/a", serde_json::json!({}));
+        server.route(
+            "POST",
+            "/v1/connector/data/.my/a",
+            FakeResponse::status(200),
+        );
+        server.route(
+            "POST",
+            "/v1/connector/data/.my/a/b",
+            FakeResponse::status(200),
+        );
+
+        client
+            .dir("data://.my/a/b/c")
+            .create_all(ReadAcl::Private)
+            .unwrap();
+        let created: Vec<_> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == "POST")
+            .map(|r| (r.body_string(), r.url))
+            .collect();
+        assert_eq!(
+            created,
+            vec![
+                (
+                    r#"{"name":"b","acl":{"read":[]}}"#.to_string(),
+                    "/v1/connector/data/.my/a".to_string()
+                ),
+                (
+                    r#"{"name":"c","acl":{"read":[]}}"#.to_string(),
+                    "/v1/connector/data/.my/a/b".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_create_all_under_file() {
+        use crate::testing::FakeServer;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        server.data_file("data://.my/a", "not a directory");
+
+        let err = client
+            .dir("data://.my/a/b")
+            .create_all(ReadAcl::Private)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("'data://.my/a' already exists as a file"));
+        assert!(server.requests().iter().all(|r| r.method != "POST"));
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_get_and_update_acl() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        server.data_dir(
+            "data://.my/shared",
+            json!({ "acl": { "read": ["user://*"] }, "files": [] }),
+        );
+        server.data_dir("data://.my/plain", json!({ "files": [] }));
+        server.route(
+            "PATCH",
+            "/v1/connector/data/.my/shared",
+            FakeResponse::status(200),
+        );
+
+        let dir = client.dir(".my/shared");
+        let acl = dir.get_acl().unwrap();
+        assert_eq!(acl.read, vec!["user://*".to_string()]);
+        assert_eq!(acl.read_acl(), ReadAcl::Public);
+        assert_eq!(
+            server.requests()[0].url,
+            "/v1/connector/data/.my/shared?acl=true"
+        );
+        assert!(client.dir(".my/plain").get_acl().is_err());
+
+        let mut listing = dir.list();
+        assert_eq!(listing.read_acl(), None);
+        assert!(listing.next().is_none());
+        assert_eq!(listing.read_acl(), Some(ReadAcl::Public));
+
+        dir.update_acl(ReadAcl::Private).unwrap();
+        let patch = server.requests().pop().unwrap();
+        assert_eq!(patch.method, "PATCH");
+        assert_eq!(patch.body_string(), r#"{"acl":{"read":[]}}"#);
+
+        let err = client
+            .dir(".my/missing")
+            .update_acl(ReadAcl::Public)
+            .unwrap_err();
+        assert_eq!(
+            err.data_error().unwrap().operation,
+            DataOperation::UpdateAcl
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_move_to() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        server.data_dir(
+            "data://.my/src",
+            json!({
+                "acl": { "read": ["user://*"] },
+                "folders": [{ "name": "sub", "acl": { "read": [] } }],
+                "files": [{ "filename": "a.txt", "size": 3, "last_modified": "2015-03-14T08:00:00Z" }]
+            }),
+        );
+        server.data_dir(
+            "data://.my/src/sub",
+            json!({
+                "files": [{ "filename": "b.txt", "size": 4, "last_modified": "2015-03-14T08:00:00Z" }]
+            }),
+        );
+        server.data_file("data://.my/src/a.txt", "foo");
+        server.data_file("data://.my/src/sub/b.txt", "barr");
+        server.route("POST", "/v1/connector/data/.my", FakeResponse::status(200));
+        server.route(
+            "POST",
+            "/v1/connector/data/.my/dest",
+            FakeResponse::status(200),
+        );
+        let deleted = json!({ "result": { "deleted": 2 } });
+        server.route(
+            "DELETE",
+            "/v1/connector/data/.my/dest",
+            FakeResponse::json(200, &deleted),
+        );
+        server.route(
+            "DELETE",
+            "/v1/connector/data/.my/src",
+            FakeResponse::json(200, &deleted),
+        );
+        server.route(
+            "PUT",
+            "/v1/connector/data/.my/dest/a.txt",
+            FakeResponse::status(200),
+        );
+
+        // The second file fails to upload, so the partial copy is removed
+        let src = client.dir("data://.my/src");
+        let dest = client.dir("data://.my/dest");
+        let mut updates = Vec::new();
+        let err = src
+            .move_to_with_progress(&dest, |p| updates.push((p.files_copied, p.bytes_copied)))
+            .unwrap_err();
+        assert!(err.to_string().contains("partial copy was removed"));
+        assert_eq!(updates, vec![(1, 3)]);
+        let deletes = |server: &FakeServer| {
+            server
+                .requests()
+                .into_iter()
+                .filter(|r| r.method == "DELETE")
+                .map(|r| r.url)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            deletes(&server),
+            vec!["/v1/connector/data/.my/dest?force=true"]
+        );
+
+        server.route(
+            "PUT",
+            "/v1/connector/data/.my/dest/sub/b.txt",
+            FakeResponse::status(200),
+        );
+        let mut updates = Vec::new();
+        src.move_to_with_progress(&dest, |p| {
+            updates.push((p.files_copied, p.total_files, p.bytes_copied, p.total_bytes))
+        })
+        .unwrap();
+        assert_eq!(updates, vec![(1, 2, 3, 7), (2, 2, 7, 7)]);
+        assert_eq!(
+            deletes(&server)[1..],
+            ["/v1/connector/data/.my/src?force=true"]
+        );
+
+        let err = src.move_to(&client.dir("data://.my/src/sub2")).unwrap_err();
+        assert!(err.to_string().contains("into itself"));
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_move_to_rollback_removes_created_parents() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let file = |name: &str| json!({ "filename": name, "size": 3, "last_modified": "2015-03-14T08:00:00Z" });
+        server.data_dir(
+            "data://.my/src",
+            json!({ "files": [file("a.txt"), file("b.txt")] }),
+        );
+        server.data_file("data://.my/src/a.txt", "foo");
+        server.data_file("data://.my/src/b.txt", "bar");
+        for parent in &["/v1/connector/data/.my", "/v1/connector/data/.my/archive"] {
+            server.route("POST", parent, FakeResponse::status(200));
+        }
+        let deleted = json!({ "result": { "deleted": 1 } });
+        for dir in &[
+            "/v1/connector/data/.my/archive/dest",
+            "/v1/connector/data/.my/archive",
+        ] {
+            server.route("DELETE", dir, FakeResponse::json(200, &deleted));
+        }
+        server.route(
+            "PUT",
+            "/v1/connector/data/.my/archive/dest/a.txt",
+            FakeResponse::status(200),
+        );
+
+        let err = client
+            .dir("data://.my/src")
+            .move_to(&client.dir("data://.my/archive/dest"))
+            .unwrap_err();
+        assert!(err.to_string().contains("partial copy was removed"));
+
+        let requests = server.requests();
+        let put = requests.iter().find(|r| r.method == "PUT").unwrap();
+        assert_eq!(put.body_string(), "foo");
+        let deletes: Vec<_> = requests
+            .iter()
+            .filter(|r| r.method == "DELETE")
+            .map(|r| r.url.as_str())
+            .collect();
+        assert_eq!(
+            deletes,
+            vec![
+                "/v1/connector/data/.my/archive/dest?force=true",
+                "/v1/connector/data/.my/archive"
+            ]
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_delete_recursive() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let file = |name: &str| json!({ "filename": name, "size": 1, "last_modified": "2015-03-14T08:00:00Z" });
+        server.data_dir(
+            "s3://bucket/dir",
+            json!({ "folders": [{ "name": "sub" }], "files": [file("a"), file("b")] }),
+        );
+        server.data_dir("s3://bucket/dir/sub", json!({ "files": [file("c")] }));
+        let deleted = json!({ "result": { "deleted": 0 } });
+        for path in &["dir/a", "dir/sub/c"] {
+            let path = format!("/v1/connector/s3/bucket/{}", path);
+            server.route("DELETE", &path, FakeResponse::status(200));
+        }
+        for path in &["dir", "dir/sub"] {
+            let path = format!("/v1/connector/s3/bucket/{}", path);
+            server.route("DELETE", &path, FakeResponse::json(200, &deleted));
+        }
+        server.route(
+            "DELETE",
+            "/v1/connector/s3/bucket/dir/b",
+            FakeResponse::status(503),
+        );
+        let deletes = |server: &FakeServer, path: &str| {
+            server
+                .requests()
+                .iter()
+                .filter(|r| r.method == "DELETE" && r.url == path)
+                .count()
+        };
+
+        // A file that keeps failing is retried, reported, and keeps its directories
+        let dir = client.dir("s3://bucket/dir");
+        let report = dir.delete_recursive(2).unwrap();
+        assert_eq!(report.deleted, 2);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].0, "s3://bucket/dir/b");
+        assert_eq!(
+            deletes(&server, "/v1/connector/s3/bucket/dir/b"),
+            DELETE_ATTEMPTS as usize
+        );
+        assert_eq!(deletes(&server, "/v1/connector/s3/bucket/dir"), 0);
+
+        // Files that are already gone count as deleted
+        server.route(
+            "DELETE",
+            "/v1/connector/s3/bucket/dir/b",
+            FakeResponse::status(404),
+        );
+        let report = dir.delete_recursive(2).unwrap();
+        assert!(report.is_complete());
+        assert_eq!(report.deleted, 3);
+        let dir_deletes: Vec<_> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == "DELETE")
+            .filter(|r| r.url.ends_with("/dir") || r.url.ends_with("/dir/sub"))
+            .map(|r| r.url)
+            .collect();
+        assert_eq!(
+            dir_deletes,
+            vec![
+                "/v1/connector/s3/bucket/dir/sub",
+                "/v1/connector/s3/bucket/dir"
+            ]
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_list_all_and_delete_files() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let file = |name: &str| json!({ "filename": name, "size": 1, "last_modified": "2015-03-14T08:00:00Z" });
+        server.data_dir(
+            "data://.my/jobs",
+            json!({ "folders": [{ "name": "b" }, { "name": "a" }], "files": [file("run.tmp"), file("keep.tmp")] }),
+        );
+        server.data_dir(
+            "data://.my/jobs/a",
+            json!({ "folders": [{ "name": "deep" }], "files": [file("x.tmp")] }),
+        );
+        server.data_dir("data://.my/jobs/b", json!({ "files": [file("y.txt")] }));
+        server.data_dir(
+            "data://.my/jobs/a/deep",
+            json!({ "files": [file("z.tmp")] }),
+        );
+
+        let dir = client.dir(".my/jobs");
+        let names: Vec<_> = dir
+            .list_all(4)
+            .unwrap()
+            .into_iter()
+            .map(|entry| match entry {
+                DataEntry::Dir(d) => format!("{}/", d.name),
+                DataEntry::File(f) => f.name,
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "b/",
+                "a/",
+                "run.tmp",
+                "keep.tmp",
+                "a/deep/",
+                "a/x.tmp",
+                "b/y.txt",
+                "a/deep/z.tmp"
+            ]
+        );
+
+        for path in &["run.tmp", "a/x.tmp", "a/deep/z.tmp"] {
+            let path = format!("/v1/connector/data/.my/jobs/{}", path);
+            server.route("DELETE", &path, FakeResponse::status(200));
+        }
+        let report = dir.delete_files(["*.tmp", "!keep.tmp"], 2).unwrap();
+        assert!(report.is_complete());
+        assert_eq!(report.deleted, 3);
+        let mut deleted: Vec<_> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == "DELETE")
+            .map(|r| r.url)
+            .collect();
+        deleted.sort();
+        assert_eq!(
+            deleted,
+            vec![
+                "/v1/connector/data/.my/jobs/a/deep/z.tmp",
+                "/v1/connector/data/.my/jobs/a/x.tmp",
+                "/v1/connector/data/.my/jobs/run.tmp"
+            ]
+        );
+
+        assert!(dir.delete_files(["{a"], 2).is_err());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_list_sorted() {
+        use crate::testing::FakeServer;
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let file = |name: &str, size: u64, day: u32| json!({ "filename": name, "size": size, "last_modified": format!("2015-03-{:02}T08:00:00Z", day) });
+        server.data_dir(
+            "data://.my/foo",
+            json!({
+                "folders": [{ "name": "b" }, { "name": "a" }],
+                "files": [file("y.txt", 1, 14), file("x.txt", 3, 1), file("z.txt", 2, 20)]
+            }),
+        );
+        let client = server.client().unwrap();
+        let dir = client.dir("data://.my/foo");
+        let names = |options: ListOptions| -> Vec<String> {
+            dir.list_entries_with(options)
+                .map(|entry| match entry.unwrap() {
+                    DataEntry::Dir(d) => d.name,
+                    DataEntry::File(f) => f.name,
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            names(ListOptions::new()),
+            ["b", "a", "y.txt", "x.txt", "z.txt"]
+        );
+        assert_eq!(
+            names(ListOptions::new().sort_by(SortKey::Name, SortOrder::Ascending)),
+            ["a", "b", "x.txt", "y.txt", "z.txt"]
+        );
+        assert_eq!(
+            names(ListOptions::new().sort_by(SortKey::LastModified, SortOrder::Descending)),
+            ["b", "a", "z.txt", "y.txt", "x.txt"]
+        );
+        assert_eq!(
+            names(
+                ListOptions::new()
+                    .sort_by(SortKey::Size, SortOrder::Ascending)
+                    .per_page(true)
+            ),
+            ["a", "b", "y.txt", "z.txt", "x.txt"]
+        );
+    }
+
+    #[cfg(all(feature = "async", feature = "testing"))]
+    #[test]
+    fn test_list_stream() {
+        use crate::testing::FakeServer;
+        use futures::StreamExt;
+
+        let server = FakeServer::start().unwrap();
+        server.data_dir(
+            "data://.my/foo",
+            serde_json::json!({
+                "folders": [{ "name": "sub" }],
+                "files": [{ "filename": "a.txt", "size": 3, "last_modified": "2015-03-14T08:00:00Z" }]
+            }),
+        );
+        let client = server.client().unwrap();
+        let items: Vec<_> =
+            futures::executor::block_on(client.dir("data://.my/foo").list_stream().collect());
+        let uris: Vec<_> = items
+            .into_iter()
+            .map(|item| match item.unwrap() {
+                DataItem::Dir(d) => d.to_data_uri(),
+                DataItem::File(f) => f.to_data_uri(),
+            })
+            .collect();
+        assert_eq!(uris, vec!["data://.my/foo/sub", "data://.my/foo/a.txt"]);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_collect_partial() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let file = |name: &str| json!({ "filename": name, "size": 1, "last_modified": "2015-03-14T08:00:00Z" });
+        server.data_dir(
+            "data://.my/foo",
+            json!({ "folders": [{ "name": "sub" }], "files": [file("b.txt")], "marker": "p2" }),
+        );
+        server.route(
+            "GET",
+            "/v1/connector/data/.my/foo?marker=p2",
+            FakeResponse::json(400, &json!({ "error": { "message": "page unavailable" } })),
+        );
+        let client = server.client().unwrap();
+        let dir = client.dir("data://.my/foo");
+        let uris = |items: &[DataItem]| -> Vec<String> {
+            items
+                .iter()
+                .map(|item| match item {
+                    DataItem::Dir(d) => d.to_data_uri(),
+                    DataItem::File(f) => f.to_data_uri(),
+                })
+                .collect()
+        };
+
+        let partial = dir.list().collect_partial();
+        assert!(!partial.is_complete());
+        assert_eq!(
+            uris(&partial.items),
+            ["data://.my/foo/sub", "data://.my/foo/b.txt"]
+        );
+        assert_eq!(partial.errors.len(), 1);
+        assert_eq!(partial.errors[0].marker.as_deref(), Some("p2"));
+        assert_eq!(
+            partial.errors[0].error.api_error().unwrap().message,
+            "page unavailable"
+        );
+
+        // Retrying resumes from the failed page
+        server.route(
+            "GET",
+            "/v1/connector/data/.my/foo?marker=p2",
+            FakeResponse::json(200, &json!({ "files": [file("a.txt")] }))
+                .with_header("X-Data-Type", "directory"),
+        );
+        let options = ListOptions::new()
+            .marker("p2")
+            .sort_by(SortKey::Name, SortOrder::Ascending);
+        let retried = dir.list_with(options).collect_partial();
+        assert!(retried.is_complete());
+        assert_eq!(uris(&retried.items), ["data://.my/foo/a.txt"]);
+
+        // Sorting the whole listing keeps the pages listed before a failure
+        server.route(
+            "GET",
+            "/v1/connector/data/.my/foo?marker=p2",
+            FakeResponse::status(400),
+        );
+        let options = ListOptions::new().sort_by(SortKey::Name, SortOrder::Descending);
+        let partial = dir.list_with(options).collect_partial();
+        assert_eq!(
+            uris(&partial.items),
+            ["data://.my/foo/sub", "data://.my/foo/b.txt"]
+        );
+        assert_eq!(partial.errors.len(), 1);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_list_sorted_retries_failed_page() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let file = |name: &str| json!({ "filename": name, "size": 1, "last_modified": "2015-03-14T08:00:00Z" });
+        server.data_dir(
+            "data://.my/foo",
+            json!({ "files": [file("b.txt")], "marker": "p2" }),
+        );
+        server.route(
+            "GET",
+            "/v1/connector/data/.my/foo?marker=p2",
+            FakeResponse::status(500),
+        );
+        let client = server.client().unwrap();
+        let dir = client.dir("data://.my/foo");
+        let options = ListOptions::new().sort_by(SortKey::Name, SortOrder::Ascending);
+        let mut entries = dir.list_entries_with(options);
+        assert!(entries.next().unwrap().is_err());
+
+        // Retrying resumes from the failed page and keeps the page listed before it
+        server.route(
+            "GET",
+            "/v1/connector/data/.my/foo?marker=p2",
+            FakeResponse::json(200, &json!({ "files": [file("a.txt")] }))
+                .with_header("X-Data-Type", "directory"),
+        );
+        let names: Vec<String> = entries
+            .map(|entry| match entry.unwrap() {
+                DataEntry::Dir(d) => d.name,
+                DataEntry::File(f) => f.name,
+            })
+            .collect();
+        assert_eq!(names, ["a.txt", "b.txt"]);
+
+        let urls: Vec<String> = server.requests().into_iter().map(|r| r.url).collect();
+        assert_eq!(
+            urls,
+            [
+                "/v1/connector/data/.my/foo",
+                "/v1/connector/data/.my/foo?marker=p2",
+                "/v1/connector/data/.my/foo?marker=p2"
+            ]
+        );
+    }
+
     #[test]
     fn test_default_acl() {
         let acl: DataAcl = DataAcl::default();
@@ -445,10 +2566,188 @@ mod tests {
         assert_eq!(acl.read, vec!["user://*".to_string()]);
     }
 
+    #[test]
+    fn test_read_acl() {
+        let read = vec!["user://*".to_string(), "algo://.my/*".to_string()];
+        let custom: DataAcl = ReadAcl::Other(read.clone()).into();
+        assert_eq!(custom.read, read);
+        assert_eq!(custom.read_acl(), ReadAcl::Other(read));
+        assert_eq!(DataAcl::default().read_acl(), ReadAcl::MyAlgorithms);
+        assert_eq!(DataAcl::from(ReadAcl::Private).read_acl(), ReadAcl::Private);
+    }
+
+    #[test]
+    fn test_acl_verbs() {
+        let json = r#"{"read":["user://*"],"write":[],"delete":["user://alice"],"owner":"alice"}"#;
+        let acl: DataAcl = serde_json::from_str(json).unwrap();
+        assert_eq!(acl.read_acl(), ReadAcl::Public);
+        assert_eq!(acl.write, Some(vec![]));
+        assert_eq!(acl.verb("delete"), Some(vec!["user://alice".to_string()]));
+        assert_eq!(acl.verb("owner"), None);
+        assert_eq!(
+            serde_json::to_value(&acl).unwrap(),
+            serde_json::from_str::<Value>(json).unwrap()
+        );
+
+        let acl = DataAcl::from(ReadAcl::Private)
+            .with_write(vec!["algo://.my/*"])
+            .with_verb("share", vec!["user://bob"]);
+        assert_eq!(
+            serde_json::to_string(&acl).unwrap(),
+            r#"{"read":[],"write":["algo://.my/*"],"share":["user://bob"]}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&DataAcl::default()).unwrap(),
+            r#"{"read":["algo://.my/*"]}"#
+        );
+    }
+
     #[test]
     fn test_myalgos_acl() {
         let acl: DataAcl = ReadAcl::MyAlgorithms.into();
         assert_eq!(acl.read, vec!["algo://.my/*".to_string()]);
     }
 
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_put_files() {
+        use crate::testing::{FakeResponse, FakeServer};
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        for name in &["a.json", "b.txt", "sub/c.txt"] {
+            let path = format!("/v1/connector/data/.my/up/{}", name);
+            server.route("PUT", &path, FakeResponse::status(200));
+        }
+        server.route(
+            "PUT",
+            "/v1/connector/data/.my/up/b.txt",
+            FakeResponse::status(500),
+        );
+
+        let tmp = tempfile::tempdir().unwrap();
+        let local = tmp.path().join("put_files.txt");
+        std::fs::write(&local, "from disk").unwrap();
+        let files = vec![
+            ("a.json", UploadSource::from(b"{}".to_vec())),
+            ("b.txt", UploadSource::from(b"fails".to_vec())),
+            ("sub/c.txt", UploadSource::from(local.as_path())),
+        ];
+        let results = client.dir(".my/up").put_files(files, 2);
+
+        let names: Vec<_> = results.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["a.json", "b.txt", "sub/c.txt"]);
+        assert!(results[0].1.is_ok());
+        assert_eq!(results[1].1.as_ref().unwrap_err().status().unwrap(), 500);
+        assert!(results[2].1.is_ok());
+
+        let requests = server.requests();
+        let a = requests.iter().find(|r| r.url.ends_with("a.json")).unwrap();
+        assert_eq!(a.header("Content-Type"), Some("application/json"));
+        let c = requests.iter().find(|r| r.url.ends_with("c.txt")).unwrap();
+        assert_eq!(c.body_string(), "from disk");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_put_dir() {
+        use crate::data::sync::Filter;
+        use crate::testing::{FakeResponse, FakeServer};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let local = tmp.path();
+        std::fs::create_dir_all(local.join("sub/deep")).unwrap();
+        std::fs::write(local.join("a.txt"), "a").unwrap();
+        std::fs::write(local.join("skip.tmp"), "tmp").unwrap();
+        std::fs::write(local.join("sub/deep/b.txt"), "b").unwrap();
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        server.data_dir("data://.my/up", serde_json::json!({}));
+        for path in &[".my/up", ".my/up/sub"] {
+            let url = format!("/v1/connector/data/{}", path);
+            server.route("POST", &url, FakeResponse::status(200));
+        }
+        for name in &["a.txt", "sub/deep/b.txt"] {
+            let url = format!("/v1/connector/data/.my/up/{}", name);
+            server.route("PUT", &url, FakeResponse::status(200));
+        }
+
+        let options = UploadOptions::new()
+            .parallelism(2)
+            .filter(Filter::new().exclude("*.tmp"));
+        let results = client.dir(".my/up").put_dir(local, options).unwrap();
+
+        let names: Vec<_> = results.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["a.txt", "sub/deep/b.txt"]);
+        assert!(results.iter().all(|(_, res)| res.is_ok()));
+        let created: Vec<_> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == "POST")
+            .map(|r| (r.url.clone(), r.body_string()))
+            .collect();
+        assert_eq!(
+            created,
+            vec![
+                (
+                    "/v1/connector/data/.my/up".to_string(),
+                    r#"{"name":"sub","acl":{"read":["algo://.my/*"]}}"#.to_string()
+                ),
+                (
+                    "/v1/connector/data/.my/up/sub".to_string(),
+                    r#"{"name":"deep","acl":{"read":["algo://.my/*"]}}"#.to_string()
+                ),
+            ]
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_download_to() {
+        use crate::testing::FakeServer;
+        use serde_json::json;
+
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        let old = "2015-03-14T08:00:00Z";
+        server.data_dir(
+            "data://.my/models",
+            json!({
+                "folders": [{ "name": "sub" }, { "name": "empty" }],
+                "files": [
+                    { "filename": "a.txt", "size": 1, "last_modified": old },
+                    { "filename": "same.txt", "size": 4, "last_modified": old },
+                    { "filename": "../escape.txt", "size": 1, "last_modified": old }
+                ]
+            }),
+        );
+        server.data_dir(
+            "data://.my/models/sub",
+            json!({ "files": [{ "filename": "b.txt", "size": 2, "last_modified": old }] }),
+        );
+        server.data_dir("data://.my/models/empty", json!({}));
+        server.data_file("data://.my/models/a.txt", "a");
+        server.data_file("data://.my/models/sub/b.txt", "bb");
+
+        let tmp = tempfile::tempdir().unwrap();
+        let local = tmp.path();
+        std::fs::write(local.join("same.txt"), "same").unwrap();
+
+        let options = DownloadOptions::new().parallelism(2).skip_unchanged(true);
+        let report = client
+            .dir(".my/models")
+            .download_to(local, options)
+            .unwrap();
+        assert_eq!(report.downloaded, vec!["a.txt", "sub/b.txt"]);
+        assert_eq!(report.skipped, vec!["same.txt"]);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].0, "../escape.txt");
+        assert_eq!(std::fs::read_to_string(local.join("a.txt")).unwrap(), "a");
+        assert_eq!(
+            std::fs::read_to_string(local.join("sub/b.txt")).unwrap(),
+            "bb"
+        );
+        assert!(local.join("empty").is_dir());
+    }
 }
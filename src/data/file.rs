@@ -11,13 +11,24 @@
 //! # Ok::<(), Box<std::error::Error>>(())
 //! ```
 
-use super::{parse_data_uri, parse_headers};
+use super::{content_type, parse_data_uri, parse_headers, HeaderData};
 use crate::client::HttpClient;
 use crate::data::{DataType, HasDataPath};
-use crate::error::{process_http_response, Error, ResultExt};
-use crate::Body;
+use crate::error::{process_http_response, DataOperation, Error, ResultExt};
+use crate::{Body, TransferStats};
 use chrono::{DateTime, TimeZone, Utc};
-use std::io::{self, Read};
+use headers_ext::{ContentType, HeaderMapExt};
+use http::header::{HeaderMap, HeaderValue, IF_NONE_MATCH, RANGE};
+use http::StatusCode;
+use mime::Mime;
+use reqwest::Response;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::ops::{Bound, RangeBounds};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 /// Response and reader when downloading a `DataFile`
 pub struct FileData {
@@ -25,17 +36,42 @@ pub struct FileData {
     pub size: u64,
     /// Last modified timestamp
     pub last_modified: DateTime<Utc>,
-    data: Box<Read>,
+    /// Content type of the file, if provided by the API
+    pub content_type: Option<String>,
+    /// ETag (checksum) of the file contents, if provided by the API
+    pub etag: Option<String>,
+    /// Full data URI of the downloaded file
+    pub data_uri: String,
+    data: Box<dyn Read>,
+    start: Instant,
+    bytes_read: u64,
+    finished: Option<Duration>,
 }
 
 impl Read for FileData {
     #[inline]
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.data.read(buf)
+        let len = self.data.read(buf)?;
+        self.bytes_read += len as u64;
+        if len == 0 && !buf.is_empty() && self.finished.is_none() {
+            self.finished = Some(self.start.elapsed());
+        }
+        Ok(len)
     }
 }
 
 impl FileData {
+    /// Byte count and wall time of the download so far
+    ///
+    /// `elapsed` stops increasing once the entire file has been read.
+    pub fn stats(&self) -> TransferStats {
+        TransferStats::new(
+            Some(0),
+            self.bytes_read,
+            self.finished.unwrap_or_else(|| self.start.elapsed()),
+        )
+    }
+
     /// Reads the result into a byte vector
     ///
     /// This is a convenience wrapper around `Read::read_to_end`
@@ -57,18 +93,233 @@ impl FileData {
     }
 }
 
+/// Outcome of a conditional download with
+/// [`DataFile::get_if_none_match`](struct.DataFile.html#method.get_if_none_match)
+pub enum ConditionalData {
+    /// The file no longer matches the ETag, and its current contents are being downloaded
+    Modified(FileData),
+    /// The file still matches the ETag, so nothing was downloaded
+    NotModified,
+}
+
+/// Metadata of a `DataFile`, returned by [`DataFile::stat`](struct.DataFile.html#method.stat)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileMetadata {
+    /// Size of file in bytes
+    pub size: u64,
+    /// Last modified timestamp, if provided by the API
+    pub last_modified: Option<DateTime<Utc>>,
+    /// Content type of the file, if provided by the API
+    pub content_type: Option<String>,
+    /// ETag (checksum) of the file contents, if provided by the API
+    pub etag: Option<String>,
+    /// Full data URI of the file
+    pub data_uri: String,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+/// Default size of the chunks yielded by a `DataStream`
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+type ProgressFn = dyn FnMut(u64, Option<u64>);
+
+/// Download of a `DataFile` that is read in chunks as it arrives
+///
+/// Implements `Read` and iterates over chunks of up to
+/// [`chunk_size`](#method.chunk_size) bytes, so large files never need to be
+/// held in memory. Attach a [`progress`](#method.progress) callback to report
+/// the download as it is read.
+///
+/// Created with [`DataFile::get_stream`](struct.DataFile.html#method.get_stream).
+pub struct DataStream {
+    data: FileData,
+    total: Option<u64>,
+    chunk_size: usize,
+    so_far: u64,
+    progress: Option<Box<ProgressFn>>,
+}
+
+impl DataStream {
+    /// Call `progress` with the bytes read so far and the total size (if known)
+    /// each time data is read from the stream
+    pub fn progress<F>(mut self, progress: F) -> DataStream
+    where
+        F: FnMut(u64, Option<u64>) + 'static,
+    {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Maximum size of each chunk yielded when iterating (default: 64 KiB)
+    pub fn chunk_size(mut self, bytes: usize) -> DataStream {
+        self.chunk_size = bytes.max(1);
+        self
+    }
+
+    /// Metadata of the file being downloaded
+    pub fn file_data(&self) -> &FileData {
+        &self.data
+    }
+
+    /// Total size of the file from the `Content-Length` header, if known
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Copy the rest of the download to `writer`, returning the number of bytes written
+    pub fn write_to<W: Write + ?Sized>(mut self, writer: &mut W) -> Result<u64, Error> {
+        let uri = self.data.data_uri.clone();
+        let mut buf = vec![0; self.chunk_size];
+        let mut written = 0;
+        loop {
+            let len = match self.read(&mut buf) {
+                Ok(0) => break,
+                Ok(len) => len,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    return Err(err).with_context(|| format!("error downloading file '{}'", uri))
+                }
+            };
+            writer
+                .write_all(&buf[..len])
+                .with_context(|| format!("error writing download of '{}'", uri))?;
+            written += len as u64;
+        }
+        writer
+            .flush()
+            .with_context(|| format!("error writing download of '{}'", uri))?;
+        Ok(written)
+    }
+
+    /// Save the rest of the download to a local file, returning the number of bytes written
+    ///
+    /// The download is written to a `.part` file next to `path` which is renamed
+    /// once complete, so `path` never holds a partial download.
+    pub fn save_to<P: AsRef<Path>>(self, path: P) -> Result<u64, Error> {
+        let path = path.as_ref();
+        let mut part_name = path.file_name().unwrap_or_default().to_owned();
+        part_name.push(".part");
+        let part_path = path.with_file_name(part_name);
+
+        let mut file = File::create(&part_path)
+            .with_context(|| format!("creating download file '{}'", part_path.display()))?;
+        let written = match self.write_to(&mut file) {
+            Ok(written) => written,
+            Err(err) => {
+                let _ = fs::remove_file(&part_path);
+                return Err(err);
+            }
+        };
+        drop(file);
+        fs::rename(&part_path, path)
+            .with_context(|| format!("moving download to '{}'", path.display()))?;
+        Ok(written)
+    }
+}
+
+impl Read for DataStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.data.read(buf)?;
+        if len > 0 {
+            self.so_far += len as u64;
+            if let Some(progress) = &mut self.progress {
+                progress(self.so_far, self.total);
+            }
+        }
+        Ok(len)
+    }
+}
+
+impl Iterator for DataStream {
+    type Item = io::Result<Vec<u8>>;
+
+    /// Read the next chunk, which is only shorter than `chunk_size` at the end of the file
+    fn next(&mut self) -> Option<io::Result<Vec<u8>>> {
+        let mut chunk = Vec::with_capacity(self.chunk_size);
+        let limit = self.chunk_size as u64;
+        match Read::take(&mut *self, limit).read_to_end(&mut chunk) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(chunk)),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+struct ProgressReader<R, F> {
+    inner: R,
+    so_far: u64,
+    total: Option<u64>,
+    progress: F,
+}
+
+impl<R: Read, F: FnMut(u64, Option<u64>)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.so_far += len as u64;
+        (self.progress)(self.so_far, self.total);
+        Ok(len)
+    }
+}
+
+/// Limits the rate at which bytes are read from the inner reader
+struct ThrottledReader<R> {
+    inner: R,
+    bytes_per_sec: u64,
+    transferred: u64,
+    start: Instant,
+}
+
+impl<R> ThrottledReader<R> {
+    fn new(inner: R, bytes_per_sec: u64) -> ThrottledReader<R> {
+        ThrottledReader {
+            inner,
+            bytes_per_sec: bytes_per_sec.max(1),
+            transferred: 0,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl<R: Read> Read for ThrottledReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Avoid reading more than a second's worth of data at once
+        let max_len = buf.len().min(self.bytes_per_sec as usize);
+        let len = self.inner.read(&mut buf[..max_len])?;
+        self.transferred += len as u64;
+
+        let expected = Duration::from_secs_f64(self.transferred as f64 / self.bytes_per_sec as f64);
+        let elapsed = self.start.elapsed();
+        if expected > elapsed {
+            thread::sleep(expected - elapsed);
+        }
+        Ok(len)
+    }
+}
+
 /// Algorithmia data file
+#[derive(Clone)]
 pub struct DataFile {
     path: String,
     client: HttpClient,
+    rate_limit: Option<u64>,
+}
+
+impl std::fmt::Debug for DataFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("DataFile")
+            .field(&self.to_data_uri())
+            .finish()
+    }
 }
 
 impl HasDataPath for DataFile {
     #[doc(hidden)]
     fn new(client: HttpClient, path: &str) -> Self {
         DataFile {
-            client: client,
+            client,
             path: parse_data_uri(path).to_string(),
+            rate_limit: None,
         }
     }
     #[doc(hidden)]
@@ -82,6 +333,30 @@ impl HasDataPath for DataFile {
 }
 
 impl DataFile {
+    /// Limit transfers of this file to a maximum rate in bytes per second
+    ///
+    /// Overrides any limit configured with `ClientBuilder::transfer_rate_limit`.
+    /// Applies to downloads and to uploads with `put_file`,
+    /// but not to in-memory bodies passed to `put`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// client.file(".my/my_dir/model.bin")
+    ///     .with_rate_limit(1024 * 1024)
+    ///     .put_file("/path/to/model.bin")?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn with_rate_limit(mut self, bytes_per_sec: u64) -> DataFile {
+        self.rate_limit = Some(bytes_per_sec);
+        self
+    }
+
+    fn rate_limit(&self) -> Option<u64> {
+        self.rate_limit.or(self.client.transfer_rate_limit)
+    }
+
     /// Write to the Algorithmia Data API
     ///
     /// # Examples
@@ -98,17 +373,94 @@ impl DataFile {
     /// # Ok::<(), Box<std::error::Error>>(())
     /// ```
     pub fn put<B>(&self, body: B) -> Result<(), Error>
+    where
+        B: Into<Body>,
+    {
+        self.put_as(body, None)
+    }
+
+    /// Upload a local file to this exact data URI
+    ///
+    /// The file is streamed from disk, and the content type is inferred from its extension.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// client.file(".my/my_dir/model.bin").put_file("/path/to/local_model.bin")?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn put_file<P: AsRef<Path>>(&self, file_path: P) -> Result<(), Error> {
+        let path_ref = file_path.as_ref();
+        let mut file = File::open(path_ref)
+            .with_context(|| format!("opening file for upload '{}'", path_ref.display()))?;
+        let content_type =
+            content_type::detect(path_ref, &mut file, self.client.sniff_content_type)
+                .with_context(|| format!("reading file for upload '{}'", path_ref.display()))?;
+        match self.rate_limit() {
+            Some(rate) => {
+                let len = file
+                    .metadata()
+                    .with_context(|| format!("reading metadata of '{}'", path_ref.display()))?
+                    .len();
+                let body = Body::sized(ThrottledReader::new(file, rate), len);
+                self.put_as(body, content_type)
+            }
+            None => self.put_as(file, content_type),
+        }
+    }
+
+    /// Upload chunks of data as they are produced, e.g. by a compressor or generator
+    ///
+    /// The body is streamed with chunked transfer encoding, so only the chunk being sent
+    /// is held in memory. If the iterator yields an error, the upload is aborted and
+    /// that error is returned.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let lines = (0..1_000_000).map(|i| Ok(format!("line {}\n", i).into_bytes()));
+    /// client.file(".my/my_dir/lines.txt").put_chunks(lines)?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn put_chunks<I>(&self, chunks: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = io::Result<Vec<u8>>>,
+        I::IntoIter: Send + 'static,
+    {
+        let producer_err = Arc::new(Mutex::new(None));
+        let reader = ChunkReader {
+            chunks: chunks.into_iter(),
+            current: io::Cursor::new(Vec::new()),
+            err: producer_err.clone(),
+        };
+        let result = self.put_as(Body::new(reader), None);
+        let producer_err = producer_err.lock().unwrap().take();
+        match producer_err {
+            Some(err) => Err(err)
+                .with_context(|| format!("error producing data for file '{}'", self.to_data_uri())),
+            None => result,
+        }
+    }
+
+    pub(crate) fn put_as<B>(&self, body: B, content_type: Option<Mime>) -> Result<(), Error>
     where
         B: Into<Body>,
     {
         let url = self.to_url()?;
+        let mut req = self.client.put(url);
+        if let Some(content_type) = content_type {
+            let mut headers = HeaderMap::new();
+            headers.typed_insert(ContentType::from(content_type));
+            req = req.headers(headers);
+        }
         self.client
-            .put(url)
-            .body(body)
-            .send()
+            .send(req.body(body))
             .with_context(|| format!("request error writing file '{}'", self.to_data_uri()))
             .and_then(process_http_response)
-            .with_context(|| format!("response error writing file '{}'", self.to_data_uri()))?;
+            .with_context(|| format!("response error writing file '{}'", self.to_data_uri()))
+            .map_err(|err| err.with_data_error(DataOperation::Upload, self.to_data_uri()))?;
 
         Ok(())
     }
@@ -126,13 +478,291 @@ impl DataFile {
     /// # Ok::<_, Box<std::error::Error>>(())
     /// ```
     pub fn get(&self) -> Result<FileData, Error> {
+        let start = Instant::now();
+        let (metadata, res) = self.fetch()?;
+        let data = self.throttle(res);
+        Ok(self.file_data(metadata, data, start))
+    }
+
+    /// Get a file only if it no longer matches an ETag from an earlier download
+    ///
+    /// Sends `If-None-Match` with `etag`, e.g. [`FileData::etag`](struct.FileData.html#structfield.etag),
+    /// so an unchanged file returns `ConditionalData::NotModified` without transferring its
+    /// contents. See [`FileCache`](struct.FileCache.html) [feature = "cache"] to keep the
+    /// contents on disk between runs.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// use algorithmia::data::ConditionalData;
+    ///
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let my_file = client.file(".my/my_dir/model.bin");
+    ///
+    /// match my_file.get_if_none_match("\"5d41402abc4b2a76b9719d911017c592\"")? {
+    ///     ConditionalData::Modified(data) => println!("Downloading new version {:?}", data.etag),
+    ///     ConditionalData::NotModified => println!("Model is unchanged"),
+    /// }
+    /// # Ok::<_, Box<std::error::Error>>(())
+    /// ```
+    pub fn get_if_none_match(&self, etag: &str) -> Result<ConditionalData, Error> {
+        let start = Instant::now();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            IF_NONE_MATCH,
+            HeaderValue::from_str(etag).context("invalid If-None-Match header")?,
+        );
+        match self.fetch_with(headers)? {
+            Some((metadata, res)) => {
+                let data = self.throttle(res);
+                Ok(ConditionalData::Modified(
+                    self.file_data(metadata, data, start),
+                ))
+            }
+            None => Ok(ConditionalData::NotModified),
+        }
+    }
+
+    /// Get a range of bytes of a file, e.g. to read the footer of a parquet or zip file
+    ///
+    /// Sends a `Range` header, so only the requested bytes are transferred. A range that
+    /// extends past the end of the file is truncated to the file's size. If the API ignores
+    /// the range and returns the whole file, the bytes outside of the range are skipped.
+    /// `FileData::size` is the length of the returned range (when known).
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let my_file = client.file(".my/my_dir/archive.zip");
+    ///
+    /// let size = my_file.stat()?.size;
+    /// let footer = my_file.get_range(size.saturating_sub(22)..)?.into_bytes()?;
+    /// # Ok::<_, Box<std::error::Error>>(())
+    /// ```
+    pub fn get_range<R: RangeBounds<u64>>(&self, range: R) -> Result<FileData, Error> {
+        let first = match range.start_bound() {
+            Bound::Included(&first) => first,
+            Bound::Excluded(&first) => first.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        // Exclusive end, or `None` to read until the end of the file
+        let end = match range.end_bound() {
+            Bound::Included(&last) => last.checked_add(1),
+            Bound::Excluded(&end) => Some(end),
+            Bound::Unbounded => None,
+        };
+        let value = match end {
+            Some(end) if end <= first => bail!(
+                "empty byte range {}..{} for file '{}'",
+                first,
+                end,
+                self.to_data_uri()
+            ),
+            Some(end) => format!("bytes={}-{}", first, end - 1),
+            None => format!("bytes={}-", first),
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RANGE,
+            HeaderValue::from_str(&value).context("invalid Range header")?,
+        );
+
+        let start = Instant::now();
+        let (mut metadata, mut res) = match self.fetch_with(headers)? {
+            Some(fetched) => fetched,
+            None => bail!(
+                "unexpected status 304 downloading file '{}'",
+                self.to_data_uri()
+            ),
+        };
+        if res.status() == StatusCode::PARTIAL_CONTENT {
+            let data = self.throttle(res);
+            return Ok(self.file_data(metadata, data, start));
+        }
+
+        // The whole file was returned: skip to the start of the range and stop at its end
+        io::copy(&mut Read::by_ref(&mut res).take(first), &mut io::sink())
+            .with_context(|| format!("error reading file '{}'", self.to_data_uri()))?;
+        let len = end.map_or(u64::MAX, |end| end - first);
+        metadata.content_length = metadata
+            .content_length
+            .map(|size| size.saturating_sub(first).min(len));
+        let data = self.throttle(res.take(len));
+        Ok(self.file_data(metadata, data, start))
+    }
+
+    /// Get a file, reporting download progress while it is read
+    ///
+    /// The callback receives the number of bytes read so far and
+    /// the total size from the `Content-Length` header (if known)
+    /// each time data is read from the returned `FileData`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let my_file = client.file(".my/my_dir/model.bin");
+    ///
+    /// let data = my_file
+    ///     .get_with_progress(|so_far, total| match total {
+    ///         Some(total) => println!("{}/{} bytes", so_far, total),
+    ///         None => println!("{} bytes", so_far),
+    ///     })?
+    ///     .into_bytes()?;
+    /// # Ok::<_, Box<std::error::Error>>(())
+    /// ```
+    pub fn get_with_progress<F>(&self, progress: F) -> Result<FileData, Error>
+    where
+        F: FnMut(u64, Option<u64>) + 'static,
+    {
+        let start = Instant::now();
+        let (metadata, res) = self.fetch()?;
+        let reader = ProgressReader {
+            inner: self.throttle(res),
+            so_far: 0,
+            total: metadata.content_length,
+            progress,
+        };
+        Ok(self.file_data(metadata, Box::new(reader), start))
+    }
+
+    /// Get a file as a stream of chunks, without buffering it in memory
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use std::io::Write;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let my_file = client.file(".my/my_dir/model.bin");
+    ///
+    /// let stream = my_file
+    ///     .get_stream()?
+    ///     .progress(|so_far, total| println!("{} of {:?} bytes", so_far, total));
+    /// let mut out = std::io::stdout();
+    /// for chunk in stream {
+    ///     out.write_all(&chunk?)?;
+    /// }
+    /// # Ok::<_, Box<std::error::Error>>(())
+    /// ```
+    pub fn get_stream(&self) -> Result<DataStream, Error> {
+        let start = Instant::now();
+        let (metadata, res) = self.fetch()?;
+        let total = metadata.content_length;
+        let data = self.throttle(res);
+        Ok(DataStream {
+            data: self.file_data(metadata, data, start),
+            total,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            so_far: 0,
+            progress: None,
+        })
+    }
+
+    /// Download a file to a local path, returning the number of bytes written
+    ///
+    /// The contents are streamed to disk rather than buffered in memory,
+    /// and `path` is only replaced once the download completes.
+    /// Use [`get_stream`](#method.get_stream) and
+    /// [`DataStream::save_to`](struct.DataStream.html#method.save_to) to report progress.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let bytes = client.file(".my/my_dir/model.bin").get_to_path("/tmp/model.bin")?;
+    /// # Ok::<_, Box<std::error::Error>>(())
+    /// ```
+    pub fn get_to_path<P: AsRef<Path>>(&self, path: P) -> Result<u64, Error> {
+        self.get_stream()?.save_to(path)
+    }
+
+    /// Get the size, last modified time, and content type of a file without downloading it
+    ///
+    /// Issues a `HEAD` request, so only the headers of the file are transferred.
+    /// Unlike [`exists`](../trait.HasDataPath.html#method.exists), a missing file is an error.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let meta = client.file(".my/my_dir/model.bin").stat()?;
+    /// println!("{} bytes, {:?}", meta.size, meta.content_type);
+    /// # Ok::<_, Box<std::error::Error>>(())
+    /// ```
+    pub fn stat(&self) -> Result<FileMetadata, Error> {
         let url = self.to_url()?;
-        let req = self.client.get(url);
-        let res = req
-            .send()
-            .with_context(|| format!("request error downloading file '{}'", self.to_data_uri()))
+        let req = self.client.head(url);
+        let res = self
+            .client
+            .send(req)
+            .with_context(|| format!("request error inspecting file '{}'", self.to_data_uri()))
             .and_then(process_http_response)
-            .with_context(|| format!("response error downloading file '{}'", self.to_data_uri()))?;
+            .with_context(|| format!("response error inspecting file '{}'", self.to_data_uri()))
+            .map_err(|err| err.with_data_error(DataOperation::Inspect, self.to_data_uri()))?;
+
+        let metadata = parse_headers(res.headers())?;
+        match metadata.data_type {
+            DataType::File => (),
+            DataType::Dir => {
+                bail!("expected API response with data type 'file', received 'directory'")
+            }
+        }
+        Ok(FileMetadata {
+            size: metadata.content_length.unwrap_or(0),
+            last_modified: metadata.last_modified,
+            content_type: metadata.content_type,
+            etag: metadata.etag,
+            data_uri: self.to_data_uri(),
+            _dummy: (),
+        })
+    }
+
+    /// Open a download as a request body for forwarding to another request
+    ///
+    /// Returns the body with the file's size and content type, if known.
+    pub(crate) fn get_body(&self) -> Result<(Body, Option<u64>, Option<String>), Error> {
+        let (metadata, res) = self.fetch()?;
+        let body = match (self.rate_limit(), metadata.content_length) {
+            (Some(rate), Some(len)) => Body::sized(ThrottledReader::new(res, rate), len),
+            (Some(rate), None) => Body::new(ThrottledReader::new(res, rate)),
+            (None, Some(len)) => Body::sized(res, len),
+            (None, None) => Body::new(res),
+        };
+        Ok((body, metadata.content_length, metadata.content_type))
+    }
+
+    fn fetch(&self) -> Result<(HeaderData, Response), Error> {
+        match self.fetch_with(HeaderMap::new())? {
+            Some(fetched) => Ok(fetched),
+            None => bail!(
+                "unexpected status 304 downloading file '{}'",
+                self.to_data_uri()
+            ),
+        }
+    }
+
+    /// Download a file with extra request headers
+    ///
+    /// Returns `None` if `headers` include `If-None-Match` and the file wasn't modified.
+    fn fetch_with(&self, headers: HeaderMap) -> Result<Option<(HeaderData, Response)>, Error> {
+        let conditional = headers.contains_key(IF_NONE_MATCH);
+        let url = self.to_url()?;
+        let req = self.client.get(url).headers(headers);
+        let res = self
+            .client
+            .send(req)
+            .with_context(|| format!("request error downloading file '{}'", self.to_data_uri()))
+            .and_then(|res| match res.status() {
+                StatusCode::NOT_MODIFIED if conditional => Ok(None),
+                _ => process_http_response(res).map(Some),
+            })
+            .with_context(|| format!("response error downloading file '{}'", self.to_data_uri()))
+            .map_err(|err| err.with_data_error(DataOperation::Download, self.to_data_uri()))?;
+        let res = match res {
+            Some(res) => res,
+            None => return Ok(None),
+        };
 
         let metadata = parse_headers(res.headers())?;
         match metadata.data_type {
@@ -141,14 +771,30 @@ impl DataFile {
                 bail!("expected API response with data type 'file', received 'directory'")
             }
         }
+        Ok(Some((metadata, res)))
+    }
+
+    fn throttle<R: Read + 'static>(&self, reader: R) -> Box<dyn Read> {
+        match self.rate_limit() {
+            Some(rate) => Box::new(ThrottledReader::new(reader, rate)),
+            None => Box::new(reader),
+        }
+    }
 
-        Ok(FileData {
+    fn file_data(&self, metadata: HeaderData, data: Box<dyn Read>, start: Instant) -> FileData {
+        FileData {
             size: metadata.content_length.unwrap_or(0),
             last_modified: metadata
                 .last_modified
-                .unwrap_or_else(|| Utc.ymd(2015, 3, 14).and_hms(8, 0, 0)),
-            data: Box::new(res),
-        })
+                .unwrap_or_else(|| Utc.with_ymd_and_hms(2015, 3, 14, 8, 0, 0).unwrap()),
+            content_type: metadata.content_type,
+            etag: metadata.etag,
+            data_uri: self.to_data_uri(),
+            data,
+            start,
+            bytes_read: 0,
+            finished: None,
+        }
     }
 
     /// Delete a file from from the Algorithmia Data API
@@ -168,11 +814,359 @@ impl DataFile {
     pub fn delete(&self) -> Result<(), Error> {
         let url = self.to_url()?;
         let req = self.client.delete(url);
-        req.send()
+        self.client
+            .send(req)
             .with_context(|| format!("request error deleting file '{}'", self.to_data_uri()))
             .and_then(process_http_response)
-            .with_context(|| format!("response error deleting file '{}'", self.to_data_uri()))?;
+            .with_context(|| format!("response error deleting file '{}'", self.to_data_uri()))
+            .map_err(|err| err.with_data_error(DataOperation::Delete, self.to_data_uri()))?;
 
         Ok(())
     }
 }
+
+/// Reader over chunks from an iterator, recording the first error it yields
+struct ChunkReader<I> {
+    chunks: I,
+    current: io::Cursor<Vec<u8>>,
+    err: Arc<Mutex<Option<io::Error>>>,
+}
+
+impl<I: Iterator<Item = io::Result<Vec<u8>>>> Read for ChunkReader<I> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let len = self.current.read(buf)?;
+            if len > 0 || buf.is_empty() {
+                return Ok(len);
+            }
+            match self.chunks.next() {
+                Some(Ok(chunk)) => self.current = io::Cursor::new(chunk),
+                Some(Err(err)) => {
+                    let kind = err.kind();
+                    *self.err.lock().unwrap() = Some(err);
+                    return Err(io::Error::new(kind, "chunk producer failed"));
+                }
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_throttled_reader() {
+        let mut reader = ThrottledReader::new(&[0u8; 300][..], 1000);
+        let start = Instant::now();
+        reader.read_to_end(&mut Vec::new()).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_progress_reader() {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let recorded = calls.clone();
+        let mut reader = ProgressReader {
+            inner: &b"hello world"[..],
+            so_far: 0,
+            total: Some(11),
+            progress: move |so_far, total| recorded.borrow_mut().push((so_far, total)),
+        };
+        let mut buf = [0; 6];
+        reader.read_exact(&mut buf).unwrap();
+        reader.read_to_end(&mut Vec::new()).unwrap();
+        assert_eq!(
+            *calls.borrow(),
+            vec![(6, Some(11)), (11, Some(11)), (11, Some(11))]
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_put_file_content_type() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use crate::Algorithmia;
+
+        let server = FakeServer::start().unwrap();
+        server.route(
+            "PUT",
+            "/v1/connector/data/.my/up",
+            FakeResponse::status(200),
+        );
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        std::fs::write(dir.join("photo"), b"\x89PNG\r\n\x1a\n...").unwrap();
+        std::fs::write(dir.join("notes.json"), b"plain text").unwrap();
+
+        let plain = server.client().unwrap();
+        let sniffing = Algorithmia::builder()
+            .api_key("")
+            .base_url(server.url())
+            .sniff_content_type(true)
+            .build()
+            .unwrap();
+        plain.file(".my/up").put_file(dir.join("photo")).unwrap();
+        sniffing.file(".my/up").put_file(dir.join("photo")).unwrap();
+        sniffing
+            .file(".my/up")
+            .put_file(dir.join("notes.json"))
+            .unwrap();
+
+        let requests = server.requests();
+        let content_types: Vec<_> = requests.iter().map(|r| r.header("Content-Type")).collect();
+        // The extension takes precedence over the contents
+        assert_eq!(
+            content_types,
+            vec![None, Some("image/png"), Some("application/json")]
+        );
+        assert_eq!(requests[1].body, b"\x89PNG\r\n\x1a\n...");
+    }
+
+    #[test]
+    fn test_chunk_reader() {
+        let err = Arc::new(Mutex::new(None));
+        let chunks = vec![Ok(b"ab".to_vec()), Ok(Vec::new()), Ok(b"cde".to_vec())];
+        let mut reader = ChunkReader {
+            chunks: chunks.into_iter(),
+            current: io::Cursor::new(Vec::new()),
+            err: err.clone(),
+        };
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"abcde");
+
+        let chunks = vec![
+            Ok(b"ab".to_vec()),
+            Err(io::Error::other("compressor failed")),
+        ];
+        let mut reader = ChunkReader {
+            chunks: chunks.into_iter(),
+            current: io::Cursor::new(Vec::new()),
+            err: err.clone(),
+        };
+        assert!(reader.read_to_end(&mut Vec::new()).is_err());
+        let recorded = err.lock().unwrap().take().unwrap();
+        assert_eq!(recorded.to_string(), "compressor failed");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_put_chunks() {
+        use crate::testing::{FakeResponse, FakeServer};
+
+        let server = FakeServer::start().unwrap();
+        server.route(
+            "PUT",
+            "/v1/connector/data/.my/up",
+            FakeResponse::status(200),
+        );
+        let client = server.client().unwrap();
+
+        let chunks = (0..3).map(|i| Ok(format!("chunk{};", i).into_bytes()));
+        client.file(".my/up").put_chunks(chunks).unwrap();
+        assert_eq!(server.requests()[0].body_string(), "chunk0;chunk1;chunk2;");
+
+        let failing = vec![
+            Ok(b"partial".to_vec()),
+            Err(io::Error::other("compressor failed")),
+        ];
+        let err = client.file(".my/up").put_chunks(failing).unwrap_err();
+        assert!(err.to_string().contains("error producing data"));
+        assert_eq!(
+            std::error::Error::source(&err).unwrap().to_string(),
+            "compressor failed"
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_data_error() {
+        use crate::testing::{FakeResponse, FakeServer};
+        use http::StatusCode;
+
+        let server = FakeServer::start().unwrap();
+        server.route(
+            "GET",
+            "/v1/connector/data/.my/foo/secret.txt",
+            FakeResponse::status(403).with_header("X-Error-Message", "access denied"),
+        );
+        server.route(
+            "HEAD",
+            "/v1/connector/data/.my/foo/secret.txt",
+            FakeResponse::status(403).with_header("X-Error-Message", "access denied"),
+        );
+        server.algo_error("demo/Fails", "AlgorithmError", "boom");
+        let client = server.client().unwrap();
+
+        let file = client.file("data://.my/foo/secret.txt");
+        let err = file.get().err().unwrap();
+        let data_err = err.data_error().unwrap();
+        assert_eq!(data_err.uri, "data://.my/foo/secret.txt");
+        assert_eq!(data_err.operation, DataOperation::Download);
+        assert_eq!(data_err.status, Some(StatusCode::FORBIDDEN));
+        assert_eq!(data_err.message.as_deref(), Some("access denied"));
+        assert_eq!(
+            data_err.to_string(),
+            "download 'data://.my/foo/secret.txt' failed with 403 Forbidden: access denied"
+        );
+
+        let err = file.exists().unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+        assert_eq!(err.data_error().unwrap().operation, DataOperation::Inspect);
+
+        let err = client.algo("demo/Fails").pipe("input").unwrap_err();
+        assert!(err.data_error().is_none());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_get_stream() {
+        use crate::testing::FakeServer;
+
+        let server = FakeServer::start().unwrap();
+        server.data_file("data://.my/foo/model.bin", "0123456789");
+        let client = server.client().unwrap();
+        let file = client.file("data://.my/foo/model.bin");
+
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let recorded = calls.clone();
+        let stream = file
+            .get_stream()
+            .unwrap()
+            .chunk_size(4)
+            .progress(move |so_far, total| recorded.borrow_mut().push((so_far, total)));
+        assert_eq!(stream.total(), Some(10));
+        let chunks: Vec<_> = stream.map(Result::unwrap).collect();
+        assert_eq!(chunks, vec![&b"0123"[..], b"4567", b"89"]);
+        assert_eq!(calls.borrow().last(), Some(&(10, Some(10))));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let dest = dir.join("model.bin");
+        assert_eq!(file.get_to_path(&dest).unwrap(), 10);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"0123456789");
+        assert!(!dir.join("model.bin.part").exists());
+
+        let missing = client.file("data://.my/foo/missing.bin");
+        assert!(missing.get_to_path(dir.join("missing.bin")).is_err());
+        assert!(!dir.join("missing.bin").exists());
+        assert!(!dir.join("missing.bin.part").exists());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_get_if_none_match() {
+        use crate::testing::{FakeResponse, FakeServer};
+
+        let server = FakeServer::start().unwrap();
+        let path = "/v1/connector/data/.my/foo/model.bin";
+        server.route(
+            "GET",
+            path,
+            FakeResponse::status(200)
+                .with_header("X-Data-Type", "file")
+                .with_header("ETag", "\"v2\"")
+                .with_body("new model"),
+        );
+        let client = server.client().unwrap();
+        let file = client.file("data://.my/foo/model.bin");
+
+        match file.get_if_none_match("\"v1\"").unwrap() {
+            ConditionalData::Modified(data) => {
+                assert_eq!(data.etag.as_deref(), Some("\"v2\""));
+                assert_eq!(data.into_string().unwrap(), "new model");
+            }
+            ConditionalData::NotModified => panic!("expected a download"),
+        }
+        assert_eq!(server.requests()[0].header("If-None-Match"), Some("\"v1\""));
+
+        server.route("GET", path, FakeResponse::status(304));
+        match file.get_if_none_match("\"v2\"").unwrap() {
+            ConditionalData::Modified(_) => panic!("expected not modified"),
+            ConditionalData::NotModified => (),
+        }
+        assert!(file.get().is_err());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_get_range() {
+        use crate::testing::{FakeResponse, FakeServer};
+
+        let server = FakeServer::start().unwrap();
+        let path = "/v1/connector/data/.my/foo/data.zip";
+        server.route(
+            "GET",
+            path,
+            FakeResponse::status(206)
+                .with_header("X-Data-Type", "file")
+                .with_header("Content-Range", "bytes 2-5/10")
+                .with_body("2345"),
+        );
+        let client = server.client().unwrap();
+        let file = client.file("data://.my/foo/data.zip");
+
+        let data = file.get_range(2..6).unwrap();
+        assert_eq!(data.size, 4);
+        assert_eq!(data.into_string().unwrap(), "2345");
+        assert_eq!(server.requests()[0].header("Range"), Some("bytes=2-5"));
+
+        // Ranges are still honored if the whole file is returned
+        server.data_file("data://.my/foo/data.zip", "0123456789");
+        let data = file.get_range(2..=5).unwrap();
+        assert_eq!(data.size, 4);
+        assert_eq!(data.into_string().unwrap(), "2345");
+        let data = file.get_range(7..).unwrap();
+        assert_eq!(data.size, 3);
+        assert_eq!(data.into_string().unwrap(), "789");
+        assert_eq!(server.requests()[2].header("Range"), Some("bytes=7-"));
+
+        assert!(file.get_range(5..5).is_err());
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_stat() {
+        use crate::testing::{FakeResponse, FakeServer};
+
+        let server = FakeServer::start().unwrap();
+        server.route(
+            "HEAD",
+            "/v1/connector/data/.my/foo/model.bin",
+            FakeResponse::status(200)
+                .with_header("X-Data-Type", "file")
+                .with_header("Content-Type", "application/octet-stream")
+                .with_header("ETag", "abc123")
+                .with_header("Date", "Sat, 14 Mar 2015 08:00:00 GMT"),
+        );
+        server.data_dir("data://.my/foo", serde_json::json!({}));
+        let client = server.client().unwrap();
+
+        let meta = client.file("data://.my/foo/model.bin").stat().unwrap();
+        assert_eq!(meta.data_uri, "data://.my/foo/model.bin");
+        assert_eq!(
+            meta.content_type.as_deref(),
+            Some("application/octet-stream")
+        );
+        assert_eq!(meta.etag.as_deref(), Some("abc123"));
+        assert_eq!(
+            meta.last_modified,
+            Some(Utc.with_ymd_and_hms(2015, 3, 14, 8, 0, 0).unwrap())
+        );
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "HEAD");
+
+        let missing = client
+            .file("data://.my/foo/missing.bin")
+            .stat()
+            .unwrap_err();
+        assert_eq!(missing.status(), Some(StatusCode::NOT_FOUND));
+        assert!(client.file("data://.my/foo").stat().is_err());
+    }
+}
@@ -2,45 +2,70 @@
 //!
 //! Instantiate from the [`Algorithmia`](../struct.Algorithmia.html) struct
 
+pub use self::background::BackgroundWriter;
+#[cfg(feature = "cache")]
+pub use self::cache::{CachedFile, FileCache};
 pub use self::dir::*;
 pub use self::file::*;
 pub use self::object::*;
 pub use self::path::*;
 
 use crate::error::{err_msg, Error};
-use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, Utc};
 use headers_ext::{ContentLength, Date, HeaderMapExt};
-use http::header::HeaderMap;
+use http::header::{HeaderMap, CONTENT_TYPE, ETAG};
 use std::ops::Deref;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::SystemTime;
 
+mod background;
+#[cfg(feature = "cache")]
+mod cache;
+mod content_type;
 mod dir;
 mod file;
 mod object;
+#[cfg(feature = "arrow")]
+mod parquet;
 mod path;
+pub mod sync;
 
-static DATA_BASE_PATH: &'static str = "v1/connector";
+static DATA_BASE_PATH: &str = "v1/connector";
+
+/// Connector schemes supported by default in data URIs, e.g. `s3://bucket/key`
+///
+/// Other connectors can be registered with
+/// [`ClientBuilder::connector`](../struct.ClientBuilder.html#method.connector).
+pub const CONNECTORS: &[&str] = &["data", "dropbox", "s3", "azure", "gcs"];
 
 use crate::client::header::{lossy_header, X_DATA_TYPE};
 
 /// Minimal representation of data type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DataType {
     File,
     Dir,
 }
 
 /// Data type wrapping the data item (including any metadata)
+#[derive(Debug, Clone)]
 pub enum DataItem {
     File(DataFileItem),
     Dir(DataDirItem),
 }
 
 /// `DataFile` wrapper with metadata
+///
+/// The full data URI is available via `to_data_uri()`.
+#[derive(Debug, Clone)]
 pub struct DataFileItem {
     /// Size of file in bytes
     pub size: u64,
     /// Last modified timestamp
     pub last_modified: DateTime<Utc>,
+    /// Content type of the file, if provided by the API
+    pub content_type: Option<String>,
+    /// ETag (checksum) of the file contents, if provided by the API
+    pub etag: Option<String>,
     file: DataFile,
 }
 
@@ -52,6 +77,7 @@ impl Deref for DataFileItem {
 }
 
 /// `DataDir` wrapper (currently no metadata)
+#[derive(Debug, Clone)]
 pub struct DataDirItem {
     dir: DataDir,
 }
@@ -67,6 +93,8 @@ struct HeaderData {
     pub data_type: DataType,
     pub content_length: Option<u64>,
     pub last_modified: Option<DateTime<Utc>>,
+    pub content_type: Option<String>,
+    pub etag: Option<String>,
 }
 
 fn parse_headers(headers: &HeaderMap) -> Result<HeaderData, Error> {
@@ -76,31 +104,30 @@ fn parse_headers(headers: &HeaderMap) -> Result<HeaderData, Error> {
         Some(dt) => {
             return Err(err_msg(format!(
                 "API responded with invalid data type: '{}'",
-                dt.to_string()
+                dt
             )));
         }
         None => return Err(err_msg("API response missing data type")),
     };
 
     let content_length = headers.typed_get::<ContentLength>().map(|c| c.0);
-    let last_modified = headers.typed_get::<Date>().map(|d| {
-        let time = SystemTime::from(d);
-        let ts = time
-            .duration_since(UNIX_EPOCH)
-            .expect("date header predates unix epoch");
-        let naive_datetime =
-            NaiveDateTime::from_timestamp(ts.as_secs() as i64, ts.subsec_nanos() as u32);
-        Utc.from_utc_datetime(&naive_datetime)
-    });
+    let last_modified = headers
+        .typed_get::<Date>()
+        .map(|d| DateTime::<Utc>::from(SystemTime::from(d)));
+
+    let content_type = headers.get(CONTENT_TYPE).map(lossy_header);
+    let etag = headers.get(ETAG).map(lossy_header);
 
     Ok(HeaderData {
-        data_type: data_type,
-        content_length: content_length,
-        last_modified: last_modified,
+        data_type,
+        content_length,
+        last_modified,
+        content_type,
+        etag,
     })
 }
 
-fn parse_data_uri(data_uri: &str) -> String {
+pub(crate) fn parse_data_uri(data_uri: &str) -> String {
     match data_uri {
         p if p.contains("://") => p.split_terminator("://").collect::<Vec<_>>().join("/"),
         p if p.starts_with('/') => format!("data/{}", &p[1..]),
@@ -110,7 +137,7 @@ fn parse_data_uri(data_uri: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_data_uri;
+    use super::*;
 
     #[test]
     fn test_parse_protocol() {
@@ -137,4 +164,15 @@ mod tests {
         assert_eq!(parse_data_uri("foo/"), "data/foo/");
         assert_eq!(parse_data_uri("foo/bar"), "data/foo/bar");
     }
+
+    #[test]
+    fn test_parse_headers_metadata() {
+        let mut headers = HeaderMap::new();
+        headers.insert(X_DATA_TYPE, "file".parse().unwrap());
+        headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+        headers.insert(ETAG, "\"abc123\"".parse().unwrap());
+        let metadata = parse_headers(&headers).unwrap();
+        assert_eq!(metadata.content_type.as_deref(), Some("text/plain"));
+        assert_eq!(metadata.etag.as_deref(), Some("\"abc123\""));
+    }
 }
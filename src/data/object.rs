@@ -1,20 +1,29 @@
 use super::{parse_data_uri, parse_headers};
 use crate::client::HttpClient;
 use crate::data::*;
-use crate::error::{process_http_response, Error, ResultExt};
+use crate::error::{process_http_response, DataOperation, Error, ResultExt};
 use chrono::{TimeZone, Utc};
 
 /// Algorithmia data object (file or directory)
+#[derive(Clone)]
 pub struct DataObject {
     path: String,
     client: HttpClient,
 }
 
+impl std::fmt::Debug for DataObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("DataObject")
+            .field(&self.to_data_uri())
+            .finish()
+    }
+}
+
 impl HasDataPath for DataObject {
     #[doc(hidden)]
     fn new(client: HttpClient, path: &str) -> Self {
         DataObject {
-            client: client,
+            client,
             path: parse_data_uri(path).to_string(),
         }
     }
@@ -45,11 +54,13 @@ impl DataObject {
     pub fn get_type(&self) -> Result<DataType, Error> {
         let url = self.to_url()?;
         let req = self.client.head(url);
-        let res = req
-            .send()
+        let res = self
+            .client
+            .send(req)
             .with_context(|| format!("request error getting type of '{}'", self.to_data_uri()))
             .and_then(process_http_response)
-            .with_context(|| format!("response error getting type of '{}'", self.to_data_uri()))?;
+            .with_context(|| format!("response error getting type of '{}'", self.to_data_uri()))
+            .map_err(|err| err.with_data_error(DataOperation::Inspect, self.to_data_uri()))?;
 
         let metadata = parse_headers(res.headers())?;
         Ok(metadata.data_type)
@@ -72,13 +83,13 @@ impl DataObject {
         let metadata = {
             let url = self.to_url()?;
             let req = self.client.head(url);
-            let res = req
-                .send()
+            let res = self
+                .client
+                .send(req)
                 .with_context(|| format!("request error getting type of '{}'", self.to_data_uri()))
                 .and_then(process_http_response)
-                .with_context(|| {
-                    format!("response error getting type of '{}'", self.to_data_uri())
-                })?;
+                .with_context(|| format!("response error getting type of '{}'", self.to_data_uri()))
+                .map_err(|err| err.with_data_error(DataOperation::Inspect, self.to_data_uri()))?;
             parse_headers(res.headers())?
         };
 
@@ -90,7 +101,9 @@ impl DataObject {
                     last_modified: metadata
                         .last_modified
                         // Fallback to Algorithmia public launch date :-)
-                        .unwrap_or_else(|| Utc.ymd(2015, 3, 14).and_hms(8, 0, 0)),
+                        .unwrap_or_else(|| Utc.with_ymd_and_hms(2015, 3, 14, 8, 0, 0).unwrap()),
+                    content_type: metadata.content_type,
+                    etag: metadata.etag,
                     file: self.into(),
                 }))
             }
@@ -0,0 +1,122 @@
+//! Parquet helpers for reading and writing Arrow record batches [feature = "arrow"]
+
+use crate::data::{DataFile, HasDataPath};
+use crate::error::{Error, ResultExt};
+
+use arrow_array::RecordBatchReader;
+use bytes::Bytes;
+use mime::Mime;
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
+use parquet::arrow::ArrowWriter;
+
+use std::io::Read;
+
+const PARQUET_CONTENT_TYPE: &str = "application/vnd.apache.parquet";
+
+impl DataFile {
+    /// Read a Parquet file as Arrow record batches [feature = "arrow"]
+    ///
+    /// Parquet keeps its metadata at the end of the file, so the file is downloaded into memory
+    /// before decoding; batches are then decoded lazily as the reader is iterated.
+    /// No temporary files are written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let batches = client.file(".my/datasets/iris.parquet").get_parquet()?;
+    /// for batch in batches {
+    ///     println!("{} rows", batch?.num_rows());
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn get_parquet(&self) -> Result<ParquetRecordBatchReader, Error> {
+        let mut data = self.get()?;
+        let mut bytes = Vec::with_capacity(data.size as usize);
+        data.read_to_end(&mut bytes)
+            .with_context(|| format!("error downloading file '{}'", self.to_data_uri()))?;
+
+        ParquetRecordBatchReaderBuilder::try_new(Bytes::from(bytes))
+            .and_then(|builder| builder.build())
+            .with_context(|| format!("error reading parquet file '{}'", self.to_data_uri()))
+    }
+
+    /// Write Arrow record batches to this file in Parquet format [feature = "arrow"]
+    ///
+    /// Batches are encoded as they are read from `batches` into an in-memory buffer,
+    /// which is uploaded once every batch has been written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let source = client.file(".my/datasets/iris.parquet").get_parquet()?;
+    /// client.file(".my/datasets/iris_copy.parquet").put_parquet(source)?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn put_parquet<R: RecordBatchReader>(&self, batches: R) -> Result<(), Error> {
+        let context = || format!("error writing parquet file '{}'", self.to_data_uri());
+
+        let mut writer =
+            ArrowWriter::try_new(Vec::new(), batches.schema(), None).with_context(context)?;
+        for batch in batches {
+            writer
+                .write(&batch.with_context(context)?)
+                .with_context(context)?;
+        }
+        let bytes = writer.into_inner().with_context(context)?;
+
+        let content_type: Mime = PARQUET_CONTENT_TYPE.parse().unwrap();
+        self.put_as(bytes, Some(content_type))
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use crate::testing::{FakeResponse, FakeServer};
+    use arrow_array::{ArrayRef, Int32Array, RecordBatch, RecordBatchIterator, StringArray};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_parquet_roundtrip() {
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+        server.route(
+            "PUT",
+            "/v1/connector/data/.my/test/table.parquet",
+            FakeResponse::status(200),
+        );
+
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef),
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["a", "b", "c"])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let schema = batch.schema();
+        let batches = RecordBatchIterator::new(vec![Ok(batch.clone())], schema);
+        client
+            .file("data://.my/test/table.parquet")
+            .put_parquet(batches)
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(
+            requests[0].header("Content-Type"),
+            Some("application/vnd.apache.parquet")
+        );
+        assert_eq!(&requests[0].body[..4], b"PAR1");
+
+        server.data_file("data://.my/test/table.parquet", requests[0].body.clone());
+        let read: Vec<_> = client
+            .file("data://.my/test/table.parquet")
+            .get_parquet()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, vec![batch]);
+    }
+}
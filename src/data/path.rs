@@ -1,6 +1,5 @@
-use crate::client::header::X_ERROR_MESSAGE;
 use crate::data::*;
-use crate::error::{ApiError, Error, ResultExt};
+use crate::error::{process_http_response, DataOperation, Error, ErrorKind, ResultExt};
 
 use crate::client::HttpClient;
 use reqwest::{StatusCode, Url};
@@ -15,9 +14,18 @@ pub trait HasDataPath {
     fn client(&self) -> &HttpClient;
 
     /// Get the API Endpoint URL for a particular data URI
+    ///
+    /// Uses the data base URL if one was configured with
+    /// [`ClientBuilder::data_base_url`](../struct.ClientBuilder.html#method.data_base_url).
+    /// Fails with `ErrorKind::UnsupportedConnector` if the URI's connector scheme
+    /// is not one of [`CONNECTORS`](constant.CONNECTORS.html) or registered with the client.
     fn to_url(&self) -> Result<Url, Error> {
+        let scheme = self.path().split('/').next().unwrap_or_default();
+        if !self.client().supports_connector(scheme) {
+            return Err(ErrorKind::UnsupportedConnector(scheme.to_owned()).into());
+        }
         let path = format!("{}/{}", super::DATA_BASE_PATH, self.path());
-        self.client().base_url.join(&path).with_context(|| {
+        self.client().data_base_url().join(&path).with_context(|| {
             format!(
                 "Failed to construct URL from data URI {}",
                 self.to_data_uri()
@@ -75,7 +83,7 @@ pub trait HasDataPath {
     /// # Ok::<(), Box<std::error::Error>>(())
     /// ```
     fn basename(&self) -> Option<String> {
-        self.path().rsplitn(2, '/').next().map(String::from)
+        self.path().rsplit('/').next().map(String::from)
     }
 
     /// Determine if a file or directory exists for a particular data URI
@@ -93,23 +101,25 @@ pub trait HasDataPath {
         let client = self.client();
         let req = client.head(url);
 
-        let res = req
-            .send()
-            .with_context(|| format!("checking existence of '{}'", self.to_data_uri()))?;
+        let res = client
+            .send(req)
+            .with_context(|| format!("checking existence of '{}'", self.to_data_uri()))
+            .map_err(|err| err.with_data_error(DataOperation::Inspect, self.to_data_uri()))?;
         match res.status() {
             StatusCode::OK => Ok(true),
             StatusCode::NOT_FOUND => Ok(false),
-            status => {
-                let msg = match res
-                    .headers()
-                    .get(X_ERROR_MESSAGE)
-                    .and_then(|x| x.to_str().ok())
-                {
-                    Some(err_header) => format!("{}: {}", status, err_header),
-                    None => format!("{}", status),
-                };
-                Err(ApiError::from(msg).into())
+            status if status.is_success() => {
+                bail!(
+                    "unexpected status {} checking existence of '{}'",
+                    status,
+                    self.to_data_uri()
+                )
             }
+            // Error statuses carry the API's error message
+            _ => process_http_response(res)
+                .map(|_| true)
+                .with_context(|| format!("checking existence of '{}'", self.to_data_uri()))
+                .map_err(|err| err.with_data_error(DataOperation::Inspect, self.to_data_uri())),
         }
     }
 }
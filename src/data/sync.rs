@@ -0,0 +1,555 @@
+//! Compare local directories with Algorithmia Data Directories
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use algorithmia::Algorithmia;
+//! use algorithmia::data::sync;
+//!
+//! let client = Algorithmia::client("111112222233333444445555566")?;
+//! let changes = sync::diff("./models", &client.dir(".my/models"))?;
+//! for path in &changes.only_local {
+//!     println!("would upload {}", path);
+//! }
+//! # Ok::<(), Box<std::error::Error>>(())
+//! ```
+//!
+//! Files matching patterns in a `.algoignore` file at the root of the local directory
+//! (using `.gitignore` syntax) are skipped. Use a [`Filter`](struct.Filter.html)
+//! to add patterns programmatically. Symlinks to files are followed, but symlinked
+//! directories are skipped.
+
+use crate::data::{DataDir, FileEntry, HasDataPath};
+use crate::error::{err_msg, Error, ResultExt};
+
+use chrono::{DateTime, Utc};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the ignore file read from the root of a local directory
+pub const IGNORE_FILE: &str = ".algoignore";
+
+/// Selects which files take part in a sync
+///
+/// Patterns use `.gitignore` syntax and are relative to the root of the local directory
+/// (e.g. `target/`, `*.ckpt`, or `/cache`). They apply to the paths of both local and
+/// remote files, so ignored remote files aren't reported either.
+///
+/// Patterns from the `.algoignore` file are applied first, followed by the patterns added
+/// with [`exclude`](#method.exclude) and [`include`](#method.include) in the order they were added.
+/// As with `.gitignore`, the last matching pattern wins.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use algorithmia::Algorithmia;
+/// use algorithmia::data::sync::{self, Filter};
+///
+/// let client = Algorithmia::client("111112222233333444445555566")?;
+/// let filter = Filter::new()
+///     .exclude("target/")
+///     .exclude("*.ckpt")
+///     .include("final.ckpt");
+/// let changes = sync::diff_with("./models", &client.dir(".my/models"), &filter)?;
+/// # Ok::<(), Box<std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Filter {
+    patterns: Vec<String>,
+    ignore_file: bool,
+}
+
+impl Default for Filter {
+    fn default() -> Filter {
+        Filter {
+            patterns: Vec::new(),
+            ignore_file: true,
+        }
+    }
+}
+
+impl Filter {
+    /// Create a filter that only applies the `.algoignore` file, if present
+    pub fn new() -> Filter {
+        Filter::default()
+    }
+
+    /// Skip files matching a pattern
+    pub fn exclude<S: Into<String>>(mut self, pattern: S) -> Filter {
+        self.patterns.push(pattern.into());
+        self
+    }
+
+    /// Keep files matching a pattern, even if an earlier pattern excludes them
+    ///
+    /// Equivalent to a `!pattern` line in the `.algoignore` file.
+    pub fn include<S: AsRef<str>>(mut self, pattern: S) -> Filter {
+        self.patterns.push(format!("!{}", pattern.as_ref()));
+        self
+    }
+
+    /// Whether to read the `.algoignore` file (default: `true`)
+    ///
+    /// When enabled, the `.algoignore` file itself is skipped as well.
+    pub fn ignore_file(mut self, enabled: bool) -> Filter {
+        self.ignore_file = enabled;
+        self
+    }
+
+    /// Compile the patterns for a local directory
+    pub(crate) fn matcher(&self, local_dir: &Path) -> Result<Matcher, Error> {
+        let mut builder = GitignoreBuilder::new(local_dir);
+        let ignore_path = local_dir.join(IGNORE_FILE);
+        if self.ignore_file {
+            // The ignore file configures the sync, so it isn't synced itself
+            builder
+                .add_line(None, &format!("/{}", IGNORE_FILE))
+                .map_err(|err| err_msg(format!("invalid ignore pattern: {}", err)))?;
+        }
+        if self.ignore_file && ignore_path.is_file() {
+            if let Some(err) = builder.add(&ignore_path) {
+                return Err(err_msg(format!(
+                    "invalid ignore file '{}': {}",
+                    ignore_path.display(),
+                    err
+                )));
+            }
+        }
+        for pattern in &self.patterns {
+            builder
+                .add_line(None, pattern)
+                .map_err(|err| err_msg(format!("invalid pattern '{}': {}", pattern, err)))?;
+        }
+        let gitignore = builder
+            .build()
+            .map_err(|err| err_msg(format!("invalid ignore patterns: {}", err)))?;
+        Ok(Matcher(gitignore))
+    }
+}
+
+/// Compiled `Filter` patterns
+pub(crate) struct Matcher(Gitignore);
+
+impl Matcher {
+    /// Whether a file is ignored, given its path relative to the root
+    pub(crate) fn is_ignored(&self, path: &str) -> bool {
+        self.0.matched_path_or_any_parents(path, false).is_ignore()
+    }
+
+    /// Whether nothing below an ignored directory can be included again
+    fn skips_dir(&self, path: &str) -> bool {
+        self.0.num_whitelists() == 0 && self.0.matched(path, true).is_ignore()
+    }
+}
+
+/// Differences between a local directory and a `DataDir`, returned by [`diff`](fn.diff.html)
+///
+/// Paths are relative to the compared directories and use `/` as the separator.
+/// Only files are compared; empty directories are ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangeSet {
+    /// Files that only exist locally
+    pub only_local: Vec<String>,
+    /// Files that only exist in the `DataDir`
+    pub only_remote: Vec<String>,
+    /// Files that exist in both places but differ
+    pub modified: Vec<Modified>,
+    /// Number of files that exist in both places and appear identical
+    pub unchanged: usize,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+impl ChangeSet {
+    /// Returns true if the directories appear identical
+    pub fn is_empty(&self) -> bool {
+        self.only_local.is_empty() && self.only_remote.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// A file that exists locally and in the `DataDir` but differs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Modified {
+    /// Path relative to the compared directories
+    pub path: String,
+    /// How the difference was detected
+    pub reason: ModifiedReason,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+/// How a modified file was detected
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModifiedReason {
+    /// The file sizes differ
+    Size {
+        /// Size of the local file in bytes
+        local: u64,
+        /// Size of the remote file in bytes
+        remote: u64,
+    },
+    /// The MD5 checksum of the local file doesn't match the remote ETag
+    Hash,
+    /// The local file was modified after the remote file was last written
+    Mtime {
+        /// Last modified time of the local file
+        local: DateTime<Utc>,
+        /// Last modified time of the remote file
+        remote: DateTime<Utc>,
+    },
+
+    /// Non-exhaustive for API stability if detection methods are added
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// Compare a local directory with a `DataDir` without transferring file contents
+///
+/// Both directories are listed recursively. Files present in both are compared by:
+///
+/// 1. size, then
+/// 2. MD5 checksum, when the remote ETag is an MD5 checksum (this reads the local file), or otherwise
+/// 3. modification time, considering the file modified if the local copy is newer than the remote one.
+///
+/// A `DataDir` that doesn't exist is treated as empty.
+/// Files matching the local `.algoignore` file are skipped; use [`diff_with`](fn.diff_with.html)
+/// for more control.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use algorithmia::Algorithmia;
+/// # use algorithmia::data::sync;
+/// let client = Algorithmia::client("111112222233333444445555566")?;
+/// let changes = sync::diff("./models", &client.dir(".my/models"))?;
+/// if changes.is_empty() {
+///     println!("Already in sync");
+/// }
+/// # Ok::<(), Box<std::error::Error>>(())
+/// ```
+pub fn diff<P: AsRef<Path>>(local_dir: P, data_dir: &DataDir) -> Result<ChangeSet, Error> {
+    diff_with(local_dir, data_dir, &Filter::default())
+}
+
+/// Compare a local directory with a `DataDir`, skipping files according to `filter`
+///
+/// See [`diff`](fn.diff.html) for how files are compared.
+pub fn diff_with<P: AsRef<Path>>(
+    local_dir: P,
+    data_dir: &DataDir,
+    filter: &Filter,
+) -> Result<ChangeSet, Error> {
+    let local_dir = local_dir.as_ref();
+    let matcher = filter.matcher(local_dir)?;
+    let mut local = BTreeMap::new();
+    walk_local(local_dir, "", &matcher, &mut local)
+        .with_context(|| format!("listing local directory '{}'", local_dir.display()))?;
+
+    let mut remote: BTreeMap<String, FileEntry> = BTreeMap::new();
+    if data_dir.exists()? {
+        let (_, files) = data_dir.walk()?;
+        remote.extend(
+            files
+                .into_iter()
+                .filter(|f| !matcher.is_ignored(&f.name))
+                .map(|f| (f.name.clone(), f)),
+        );
+    }
+
+    let mut changes = ChangeSet::default();
+    for (path, local_path) in &local {
+        let remote_file = match remote.remove(path) {
+            Some(remote_file) => remote_file,
+            None => {
+                changes.only_local.push(path.clone());
+                continue;
+            }
+        };
+        match compare(local_path, &remote_file)
+            .with_context(|| format!("comparing '{}'", local_path.display()))?
+        {
+            Some(reason) => changes.modified.push(Modified {
+                path: path.clone(),
+                reason,
+                _dummy: (),
+            }),
+            None => changes.unchanged += 1,
+        }
+    }
+    changes.only_remote = remote.into_keys().collect();
+    Ok(changes)
+}
+
+/// Local files that aren't skipped by `filter`, keyed by their path relative to `local_dir`
+pub(crate) fn local_files(
+    local_dir: &Path,
+    filter: &Filter,
+) -> Result<BTreeMap<String, PathBuf>, Error> {
+    let matcher = filter.matcher(local_dir)?;
+    let mut files = BTreeMap::new();
+    walk_local(local_dir, "", &matcher, &mut files)
+        .with_context(|| format!("listing local directory '{}'", local_dir.display()))?;
+    Ok(files)
+}
+
+/// Recursively collect local files that aren't ignored, keyed by their relative path
+///
+/// Symlinks to files are followed, but symlinked directories are skipped.
+fn walk_local(
+    dir: &Path,
+    prefix: &str,
+    matcher: &Matcher,
+    files: &mut BTreeMap<String, PathBuf>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        let name = match path.file_name() {
+            Some(name) => name.to_string_lossy(),
+            None => continue,
+        };
+        let relative = match prefix {
+            "" => name.into_owned(),
+            prefix => format!("{}/{}", prefix, name),
+        };
+        if file_type.is_dir() {
+            if !matcher.skips_dir(&relative) {
+                walk_local(&path, &relative, matcher, files)?;
+            }
+        } else if file_type.is_symlink() && path.is_dir() {
+            // Symlinked directories aren't followed, since they may form loops
+            continue;
+        } else if !matcher.is_ignored(&relative) {
+            files.insert(relative, path);
+        }
+    }
+    Ok(())
+}
+
+/// Whether a local file is an up to date copy of a remote file
+///
+/// The sizes must match, and either the MD5 checksum matches an MD5 ETag or,
+/// without one, the local copy was modified after the remote file.
+pub(crate) fn is_current_copy(local_path: &Path, remote: &FileEntry) -> io::Result<bool> {
+    let has_checksum = remote.etag.as_deref().and_then(md5_etag).is_some();
+    match compare(local_path, remote) {
+        // A copy written after the remote file isn't modified from the download's point of view
+        Ok(Some(ModifiedReason::Mtime { .. })) => Ok(true),
+        // Without a checksum, `None` means the remote file changed after the local copy
+        Ok(reason) => Ok(reason.is_none() && has_checksum),
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+fn compare(local_path: &Path, remote: &FileEntry) -> io::Result<Option<ModifiedReason>> {
+    let metadata = fs::metadata(local_path)?;
+    if metadata.len() != remote.size {
+        return Ok(Some(ModifiedReason::Size {
+            local: metadata.len(),
+            remote: remote.size,
+        }));
+    }
+
+    if let Some(etag) = remote.etag.as_deref().and_then(md5_etag) {
+        let mut context = md5::Context::new();
+        io::copy(&mut File::open(local_path)?, &mut context)?;
+        let checksum = format!("{:x}", context.compute());
+        return Ok(if checksum == etag {
+            None
+        } else {
+            Some(ModifiedReason::Hash)
+        });
+    }
+
+    let local = DateTime::<Utc>::from(metadata.modified()?);
+    Ok(if local > remote.last_modified {
+        Some(ModifiedReason::Mtime {
+            local,
+            remote: remote.last_modified,
+        })
+    } else {
+        None
+    })
+}
+
+/// Extract the checksum from an ETag that holds an MD5 hex digest
+fn md5_etag(etag: &str) -> Option<String> {
+    let etag = etag.trim_start_matches("W/").trim_matches('"');
+    if etag.len() == 32 && etag.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(etag.to_ascii_lowercase())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_md5_etag() {
+        assert_eq!(
+            md5_etag("\"ACBD18DB4CC2F85CEDEF654FCCC4A4D8\"").as_deref(),
+            Some("acbd18db4cc2f85cedef654fccc4a4d8")
+        );
+        assert_eq!(md5_etag("\"abc123\""), None);
+        assert_eq!(md5_etag("\"acbd18db4cc2f85cedef654fccc4a4d8-2\""), None);
+    }
+
+    #[test]
+    fn test_is_current_copy() {
+        use serde_json::json;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let local = tmp.path().join("foo.txt");
+        std::fs::write(&local, "foo").unwrap();
+        let remote = |size: u64, last_modified: &str, etag: Option<&str>| -> FileEntry {
+            serde_json::from_value(json!({
+                "filename": "foo.txt",
+                "size": size,
+                "last_modified": last_modified,
+                "etag": etag,
+            }))
+            .unwrap()
+        };
+        let (old, future) = ("2015-03-14T08:00:00Z", "2999-01-01T00:00:00Z");
+        let md5 = Some("\"acbd18db4cc2f85cedef654fccc4a4d8\"");
+
+        assert!(is_current_copy(&local, &remote(3, old, None)).unwrap());
+        assert!(!is_current_copy(&local, &remote(3, future, None)).unwrap());
+        assert!(!is_current_copy(&local, &remote(4, old, None)).unwrap());
+        assert!(is_current_copy(&local, &remote(3, future, md5)).unwrap());
+        let other = Some("\"00000000000000000000000000000000\"");
+        assert!(!is_current_copy(&local, &remote(3, old, other)).unwrap());
+        let missing = tmp.path().join("missing.txt");
+        assert!(!is_current_copy(&missing, &remote(3, old, None)).unwrap());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_diff() {
+        use crate::testing::FakeServer;
+        use serde_json::json;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let local = tmp.path();
+        fs::create_dir_all(local.join("sub")).unwrap();
+        fs::write(local.join("same.txt"), "foo").unwrap();
+        fs::write(local.join("size.txt"), "12345").unwrap();
+        fs::write(local.join("newer.txt"), "abc").unwrap();
+        fs::write(local.join("local.txt"), "").unwrap();
+        fs::write(local.join("sub/hash.txt"), "x").unwrap();
+
+        let server = FakeServer::start().unwrap();
+        let file = |name: &str, size: u64, etag: Option<String>| json!({ "filename": name, "size": size, "last_modified": "2015-03-14T08:00:00Z", "etag": etag });
+        let md5 = |s: &str| Some(format!("\"{:x}\"", md5::compute(s)));
+        server.data_dir(
+            "data://.my/sync",
+            json!({
+                "folders": [{ "name": "sub" }],
+                "files": [
+                    file("same.txt", 3, md5("foo")),
+                    file("size.txt", 4, None),
+                    file("newer.txt", 3, None),
+                    file("remote.txt", 1, None),
+                ]
+            }),
+        );
+        server.data_dir(
+            "data://.my/sync/sub",
+            json!({ "files": [file("hash.txt", 1, md5("y"))] }),
+        );
+        let client = server.client().unwrap();
+
+        let changes = diff(local, &client.dir("data://.my/sync")).unwrap();
+        // A missing DataDir is treated as empty
+        let missing = diff(local, &client.dir("data://.my/missing")).unwrap();
+        assert_eq!(missing.only_local.len(), 5);
+        assert!(missing.only_remote.is_empty());
+
+        assert_eq!(changes.only_local, vec!["local.txt"]);
+        assert_eq!(changes.only_remote, vec!["remote.txt"]);
+        assert_eq!(changes.unchanged, 1);
+        let modified: Vec<_> = changes
+            .modified
+            .iter()
+            .map(|m| (m.path.as_str(), &m.reason))
+            .collect();
+        assert_eq!(modified[0].0, "newer.txt");
+        assert!(matches!(modified[0].1, ModifiedReason::Mtime { .. }));
+        assert_eq!(
+            modified[1..],
+            [
+                (
+                    "size.txt",
+                    &ModifiedReason::Size {
+                        local: 5,
+                        remote: 4
+                    }
+                ),
+                ("sub/hash.txt", &ModifiedReason::Hash),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_local_files_skips_symlinked_dirs() {
+        use std::os::unix::fs::symlink;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let local = tmp.path();
+        fs::create_dir_all(local.join("sub")).unwrap();
+        fs::write(local.join("sub/a.txt"), "a").unwrap();
+        symlink(local, local.join("sub/loop")).unwrap();
+        symlink(local.join("sub/a.txt"), local.join("link.txt")).unwrap();
+
+        let files = local_files(local, &Filter::new()).unwrap();
+        let names: Vec<_> = files.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["link.txt", "sub/a.txt"]);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_diff_filter() {
+        use crate::testing::FakeServer;
+        use serde_json::json;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let local = tmp.path();
+        fs::create_dir_all(local.join("target/debug")).unwrap();
+        fs::write(local.join(IGNORE_FILE), "# build output\ntarget/\n*.ckpt\n").unwrap();
+        fs::write(local.join("target/debug/model"), "").unwrap();
+        fs::write(local.join("a.ckpt"), "").unwrap();
+        fs::write(local.join("final.ckpt"), "").unwrap();
+        fs::write(local.join("model.bin"), "").unwrap();
+
+        let server = FakeServer::start().unwrap();
+        let file = |name: &str| json!({ "filename": name, "size": 1, "last_modified": "2015-03-14T08:00:00Z" });
+        server.data_dir(
+            "data://.my/sync",
+            json!({ "folders": [{ "name": "cache" }], "files": [file("b.ckpt"), file("other.txt")] }),
+        );
+        server.data_dir("data://.my/sync/cache", json!({ "files": [file("c.bin")] }));
+        let client = server.client().unwrap();
+        let dir = client.dir("data://.my/sync");
+
+        let changes = diff(local, &dir).unwrap();
+        let filter = Filter::new().exclude("cache/").include("final.ckpt");
+        let filtered = diff_with(local, &dir, &filter).unwrap();
+        let unfiltered = diff_with(local, &dir, &Filter::new().ignore_file(false)).unwrap();
+        let invalid = diff_with(local, &dir, &Filter::new().exclude("{a")).unwrap_err();
+
+        assert_eq!(changes.only_local, vec!["model.bin"]);
+        assert_eq!(changes.only_remote, vec!["cache/c.bin", "other.txt"]);
+        assert_eq!(filtered.only_local, vec!["final.ckpt", "model.bin"]);
+        assert_eq!(filtered.only_remote, vec!["other.txt"]);
+        assert_eq!(unfiltered.only_local.len(), 5);
+        assert!(invalid.to_string().contains("invalid pattern '{a'"));
+    }
+}
@@ -0,0 +1,340 @@
+//! Deserialize configuration structs from environment variables and secret files
+
+use crate::error::{err_msg, Error};
+
+use serde::de::value::{SeqDeserializer, StringDeserializer};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, Visitor};
+use std::fmt::{self, Display};
+use std::fs;
+
+/// Deserialize algorithm configuration from environment variables
+///
+/// Each field of `T` is read from the environment variable with the upper-cased field name
+/// (e.g. `model_path` from `MODEL_PATH`). If that variable isn't set, but a variable with a
+/// `_FILE` suffix is (e.g. `MODEL_PATH_FILE`), the field is read from the contents of that file
+/// with surrounding whitespace trimmed, which allows loading secrets mounted as files.
+///
+/// Values are parsed according to the field type: numbers and booleans are parsed from text,
+/// sequences are comma-separated, and `Option` fields are `None` when unset.
+/// Use `#[serde(default)]` for optional fields with defaults.
+///
+/// Errors name the offending environment variable. When used to load state for
+/// [`load_and_run`](fn.load_and_run.html), they are reported to callers as an `InitializationError`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use algorithmia::prelude::*;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     model_path: String,
+///     threads: Option<u32>,
+///     labels: Vec<String>,
+/// }
+///
+/// let config: Config = handler::config()?;
+/// # Ok::<(), Box<std::error::Error>>(())
+/// ```
+pub fn config<T: DeserializeOwned>() -> Result<T, Error> {
+    from_lookup(|name| std::env::var(name).ok())
+}
+
+fn from_lookup<T, F>(lookup: F) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+    F: Fn(&str) -> Option<String>,
+{
+    T::deserialize(EnvDeserializer { lookup: &lookup })
+        .map_err(|err| err_msg(format!("invalid algorithm configuration: {}", err)))
+}
+
+#[derive(Debug)]
+struct ConfigError(String);
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl de::Error for ConfigError {
+    fn custom<T: Display>(msg: T) -> Self {
+        ConfigError(msg.to_string())
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        ConfigError(format!(
+            "missing environment variable {}",
+            field.to_uppercase()
+        ))
+    }
+}
+
+struct EnvDeserializer<'a> {
+    lookup: &'a dyn Fn(&str) -> Option<String>,
+}
+
+impl<'de, 'a> de::Deserializer<'de> for EnvDeserializer<'a> {
+    type Error = ConfigError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, ConfigError> {
+        Err(ConfigError(
+            "configuration must be deserialized into a struct".into(),
+        ))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ConfigError> {
+        visitor.visit_map(EnvMap {
+            lookup: self.lookup,
+            fields: fields.iter(),
+            pending: None,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct EnvMap<'a> {
+    lookup: &'a dyn Fn(&str) -> Option<String>,
+    fields: std::slice::Iter<'static, &'static str>,
+    pending: Option<(String, String)>,
+}
+
+impl<'a> EnvMap<'a> {
+    fn lookup(&self, field: &str) -> Result<Option<(String, String)>, ConfigError> {
+        let var = field.to_uppercase();
+        if let Some(value) = (self.lookup)(&var) {
+            return Ok(Some((var, value)));
+        }
+
+        let file_var = format!("{}_FILE", var);
+        match (self.lookup)(&file_var) {
+            Some(path) => match fs::read_to_string(&path) {
+                Ok(contents) => Ok(Some((file_var, contents.trim().to_owned()))),
+                Err(err) => Err(ConfigError(format!(
+                    "{}: failed to read '{}': {}",
+                    file_var, path, err
+                ))),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+impl<'de, 'a> MapAccess<'de> for EnvMap<'a> {
+    type Error = ConfigError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, ConfigError> {
+        while let Some(field) = self.fields.next() {
+            // Unset fields are skipped so serde can apply defaults or report them as missing
+            if let Some(pending) = self.lookup(field)? {
+                self.pending = Some(pending);
+                return seed.deserialize(field.into_deserializer()).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, ConfigError> {
+        let (var, value) = self
+            .pending
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(EnvValue(value))
+            .map_err(|err| ConfigError(format!("{}: {}", var, err)))
+    }
+}
+
+/// Deserializer for a single environment variable value
+struct EnvValue(String);
+
+impl EnvValue {
+    fn invalid(&self, expected: &str) -> ConfigError {
+        ConfigError(format!("invalid value '{}', expected {}", self.0, expected))
+    }
+}
+
+macro_rules! parse_value {
+    ($($method:ident => $visit:ident($ty:ty),)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ConfigError> {
+            match self.0.trim().parse::<$ty>() {
+                Ok(val) => visitor.$visit(val),
+                Err(_) => Err(self.invalid(stringify!($ty))),
+            }
+        }
+    )*};
+}
+
+impl<'de> de::Deserializer<'de> for EnvValue {
+    type Error = ConfigError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ConfigError> {
+        visitor.visit_string(self.0)
+    }
+
+    parse_value! {
+        deserialize_bool => visit_bool(bool),
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ConfigError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ConfigError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ConfigError> {
+        let items = self
+            .0
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| EnvValue(item.to_owned()));
+        visitor.visit_seq(SeqDeserializer::new(items))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ConfigError> {
+        let variant: StringDeserializer<ConfigError> = self.0.into_deserializer();
+        visitor.visit_enum(variant)
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct tuple tuple_struct
+        map struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, ConfigError> for EnvValue {
+    type Deserializer = EnvValue;
+
+    fn into_deserializer(self) -> EnvValue {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Mode {
+        Fast,
+        Accurate,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Config {
+        model_path: String,
+        threads: Option<u32>,
+        labels: Vec<String>,
+        mode: Mode,
+        #[serde(default)]
+        verbose: bool,
+    }
+
+    fn load(vars: &[(&str, &str)]) -> Result<Config, Error> {
+        let vars: HashMap<_, _> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        from_lookup(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_config() {
+        let config = load(&[
+            ("MODEL_PATH", "data://.my/models/v1"),
+            ("THREADS", "4"),
+            ("LABELS", "cat, dog"),
+            ("MODE", "fast"),
+        ])
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                model_path: "data://.my/models/v1".into(),
+                threads: Some(4),
+                labels: vec!["cat".into(), "dog".into()],
+                mode: Mode::Fast,
+                verbose: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_config_errors() {
+        let err = load(&[("LABELS", ""), ("MODE", "accurate")]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid algorithm configuration: missing environment variable MODEL_PATH"
+        );
+
+        let err = load(&[
+            ("MODEL_PATH", "model"),
+            ("THREADS", "many"),
+            ("LABELS", ""),
+            ("MODE", "fast"),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid algorithm configuration: THREADS: invalid value 'many', expected u32"
+        );
+    }
+
+    #[test]
+    fn test_config_secret_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("secret");
+        fs::write(&path, "data://.my/models/secret\n").unwrap();
+        let config = load(&[
+            ("MODEL_PATH_FILE", path.to_str().unwrap()),
+            ("LABELS", "a"),
+            ("MODE", "accurate"),
+        ])
+        .unwrap();
+        assert_eq!(config.model_path, "data://.my/models/secret");
+        assert_eq!(config.mode, Mode::Accurate);
+    }
+}
@@ -1,64 +1,215 @@
 //! Error types
-use crate::client::header::{lossy_header, X_ERROR_MESSAGE};
-use backtrace::Backtrace;
+use crate::client::header::{lossy_header, X_ERROR_MESSAGE, X_REQUEST_ID};
 use reqwest::Response;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::error::Error as StdError;
 use std::fmt::Display;
+use std::io::Read;
+use std::ops::Deref;
+use std::sync::Arc;
 use std::{fmt, str};
 
 /// Default error type for errors originating in algorithm code
-const ALGORITHM_ERROR: &'static str = "AlgorithmError";
+const ALGORITHM_ERROR: &str = "AlgorithmError";
 
 macro_rules! bail {
-    ($e:expr) => {
+    ($e:expr) => {{
         return Err($crate::error::err_msg($e));
-    };
-    ($fmt:expr, $($arg:tt)+) => {
+    }};
+    ($fmt:expr, $($arg:tt)+) => {{
         return Err($crate::error::err_msg(format!($fmt, $($arg)+)));
-    };
+    }};
 }
 
+/// Error type for all fallible operations in this client
+///
+/// Every error has a message describing what failed. Errors caused by something other
+/// than this client's own validation also have an [`ErrorKind`](enum.ErrorKind.html),
+/// which is the error's `source()` unless the message is just the kind's own message.
 #[derive(Debug)]
 pub struct Error {
-    kind: ErrorKind,
+    kind: Option<ErrorKind>,
     ctx: String,
+    details: Option<Box<Details>>,
+}
+
+/// Optional details of an error, boxed to keep `Error` small
+#[derive(Debug, Default)]
+struct Details {
+    response: Option<ResponseInfo>,
+    data: Option<DataError>,
 }
 
+/// Details of the HTTP response that produced an error
 #[derive(Debug)]
-pub(crate) enum ErrorKind {
-    // Error from the Algorithmia API (may be from the algorithm)
-    Api(ApiError),
+struct ResponseInfo {
+    status: http::status::StatusCode,
+    request_id: Option<String>,
+    error_message: Option<String>,
+    body_snippet: Option<String>,
+}
 
-    // Http errors calling the API (optionally with message from server)
-    Http(reqwest::Error, Option<ApiError>),
+/// Details of a failed Data API request, returned by [`Error::data_error`](struct.Error.html#method.data_error)
+///
+/// Distinguishes storage failures from algorithm failures, and identifies the data URI
+/// and operation without parsing the error message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataError {
+    /// Data URI of the file or directory, e.g. `data://.my/foo/bar.txt`
+    pub uri: String,
+    /// Operation that failed
+    pub operation: DataOperation,
+    /// HTTP status of the response, if the API responded
+    pub status: Option<http::status::StatusCode>,
+    /// Error message from the `X-Error-Message` header or the response body, if any
+    pub message: Option<String>,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
 
-    // Error context generated in this client
-    Client,
+impl Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} '{}' failed", self.operation, self.uri)?;
+        if let Some(status) = self.status {
+            write!(f, " with {}", status)?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
 
-    // Error context generated in this client
-    Inner(Box<dyn StdError + Send + Sync + 'static>),
+/// Data API operation described by a `DataError`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataOperation {
+    /// Downloading a file
+    Download,
+    /// Uploading a file
+    Upload,
+    /// Deleting a file or directory
+    Delete,
+    /// Listing a directory
+    List,
+    /// Creating a directory
+    Create,
+    /// Checking the existence or type of a path
+    Inspect,
+    /// Updating the ACL of a directory
+    UpdateAcl,
+
+    /// Non-exhaustive for API stability if operations are added
+    #[doc(hidden)]
+    __Nonexhaustive,
 }
 
-impl Display for Error {
+impl DataOperation {
+    /// Lowercase name of the operation, e.g. `"download"`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DataOperation::Download => "download",
+            DataOperation::Upload => "upload",
+            DataOperation::Delete => "delete",
+            DataOperation::List => "list",
+            DataOperation::Create => "create",
+            DataOperation::Inspect => "inspect",
+            DataOperation::UpdateAcl => "update_acl",
+            DataOperation::__Nonexhaustive => "unknown",
+        }
+    }
+}
+
+impl Display for DataOperation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match &self.kind {
-            ErrorKind::Http(err, _) => match err.status() {
-                Some(status) => write!(f, "{}: {}", status, self.ctx),
-                None => write!(f, "{}", self.ctx),
-            },
-            _ => write!(f, "{}", self.ctx),
+        f.write_str(self.as_str())
+    }
+}
+
+/// Maximum number of error response bytes read when looking for an error message
+const MAX_ERROR_BODY_LEN: u64 = 64 * 1024;
+
+/// Maximum number of bytes of a non-JSON error response body retained in an `Error`
+const BODY_SNIPPET_LEN: usize = 1024;
+
+/// Underlying cause of an `Error`
+#[derive(Debug, thiserror::Error)]
+pub enum ErrorKind {
+    /// Error returned by the Algorithmia API (may be from the algorithm)
+    #[error(transparent)]
+    Api(ApiError),
+    /// HTTP request failed or the API responded with an error status
+    #[error(transparent)]
+    Http(HttpError),
+    /// I/O error, e.g. reading a local file or a response body
+    #[error(transparent)]
+    Io(std::io::Error),
+    /// JSON encoding or decoding error
+    #[error(transparent)]
+    Json(serde_json::Error),
+    /// Invalid URL
+    #[error(transparent)]
+    Url(url::ParseError),
+    /// Data URI with a connector scheme that is neither built in nor registered
+    #[error("unsupported data connector '{0}://'")]
+    UnsupportedConnector(String),
+    /// Error from another source, e.g. an invalid header or config file
+    #[error(transparent)]
+    Other(Box<dyn StdError + Send + Sync + 'static>),
+
+    /// Non-exhaustive for API stability if error kinds are added
+    #[doc(hidden)]
+    #[error("unknown error")]
+    __Nonexhaustive,
+}
+
+/// HTTP request failure, including any error message returned by the API
+#[derive(Debug, thiserror::Error)]
+#[error("{}", self.message())]
+pub struct HttpError {
+    #[source]
+    source: reqwest::Error,
+    api_error: Option<ApiError>,
+}
+
+impl HttpError {
+    /// The HTTP status code, if the API responded with an error status
+    pub fn status(&self) -> Option<http::status::StatusCode> {
+        self.source.status()
+    }
+
+    /// The error message returned by the API, if any
+    pub fn api_error(&self) -> Option<&ApiError> {
+        self.api_error.as_ref()
+    }
+
+    /// Whether the request timed out
+    pub fn is_timeout(&self) -> bool {
+        self.source.is_timeout()
+    }
+
+    fn message(&self) -> String {
+        match &self.api_error {
+            Some(api_error) => api_error.to_string(),
+            None => self.source.to_string(),
         }
     }
 }
 
 impl Error {
+    /// The underlying cause of this error
+    ///
+    /// Returns `None` for errors detected by this client itself
+    /// (e.g. invalid input or an unexpected response), which are described by the message.
+    pub fn kind(&self) -> Option<&ErrorKind> {
+        self.kind.as_ref()
+    }
+
     /// If the Algorithmia API returned an error, return the error response
     pub fn api_error(&self) -> Option<&ApiError> {
         match &self.kind {
-            ErrorKind::Api(e) => Some(e),
-            ErrorKind::Http(_, api_err) => api_err.as_ref(),
+            Some(ErrorKind::Api(e)) => Some(e),
+            Some(ErrorKind::Http(e)) => e.api_error(),
             _ => None,
         }
     }
@@ -66,9 +217,175 @@ impl Error {
     /// If an HTTP error occurred, return the relevant status code
     pub fn status(&self) -> Option<http::status::StatusCode> {
         match &self.kind {
-            ErrorKind::Http(e, _) => e.status(),
-            _ => None,
+            Some(ErrorKind::Http(e)) => e.status(),
+            _ => self.response().map(|r| r.status),
+        }
+    }
+
+    fn message(&self) -> String {
+        let ctx = match (&*self.ctx, &self.kind) {
+            ("", Some(kind)) => kind.to_string(),
+            (ctx, _) => ctx.to_owned(),
+        };
+        match self.status() {
+            Some(status) if !status.is_success() => format!("{}: {}", status, ctx),
+            _ => ctx,
+        }
+    }
+
+    /// Classify this error for logging and alerting
+    ///
+    /// Authentication failures are detected from a `401`/`403` status or an
+    /// authorization error code, regardless of how the error otherwise surfaced.
+    pub fn category(&self) -> ErrorCategory {
+        match self.code() {
+            Some(ErrorCode::AuthorizationError) | Some(ErrorCode::ThirdPartyCredentialError) => {
+                return ErrorCategory::Auth
+            }
+            Some(ErrorCode::InputError) => return ErrorCategory::InvalidInput,
+            _ => (),
+        }
+        match self.status().map(|s| s.as_u16()) {
+            Some(401) | Some(403) => return ErrorCategory::Auth,
+            Some(_) => return ErrorCategory::Api,
+            None => (),
+        }
+        match &self.kind {
+            Some(ErrorKind::Api(_)) => ErrorCategory::Api,
+            Some(ErrorKind::Http(e)) if e.source.is_serialization() => ErrorCategory::Serialization,
+            Some(ErrorKind::Http(_)) | Some(ErrorKind::Io(_)) => ErrorCategory::Network,
+            Some(ErrorKind::Json(_)) => ErrorCategory::Serialization,
+            _ => ErrorCategory::InvalidInput,
+        }
+    }
+
+    /// If the Algorithmia API returned an error, return its parsed error code
+    pub fn code(&self) -> Option<ErrorCode> {
+        self.api_error().and_then(ApiError::code)
+    }
+
+    /// The request ID of the API response that produced this error, if available
+    ///
+    /// Including this when contacting Algorithmia support helps locate the failed request.
+    pub fn request_id(&self) -> Option<&str> {
+        self.response()?.request_id.as_deref()
+    }
+
+    /// Attach details of the HTTP response that produced this error
+    pub(crate) fn with_response(
+        mut self,
+        status: http::status::StatusCode,
+        headers: &http::header::HeaderMap,
+    ) -> Error {
+        self.details.get_or_insert_with(Default::default).response = Some(ResponseInfo {
+            status,
+            request_id: headers.get(X_REQUEST_ID).map(lossy_header),
+            error_message: headers.get(X_ERROR_MESSAGE).map(lossy_header),
+            body_snippet: None,
+        });
+        self
+    }
+
+    fn response(&self) -> Option<&ResponseInfo> {
+        self.details.as_ref()?.response.as_ref()
+    }
+
+    /// If a Data API request failed, return which data URI and operation failed
+    ///
+    /// Returns `None` for algorithm and other non-storage errors.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::error::DataOperation;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// if let Err(err) = client.file(".my/foo/bar.txt").get() {
+    ///     if let Some(data_err) = err.data_error() {
+    ///         assert_eq!(data_err.operation, DataOperation::Download);
+    ///         println!("{} ({:?})", data_err.uri, data_err.message);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn data_error(&self) -> Option<&DataError> {
+        self.details.as_ref()?.data.as_ref()
+    }
+
+    /// Record the data URI and operation of a failed Data API request
+    pub(crate) fn with_data_error(mut self, operation: DataOperation, uri: String) -> Error {
+        let message = self
+            .response()
+            .and_then(|r| r.error_message.clone())
+            .or_else(|| self.api_error().map(|e| e.message.clone()));
+        let status = self.status();
+        self.details.get_or_insert_with(Default::default).data = Some(DataError {
+            uri,
+            operation,
+            status,
+            message,
+            _dummy: (),
+        });
+        self
+    }
+
+    /// The beginning of the response body when the API returned an error that wasn't JSON
+    ///
+    /// This is often the only clue when a proxy or misconfigured gateway
+    /// responds with an HTML error page instead of the Algorithmia API.
+    pub fn body_snippet(&self) -> Option<&str> {
+        self.response()?.body_snippet.as_deref()
+    }
+
+    /// Attach the beginning of an unparseable response body (requires `with_response` first)
+    pub(crate) fn with_body_snippet(mut self, body: &[u8]) -> Error {
+        if let Some(response) = self.details.as_mut().and_then(|d| d.response.as_mut()) {
+            let snippet = String::from_utf8_lossy(&body[..body.len().min(BODY_SNIPPET_LEN)]);
+            if !snippet.trim().is_empty() {
+                response.body_snippet = Some(snippet.into_owned());
+            }
         }
+        self
+    }
+}
+
+/// Cheaply cloneable, thread-safe handle to an `Error`
+///
+/// `Error` may wrap values that can't be cloned (e.g. the underlying HTTP error),
+/// so retry frameworks and concurrent tasks that need to store or redistribute
+/// an error can share it through this `Arc`-based wrapper instead.
+/// Derefs to `Error`, so all of its accessors remain available.
+#[derive(Debug, Clone)]
+pub struct SharedError(Arc<Error>);
+
+impl Error {
+    /// Convert into a cheaply cloneable `SharedError`
+    pub fn into_shared(self) -> SharedError {
+        SharedError(Arc::new(self))
+    }
+}
+
+impl From<Error> for SharedError {
+    fn from(err: Error) -> SharedError {
+        err.into_shared()
+    }
+}
+
+impl Deref for SharedError {
+    type Target = Error;
+    fn deref(&self) -> &Error {
+        &self.0
+    }
+}
+
+impl Display for SharedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&*self.0, f)
+    }
+}
+
+impl StdError for SharedError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
     }
 }
 
@@ -83,19 +400,68 @@ pub(crate) trait ResultExt<T> {
         F: FnOnce() -> D;
 }
 
-pub(crate) trait IntoErrorKind {
+pub(crate) trait IntoErrorKind: Sized {
     fn into_error_kind(self) -> ErrorKind;
+
+    fn into_error(self, ctx: String) -> Error {
+        Error {
+            kind: Some(self.into_error_kind()),
+            ctx,
+            details: None,
+        }
+    }
 }
 
 impl IntoErrorKind for Error {
     fn into_error_kind(self) -> ErrorKind {
-        self.kind
+        let ctx = self.ctx;
+        self.kind.unwrap_or_else(|| ErrorKind::Other(ctx.into()))
+    }
+
+    // Preserve the kind and response details when adding context
+    fn into_error(self, ctx: String) -> Error {
+        match self.kind {
+            // Retain the original message as the source of client errors
+            None => Error {
+                kind: Some(ErrorKind::Other(self.ctx.into())),
+                ctx,
+                details: self.details,
+            },
+            Some(_) => Error { ctx, ..self },
+        }
+    }
+}
+
+impl IntoErrorKind for ErrorKind {
+    fn into_error_kind(self) -> ErrorKind {
+        self
     }
 }
 
 impl IntoErrorKind for reqwest::Error {
     fn into_error_kind(self) -> ErrorKind {
-        ErrorKind::Http(self, None)
+        ErrorKind::Http(HttpError {
+            source: self,
+            api_error: None,
+        })
+    }
+}
+
+impl IntoErrorKind for std::io::Error {
+    fn into_error_kind(self) -> ErrorKind {
+        ErrorKind::Io(self)
+    }
+}
+
+impl IntoErrorKind for serde_json::Error {
+    fn into_error_kind(self) -> ErrorKind {
+        ErrorKind::Json(self)
+    }
+}
+
+impl IntoErrorKind for url::ParseError {
+    fn into_error_kind(self) -> ErrorKind {
+        ErrorKind::Url(self)
     }
 }
 
@@ -103,17 +469,20 @@ macro_rules! impl_into_error_kind {
     ($p:ty) => {
         impl IntoErrorKind for $p {
             fn into_error_kind(self) -> ErrorKind {
-                ErrorKind::Inner(Box::new(self))
+                ErrorKind::Other(Box::new(self))
             }
         }
     };
 }
 
-impl_into_error_kind!(std::io::Error);
-impl_into_error_kind!(serde_json::error::Error);
 impl_into_error_kind!(reqwest::header::InvalidHeaderValue);
-impl_into_error_kind!(url::ParseError);
+impl_into_error_kind!(http::Error);
+impl_into_error_kind!(toml::de::Error);
 impl_into_error_kind!(base64::DecodeError);
+#[cfg(feature = "arrow")]
+impl_into_error_kind!(parquet::errors::ParquetError);
+#[cfg(feature = "arrow")]
+impl_into_error_kind!(arrow_schema::ArrowError);
 
 impl<T, E> ResultExt<T> for Result<T, E>
 where
@@ -131,15 +500,12 @@ where
         D: Display + Send + Sync + 'static,
         F: FnOnce() -> D,
     {
-        self.map_err(|source| Error {
-            kind: source.into_error_kind(),
-            ctx: f().to_string(),
-        })
+        self.map_err(|source| source.into_error(f().to_string()))
     }
 }
 
 /// Error from the Algorithmia API (may be from the algorithm)
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct ApiError {
     /// Error message returned from the Algorithmia API
     pub message: String,
@@ -147,6 +513,8 @@ pub struct ApiError {
     pub error_type: Option<String>,
     /// Stacktrace of algorithm exception/panic
     pub stacktrace: Option<String>,
+    #[serde(skip)]
+    backtrace: LazyBacktrace,
 }
 
 impl Display for ApiError {
@@ -155,19 +523,133 @@ impl Display for ApiError {
             write!(f, "{}: ", error_type)?;
         }
         write!(f, "{}", self.message)?;
-        if let Some(trace) = &self.stacktrace {
+        if let Some(trace) = self.stacktrace.clone().or_else(|| self.backtrace.resolve()) {
             write!(f, "\n{}", trace)?;
         }
         Ok(())
     }
 }
 
+/// Backtrace captured when creating an `ApiError`, only symbolized when displayed
+///
+/// Capturing requires the `backtrace` feature and is enabled at runtime by setting
+/// `ALGORITHMIA_BACKTRACE` (or `RUST_BACKTRACE`) to a value other than `0`.
+#[derive(Clone, Default)]
+struct LazyBacktrace(#[cfg(feature = "backtrace")] Option<Arc<backtrace::Backtrace>>);
+
+impl LazyBacktrace {
+    #[cfg(feature = "backtrace")]
+    fn capture() -> LazyBacktrace {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // 0 = not yet checked, 1 = disabled, 2 = enabled
+        static ENABLED: AtomicUsize = AtomicUsize::new(0);
+        let enabled = match ENABLED.load(Ordering::Relaxed) {
+            0 => {
+                let enabled = match ["ALGORITHMIA_BACKTRACE", "RUST_BACKTRACE"]
+                    .iter()
+                    .find_map(|var| std::env::var(var).ok())
+                {
+                    Some(val) => val != "0",
+                    None => false,
+                };
+                ENABLED.store(if enabled { 2 } else { 1 }, Ordering::Relaxed);
+                enabled
+            }
+            state => state == 2,
+        };
+
+        if enabled {
+            LazyBacktrace(Some(Arc::new(backtrace::Backtrace::new_unresolved())))
+        } else {
+            LazyBacktrace(None)
+        }
+    }
+
+    #[cfg(not(feature = "backtrace"))]
+    fn capture() -> LazyBacktrace {
+        LazyBacktrace()
+    }
+
+    #[cfg(feature = "backtrace")]
+    fn resolve(&self) -> Option<String> {
+        self.0.as_ref().map(|bt| {
+            let mut bt = backtrace::Backtrace::clone(bt);
+            bt.resolve();
+            format!("{:?}", bt)
+        })
+    }
+
+    #[cfg(not(feature = "backtrace"))]
+    fn resolve(&self) -> Option<String> {
+        None
+    }
+}
+
+impl fmt::Debug for LazyBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        #[cfg(feature = "backtrace")]
+        {
+            if self.0.is_some() {
+                return f.write_str("Backtrace(..)");
+            }
+        }
+        f.write_str("None")
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message())
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        let kind = self.kind.as_ref()?;
+        if self.ctx.is_empty() {
+            // The message is the kind's message, so skip to its cause to avoid repeating it
+            kind.source()
+        } else {
+            Some(kind)
+        }
+    }
+}
+
+// Backtraces are diagnostic only and never affect equality
+impl PartialEq for LazyBacktrace {
+    fn eq(&self, _: &LazyBacktrace) -> bool {
+        true
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        kind.into_error(String::new())
+    }
+}
+
 impl From<ApiError> for Error {
     fn from(err: ApiError) -> Self {
-        Error {
-            kind: ErrorKind::Api(err),
-            ctx: String::new(), // TODO: should we allow this
-        }
+        ErrorKind::Api(err).into()
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        err.into_error(String::new())
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        err.into_error(String::new())
+    }
+}
+
+impl From<url::ParseError> for Error {
+    fn from(err: url::ParseError) -> Self {
+        err.into_error(String::new())
     }
 }
 
@@ -178,29 +660,33 @@ pub(crate) fn err_msg<D: Display>(msg: D) -> Error {
 impl From<String> for Error {
     fn from(msg: String) -> Self {
         Error {
-            kind: ErrorKind::Client,
+            kind: None,
             ctx: msg,
+            details: None,
         }
     }
 }
 
-impl StdError for ApiError {}
-
-impl StdError for Error {
-    fn source(&self) -> Option<&(dyn StdError + 'static)> {
-        match &self.kind {
-            ErrorKind::Api(e) => Some(e as &(dyn StdError + 'static)),
-            ErrorKind::Http(_, Some(e)) => Some(e as &(dyn StdError + 'static)),
-            ErrorKind::Http(e, None) => Some(e as &(dyn StdError + 'static)),
-            ErrorKind::Inner(e) => Some(e.as_ref() as &(dyn StdError + 'static)),
-            ErrorKind::Client => None,
-        }
+impl<'a> From<&'a str> for Error {
+    fn from(msg: &'a str) -> Self {
+        Error::from(msg.to_owned())
     }
 }
 
+impl StdError for ApiError {}
+
 impl ApiError {
+    /// Parse the `error_type` into an `ErrorCode`
+    pub fn code(&self) -> Option<ErrorCode> {
+        self.error_type
+            .as_ref()
+            .map(|t| ErrorCode::from(t.as_str()))
+    }
+
     /// Creates an ApiError - intended for creating ApiErrors from Rust algorithms
     ///
+    /// A backtrace is only captured when opted in (see [`backtrace`](#method.backtrace)).
+    ///
     /// ## Examples:
     ///
     /// ```
@@ -212,9 +698,19 @@ impl ApiError {
         ApiError {
             error_type: Some(error_type.into()),
             message: message.into(),
-            stacktrace: Some(format!("{:?}", Backtrace::new())),
+            stacktrace: None,
+            backtrace: LazyBacktrace::capture(),
         }
     }
+
+    /// Backtrace of where this error was created, if captured
+    ///
+    /// Capturing is opt-in since it is expensive: it requires the `backtrace` feature
+    /// and setting the `ALGORITHMIA_BACKTRACE` (or `RUST_BACKTRACE`) environment variable
+    /// to a value other than `0`. Symbols are only resolved when the backtrace is displayed.
+    pub fn backtrace(&self) -> Option<String> {
+        self.backtrace.resolve()
+    }
 }
 
 impl<S> From<S> for ApiError
@@ -225,11 +721,135 @@ where
         ApiError {
             error_type: Some(ALGORITHM_ERROR.into()),
             message: message.into(),
-            stacktrace: Some(format!("{:?}", Backtrace::new())),
+            stacktrace: None,
+            backtrace: LazyBacktrace::capture(),
+        }
+    }
+}
+
+/// Known error types returned by the Algorithmia API
+///
+/// Error types not known to this client are preserved as `Unknown`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// Error raised by algorithm code
+    AlgorithmError,
+    /// Algorithm input was invalid
+    InputError,
+    /// Algorithm does not support the requested operation
+    UnsupportedError,
+    /// Algorithm failed to initialize
+    InitializationError,
+    /// Algorithm exceeded its memory limit
+    OutOfMemoryError,
+    /// Algorithm exceeded its GPU memory limit
+    OutOfGpuMemoryError,
+    /// Error in the algorithm's language runtime
+    LanguageError,
+    /// Input or output exceeded the maximum size
+    TooLargeError,
+    /// Input or output could not be parsed
+    ParsingError,
+    /// Requested algorithm or data was not found
+    EntityNotFoundError,
+    /// Credentials for a third-party service (e.g. a data connector) were rejected
+    ThirdPartyCredentialError,
+    /// Caller is not authorized to perform the request
+    AuthorizationError,
+    /// Platform error unrelated to algorithm code
+    SystemError,
+    /// Error type not known to this client
+    Unknown(String),
+}
+
+impl ErrorCode {
+    /// The error type string as returned by the API
+    pub fn as_str(&self) -> &str {
+        match self {
+            ErrorCode::AlgorithmError => "AlgorithmError",
+            ErrorCode::InputError => "InputError",
+            ErrorCode::UnsupportedError => "UnsupportedError",
+            ErrorCode::InitializationError => "InitializationError",
+            ErrorCode::OutOfMemoryError => "OutOfMemoryError",
+            ErrorCode::OutOfGpuMemoryError => "OutOfGpuMemoryError",
+            ErrorCode::LanguageError => "LanguageError",
+            ErrorCode::TooLargeError => "TooLargeError",
+            ErrorCode::ParsingError => "ParsingError",
+            ErrorCode::EntityNotFoundError => "EntityNotFoundError",
+            ErrorCode::ThirdPartyCredentialError => "ThirdPartyCredentialError",
+            ErrorCode::AuthorizationError => "AuthorizationError",
+            ErrorCode::SystemError => "SystemError",
+            ErrorCode::Unknown(code) => code,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for ErrorCode {
+    fn from(code: &'a str) -> ErrorCode {
+        match code {
+            "AlgorithmError" => ErrorCode::AlgorithmError,
+            "InputError" => ErrorCode::InputError,
+            "UnsupportedError" => ErrorCode::UnsupportedError,
+            "InitializationError" => ErrorCode::InitializationError,
+            "OutOfMemoryError" => ErrorCode::OutOfMemoryError,
+            "OutOfGpuMemoryError" => ErrorCode::OutOfGpuMemoryError,
+            "LanguageError" => ErrorCode::LanguageError,
+            "TooLargeError" => ErrorCode::TooLargeError,
+            "ParsingError" => ErrorCode::ParsingError,
+            "EntityNotFoundError" => ErrorCode::EntityNotFoundError,
+            "ThirdPartyCredentialError" => ErrorCode::ThirdPartyCredentialError,
+            "AuthorizationError" => ErrorCode::AuthorizationError,
+            "SystemError" => ErrorCode::SystemError,
+            other => ErrorCode::Unknown(other.to_owned()),
+        }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Coarse classification of an `Error` for logging and alerting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// Failed to connect, timed out, or lost the connection
+    Network,
+    /// The Algorithmia API or algorithm returned an error
+    Api,
+    /// Failed to encode or decode JSON
+    Serialization,
+    /// Invalid input or configuration detected by this client
+    InvalidInput,
+    /// Missing, invalid, or insufficient credentials
+    Auth,
+
+    /// Non-exhaustive for API stability if categories are added
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl ErrorCategory {
+    /// Lowercase name of the category, e.g. `"network"`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCategory::Network => "network",
+            ErrorCategory::Api => "api",
+            ErrorCategory::Serialization => "serialization",
+            ErrorCategory::InvalidInput => "invalid_input",
+            ErrorCategory::Auth => "auth",
+            ErrorCategory::__Nonexhaustive => "unknown",
         }
     }
 }
 
+impl Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Struct for decoding Algorithmia API error responses
 #[derive(Debug, Deserialize)]
 #[doc(hidden)]
@@ -250,24 +870,133 @@ impl Error {
 
 pub(crate) fn process_http_response(mut resp: Response) -> Result<Response, Error> {
     let status = resp.status();
+    let headers = resp.headers().clone();
     if status.is_success() {
         Ok(resp)
     } else {
-        let api_err = match resp.json::<ApiErrorResponse>() {
+        // Error bodies are small, but guard against unbounded proxy responses
+        let mut body = Vec::new();
+        let _ = (&mut resp).take(MAX_ERROR_BODY_LEN).read_to_end(&mut body);
+
+        let mut body_snippet = None;
+        let api_err = match serde_json::from_slice::<ApiErrorResponse>(&body) {
             Ok(err_res) => Some(err_res.error),
-            Err(_) => match resp.headers().get(X_ERROR_MESSAGE).map(lossy_header) {
-                Some(message) => Some(ApiError {
-                    message,
-                    error_type: None,
-                    stacktrace: None,
-                }),
-                None => None,
-            },
+            Err(_) => {
+                body_snippet = Some(body);
+                resp.headers()
+                    .get(X_ERROR_MESSAGE)
+                    .map(lossy_header)
+                    .map(|message| ApiError {
+                        message,
+                        error_type: None,
+                        stacktrace: None,
+                        backtrace: LazyBacktrace::default(),
+                    })
+            }
         };
 
-        Response::error_for_status(resp).map_err(|e| Error {
-            kind: ErrorKind::Http(e, api_err),
-            ctx: String::new(),
+        Response::error_for_status(resp).map_err(|e| {
+            let err = ErrorKind::Http(HttpError {
+                source: e,
+                api_error: api_err,
+            })
+            .into_error(String::new())
+            .with_response(status, &headers);
+            match body_snippet {
+                Some(body) => err.with_body_snippet(&body),
+                None => err,
+            }
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_kind() {
+        let err =
+            Error::from(std::io::Error::other("disk")).into_error("failed to read file".into());
+        assert!(matches!(err.kind(), Some(ErrorKind::Io(_))));
+        assert_eq!(err.to_string(), "failed to read file");
+        assert_eq!(err.source().unwrap().to_string(), "disk");
+
+        let err = Error::from("invalid input");
+        assert!(err.kind().is_none());
+        assert!(err.source().is_none());
+
+        // Without context the message is the kind's, so it isn't repeated as the source
+        let err = Error::from(std::io::Error::other("disk"));
+        assert_eq!(err.to_string(), "disk");
+        assert!(err.source().is_none());
+        let err = Error::from(url::ParseError::EmptyHost);
+        assert_eq!(err.to_string(), "empty host");
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn test_api_error_backtrace() {
+        let err = ApiError {
+            backtrace: LazyBacktrace::default(),
+            ..ApiError::new("InputError", "missing field")
+        };
+        assert_eq!(err.to_string(), "InputError: missing field");
+        assert!(err.backtrace().is_none());
+
+        #[cfg(feature = "backtrace")]
+        {
+            let err = ApiError {
+                backtrace: LazyBacktrace(Some(Arc::new(backtrace::Backtrace::new_unresolved()))),
+                ..err
+            };
+            assert!(err
+                .backtrace()
+                .unwrap()
+                .contains("test_api_error_backtrace"));
+            assert_eq!(err, ApiError::new("InputError", "missing field"));
+        }
+    }
+
+    #[test]
+    fn test_error_category() {
+        let err = Error::from_json(r#"{"error": {"message": "boom"}}"#);
+        assert_eq!(err.category(), ErrorCategory::Api);
+        let err =
+            Error::from_json(r#"{"error": {"message": "no", "error_type": "AuthorizationError"}}"#);
+        assert_eq!(err.category(), ErrorCategory::Auth);
+        let err = Error::from_json("not json");
+        assert_eq!(err.category(), ErrorCategory::Serialization);
+        let err = Error::from(url::ParseError::EmptyHost);
+        assert_eq!(err.category(), ErrorCategory::InvalidInput);
+        assert_eq!(
+            Error::from("bad path").category(),
+            ErrorCategory::InvalidInput
+        );
+        let err = Error::from(std::io::Error::other("reset"));
+        assert_eq!(err.category().to_string(), "network");
+    }
+
+    #[test]
+    fn test_shared_error() {
+        fn assert_send_sync<T: Send + Sync + Clone>(_: &T) {}
+
+        let shared = Error::from_json(r#"{"error": {"message": "boom"}}"#).into_shared();
+        assert_send_sync(&shared);
+        let copy = shared.clone();
+        assert_eq!(copy.api_error().unwrap().message, "boom");
+        assert_eq!(copy.to_string(), shared.to_string());
+    }
+
+    #[test]
+    fn test_error_code() {
+        let err = Error::from_json(
+            r#"{"error": {"message": "bad creds", "error_type": "ThirdPartyCredentialError"}}"#,
+        );
+        assert_eq!(err.code(), Some(ErrorCode::ThirdPartyCredentialError));
+
+        let code = ErrorCode::from("BrandNewError");
+        assert_eq!(code, ErrorCode::Unknown("BrandNewError".into()));
+        assert_eq!(code.to_string(), "BrandNewError");
+    }
+}
@@ -3,17 +3,47 @@
 use base64;
 use serde_json;
 
+pub use crate::env_config::config;
+
+/// Generate the algorithm's `main` function to run the annotated handler [feature = "derive"]
+///
+/// The annotated function must take a single input and return a `Result`,
+/// with the same supported types as handlers passed to [`run`](fn.run.html).
+/// Unsupported signatures are reported as compile errors pointing at the offending type.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use algorithmia::handler::entrypoint;
+///
+/// #[entrypoint]
+/// fn apply(name: String) -> Result<String, String> {
+///     Ok(format!("Hello {}", name))
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use algorithmia_derive::entrypoint;
+
 use crate::algo::{AlgoData, ByteVec, TryFrom};
 use crate::error::{err_msg, ResultExt};
 use crate::prelude::AlgoIo;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
+use std::cell::RefCell;
 use std::error::Error;
 use std::fs::OpenOptions;
 use std::io::{self, BufRead, Write};
+#[cfg(feature = "handler-server")]
+use std::net::ToSocketAddrs;
 use std::process;
+#[cfg(feature = "handler-server")]
+use std::time::Instant;
+
+const ALGOOUT: &str = "/tmp/algoout";
 
-const ALGOOUT: &'static str = "/tmp/algoout";
+thread_local! {
+    static EXTRA_METADATA: RefCell<Map<String, Value>> = RefCell::new(Map::new());
+}
 
 #[derive(Deserialize)]
 struct Request {
@@ -35,6 +65,10 @@ struct AlgoFailure {
 #[derive(Serialize)]
 struct RunnerMetadata {
     content_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration: Option<f64>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
 }
 
 #[derive(Serialize)]
@@ -46,12 +80,23 @@ struct RunnerError {
 impl AlgoSuccess {
     fn new<S: Into<String>>(result: Value, content_type: S) -> AlgoSuccess {
         AlgoSuccess {
-            result: result,
+            result,
             metadata: RunnerMetadata {
                 content_type: content_type.into(),
+                duration: None,
+                extra: Map::new(),
             },
         }
     }
+
+    fn with_metadata(mut self, extra: Map<String, Value>) -> AlgoSuccess {
+        // Reserved fields are always set by the runner
+        self.metadata.extra = extra
+            .into_iter()
+            .filter(|(k, _)| k != "content_type" && k != "duration")
+            .collect();
+        self
+    }
 }
 
 impl AlgoFailure {
@@ -72,6 +117,15 @@ impl AlgoFailure {
             },
         }
     }
+
+    fn initialization(err: &dyn Error) -> AlgoFailure {
+        AlgoFailure {
+            error: RunnerError {
+                message: error_cause_chain(err),
+                error_type: "InitializationError",
+            },
+        }
+    }
 }
 
 /// Configures the Algorithmia-compatible FaaS handler
@@ -91,7 +145,7 @@ impl AlgoFailure {
 ///
 /// The simplest usage of this function is to just use a simple function that works entirely with `String`s:
 ///
-/// ```rust
+/// ```rust,no_run
 /// use algorithmia::prelude::*;
 ///
 /// fn apply(name: String) -> Result<String, String> {
@@ -107,14 +161,17 @@ impl AlgoFailure {
 ///
 /// To use your own custom types as input and output, simply implement `Deserialize` and `Serialize` respectively.
 ///
-/// ```rust
+/// ```rust,no_run
+/// # use algorithmia::prelude::*;
+/// # use serde::{Deserialize, Serialize};
+/// # use std::error::Error;
 /// #[derive(Deserialize)]
 /// struct Input { titles: Vec<String>, max: u32 }
 ///
 /// #[derive(Serialize)]
 /// struct Output { titles: Vec<String> }
 ///
-/// fn apply(input: Input) -> Result<Output, Box<Error>> {
+/// fn apply(input: Input) -> Result<Output, Box<dyn Error>> {
 ///     unimplemented!();
 /// }
 ///
@@ -133,14 +190,17 @@ impl AlgoFailure {
 /// - `algo::ByteVec` if working with binary output
 ///
 /// **Valid error types (`Err` variant of return value)**
-/// Anything with an conversion to `Box<Error>`. This includes `String` and basically any type that implements the `Error` trait.
+/// Anything with an conversion to `Box<dyn Error>`. This includes `String` and basically any type that implements the `Error` trait.
 ///
 /// ## Preloading and Maintaining State (Advanced Usage)
 ///
 /// If your algorithm has a preload step that doesn't vary with user input (e.g. loading a model),
 /// you can perform that prior to calling `handler::run` and then passing in a reference to that stay via a capturing closure:
 ///
-/// ```rust
+/// ```rust,no_run
+/// # use algorithmia::prelude::*;
+/// # use serde::{Deserialize, Serialize};
+/// # fn load_model() -> Vec<u8> { Vec::new() }
 /// #[derive(Deserialize)]
 /// struct Input { titles: Vec<String>, max: u32 }
 ///
@@ -163,8 +223,8 @@ where
     F: FnMut(IN) -> Result<OUT, E>,
     IN: TryFrom<AlgoIo, Error = E2>,
     OUT: Into<AlgoIo>,
-    E: Into<Box<Error>>,
-    E2: Into<Box<Error>>,
+    E: Into<Box<dyn Error>>,
+    E2: Into<Box<dyn Error>>,
 {
     println!("PIPE_INIT_COMPLETE");
     flush_std_pipes();
@@ -173,43 +233,329 @@ where
     for line in stdin.lock().lines() {
         let output_json = match line {
             Ok(json_line) => {
-                let output = build_input(json_line).and_then(|input| match IN::try_from(input) {
-                    Ok(algo_io) => match apply(algo_io) {
-                        Ok(out) => Ok(out.into()),
-                        Err(err) => Err(err.into()),
-                    },
-                    Err(err) => Err(err.into()),
-                });
+                let output = build_input(json_line).and_then(|input| call(&mut apply, input));
                 flush_std_pipes();
                 serialize_output(output)
             }
             Err(_) => {
                 let err = line.context("failed to read stdin").unwrap_err();
-                serde_json::to_string(&AlgoFailure::system(&err as &dyn Error)).expect(&format!(
-                    "Failed to read stdin and failed to encode the error: {}",
-                    err
-                ))
+                serde_json::to_string(&AlgoFailure::system(&err as &dyn Error)).unwrap_or_else(
+                    |_| {
+                        panic!(
+                            "Failed to read stdin and failed to encode the error: {}",
+                            err
+                        )
+                    },
+                )
             }
         };
         algoout(&output_json);
     }
 }
 
-pub fn load_and_run<F, LOAD, IN, OUT, STATE, E, E2, E3>(load: LOAD, mut apply: F) -> Result<(), Box<Error>>
+/// Configures the FaaS handler with state that is loaded once before handling requests
+///
+/// `load` is called once (e.g. to load a model or read [`config`](fn.config.html)),
+/// and its result is passed to `apply` along with each request input.
+/// If `load` fails, every request is answered with an `InitializationError`
+/// describing the failure, and the error is returned once STDIN reaches EOF.
+pub fn load_and_run<F, LOAD, IN, OUT, STATE, E, E2, E3>(
+    load: LOAD,
+    mut apply: F,
+) -> Result<(), Box<dyn Error>>
 where
     F: FnMut(IN, &mut STATE) -> Result<OUT, E>,
     LOAD: FnOnce() -> Result<STATE, E3>,
     IN: TryFrom<AlgoIo, Error = E2>,
     OUT: Into<AlgoIo>,
-    E: Into<Box<Error>>,
-    E2: Into<Box<Error>>,
-    E3: Into<Box<Error>>,
+    E: Into<Box<dyn Error>>,
+    E2: Into<Box<dyn Error>>,
+    E3: Into<Box<dyn Error>>,
 {
-    let mut state = load().map_err(|err| err.into())?;
+    let mut state = match load() {
+        Ok(state) => state,
+        Err(err) => {
+            let err = err.into();
+            fail_initialization(&*err);
+            return Err(err);
+        }
+    };
     run(|input| apply(input, &mut state));
     Ok(())
 }
 
+fn fail_initialization(err: &dyn Error) {
+    println!("PIPE_INIT_COMPLETE");
+    flush_std_pipes();
+
+    let output_json =
+        serde_json::to_string(&AlgoFailure::initialization(err)).expect("Failed to encode JSON");
+    let stdin = io::stdin();
+    for _ in stdin.lock().lines() {
+        algoout(&output_json);
+    }
+}
+
+/// Add a field to the `metadata` of the response for the request currently being handled
+///
+/// This lets algorithms report details such as a model version or cache hits to callers
+/// alongside the output. Metadata is only included in successful responses, and the
+/// reserved `content_type` and `duration` fields cannot be overridden.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use algorithmia::prelude::*;
+///
+/// fn apply(name: String) -> Result<String, String> {
+///     handler::add_metadata("model_version", "1.2.0");
+///     handler::add_metadata("cache_hit", false);
+///     Ok(format!("Hello {}", name))
+/// }
+///
+/// fn main() {
+///     handler::run(apply)
+/// }
+/// ```
+pub fn add_metadata<K: Into<String>, V: Into<Value>>(key: K, value: V) {
+    EXTRA_METADATA.with(|m| m.borrow_mut().insert(key.into(), value.into()));
+}
+
+fn take_metadata() -> Map<String, Value> {
+    EXTRA_METADATA.with(|m| std::mem::take(&mut *m.borrow_mut()))
+}
+
+type BoxedHandler<'a> = Box<dyn FnMut(AlgoIo) -> Result<AlgoIo, Box<dyn Error>> + 'a>;
+
+/// Dispatches each request to one of several handlers based on a field of the JSON input
+///
+/// This allows a single algorithm to expose multiple operations. The input must be
+/// a JSON object containing the routing field (e.g. `{"method": "predict", ...}`).
+/// The routing field is removed, and the remaining object is converted to the input type
+/// of the matching handler, which accepts the same types as handlers passed to [`run`](fn.run.html).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use algorithmia::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize)]
+/// struct TrainInput { samples: Vec<f64> }
+///
+/// #[derive(Deserialize)]
+/// struct PredictInput { value: f64 }
+///
+/// fn train(input: TrainInput) -> Result<String, String> {
+///     unimplemented!()
+/// }
+///
+/// fn predict(input: PredictInput) -> Result<f64, String> {
+///     unimplemented!()
+/// }
+///
+/// fn main() {
+///     // e.g. {"method": "predict", "value": 4.2}
+///     handler::Router::new("method")
+///         .route("train", train)
+///         .route("predict", predict)
+///         .run()
+/// }
+/// ```
+pub struct Router<'a> {
+    field: String,
+    routes: Vec<(String, BoxedHandler<'a>)>,
+}
+
+impl<'a> Router<'a> {
+    /// Create a router that dispatches on the value of `field`
+    pub fn new<S: Into<String>>(field: S) -> Router<'a> {
+        Router {
+            field: field.into(),
+            routes: Vec::new(),
+        }
+    }
+
+    /// Add a handler for requests where the routing field equals `name`
+    ///
+    /// Adding a handler for an existing name replaces the previous handler.
+    pub fn route<F, IN, OUT, E, E2>(mut self, name: &str, mut apply: F) -> Router<'a>
+    where
+        F: FnMut(IN) -> Result<OUT, E> + 'a,
+        IN: TryFrom<AlgoIo, Error = E2>,
+        OUT: Into<AlgoIo>,
+        E: Into<Box<dyn Error>>,
+        E2: Into<Box<dyn Error>>,
+    {
+        self.routes.retain(|(n, _)| n != name);
+        self.routes.push((
+            name.to_owned(),
+            Box::new(move |input| call(&mut apply, input)),
+        ));
+        self
+    }
+
+    /// Dispatch a single request to the matching handler
+    pub fn handle(&mut self, input: AlgoIo) -> Result<AlgoIo, Box<dyn Error>> {
+        let mut object = match input.data {
+            AlgoData::Json(Value::Object(object)) => object,
+            _ => {
+                return Err(err_msg(format!(
+                    "Input must be a JSON object with a '{}' field",
+                    self.field
+                ))
+                .into())
+            }
+        };
+        let name = match object.remove(&self.field) {
+            Some(Value::String(name)) => name,
+            _ => {
+                return Err(
+                    err_msg(format!("Input must have a string '{}' field", self.field)).into(),
+                )
+            }
+        };
+        match self.routes.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, handler)) => handler(AlgoIo::from(Value::Object(object))),
+            None => {
+                let names: Vec<_> = self.routes.iter().map(|(n, _)| n.as_str()).collect();
+                Err(err_msg(format!(
+                    "Unknown {} '{}', expected one of: {}",
+                    self.field,
+                    name,
+                    names.join(", ")
+                ))
+                .into())
+            }
+        }
+    }
+
+    /// Run the router as the algorithm handler (see [`run`](fn.run.html))
+    pub fn run(mut self) {
+        run(move |input: AlgoIo| self.handle(input))
+    }
+}
+
+/// Serve a handler over HTTP for local testing, mimicking the Algorithmia API [feature = "handler-server"]
+///
+/// This accepts the same handler functions as [`run`](fn.run.html), but instead of
+/// communicating with the platform, it listens on `addr` and responds to algorithm calls
+/// with the same request and response envelope as the Algorithmia API.
+/// This allows integration testing callers against a locally running algorithm
+/// by pointing a client at the local address. Requests are handled one at a time
+/// and the request path is ignored. This function blocks while serving.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use algorithmia::prelude::*;
+///
+/// fn apply(name: String) -> Result<String, String> {
+///     Ok(format!("Hello {}", name))
+/// }
+///
+/// fn main() {
+///     // Call with Algorithmia::client_with_url("", "http://127.0.0.1:9090")
+///     handler::serve("127.0.0.1:9090", apply).unwrap();
+/// }
+/// ```
+#[cfg(feature = "handler-server")]
+pub fn serve<A, F, IN, OUT, E, E2>(addr: A, apply: F) -> Result<(), Box<dyn Error + Send + Sync>>
+where
+    A: ToSocketAddrs,
+    F: FnMut(IN) -> Result<OUT, E>,
+    IN: TryFrom<AlgoIo, Error = E2>,
+    OUT: Into<AlgoIo>,
+    E: Into<Box<dyn Error>>,
+    E2: Into<Box<dyn Error>>,
+{
+    let server = tiny_http::Server::http(addr)?;
+    serve_with(&server, apply);
+    Ok(())
+}
+
+#[cfg(feature = "handler-server")]
+fn serve_with<F, IN, OUT, E, E2>(server: &tiny_http::Server, mut apply: F)
+where
+    F: FnMut(IN) -> Result<OUT, E>,
+    IN: TryFrom<AlgoIo, Error = E2>,
+    OUT: Into<AlgoIo>,
+    E: Into<Box<dyn Error>>,
+    E2: Into<Box<dyn Error>>,
+{
+    for mut request in server.incoming_requests() {
+        let start = Instant::now();
+        let content_type = request
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv("Content-Type"))
+            .map(|h| h.value.as_str().to_owned())
+            .unwrap_or_default();
+        let mut body = Vec::new();
+        let output = request
+            .as_reader()
+            .read_to_end(&mut body)
+            .context("Error reading request body")
+            .map_err(Into::into)
+            .and_then(|_| build_http_input(&content_type, body))
+            .and_then(|input| call(&mut apply, input));
+
+        let extra = take_metadata();
+        let (status, json) = match output {
+            Ok(output) => {
+                let mut success = AlgoSuccess::from(output).with_metadata(extra);
+                success.metadata.duration = Some(start.elapsed().as_secs_f64());
+                (200, serde_json::to_string(&success))
+            }
+            Err(err) => (400, serde_json::to_string(&AlgoFailure::new(&*err))),
+        };
+        let response = tiny_http::Response::from_string(json.expect("Failed to encode JSON"))
+            .with_status_code(status)
+            .with_header(
+                "Content-Type: application/json"
+                    .parse::<tiny_http::Header>()
+                    .unwrap(),
+            );
+        let _ = request.respond(response);
+    }
+}
+
+#[cfg(feature = "handler-server")]
+fn build_http_input(content_type: &str, body: Vec<u8>) -> Result<AlgoIo, Box<dyn Error>> {
+    let mime = content_type.split(';').next().unwrap_or("").trim();
+    let input = match mime {
+        "application/json" => {
+            let json: Value =
+                serde_json::from_slice(&body).context("Error decoding request input as JSON")?;
+            AlgoIo::from(json)
+        }
+        "application/octet-stream" => AlgoIo::from(ByteVec::from(body)),
+        _ => {
+            let text = String::from_utf8(body)
+                .map_err(|err| err_msg(format!("Error decoding request input as text: {}", err)))?;
+            AlgoIo::from(text)
+        }
+    };
+    Ok(input)
+}
+
+fn call<F, IN, OUT, E, E2>(apply: &mut F, input: AlgoIo) -> Result<AlgoIo, Box<dyn Error>>
+where
+    F: FnMut(IN) -> Result<OUT, E>,
+    IN: TryFrom<AlgoIo, Error = E2>,
+    OUT: Into<AlgoIo>,
+    E: Into<Box<dyn Error>>,
+    E2: Into<Box<dyn Error>>,
+{
+    match IN::try_from(input) {
+        Ok(algo_io) => match apply(algo_io) {
+            Ok(out) => Ok(out.into()),
+            Err(err) => Err(err.into()),
+        },
+        Err(err) => Err(err.into()),
+    }
+}
+
 impl From<AlgoIo> for AlgoSuccess {
     fn from(output: AlgoIo) -> AlgoSuccess {
         match output.data {
@@ -234,8 +580,9 @@ fn error_cause_chain(err: &dyn Error) -> String {
 }
 
 fn serialize_output(output: Result<AlgoIo, Box<dyn Error>>) -> String {
+    let extra = take_metadata();
     let json_result = match output {
-        Ok(output) => serde_json::to_string(&AlgoSuccess::from(output)),
+        Ok(output) => serde_json::to_string(&AlgoSuccess::from(output).with_metadata(extra)),
         Err(err) => serde_json::to_string(&AlgoFailure::new(&*err as &dyn Error)),
     };
 
@@ -277,3 +624,108 @@ fn build_input(stdin: String) -> Result<AlgoIo, Box<dyn Error>> {
     };
     Ok(input)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "handler-server")]
+    use crate::Algorithmia;
+    #[cfg(feature = "handler-server")]
+    use std::thread;
+
+    fn hello(name: String) -> Result<String, String> {
+        match name.as_str() {
+            "" => Err("name is required".into()),
+            _ => Ok(format!("Hello {}", name)),
+        }
+    }
+
+    #[test]
+    fn test_router() {
+        #[derive(Deserialize)]
+        struct Add {
+            a: i32,
+            b: i32,
+        }
+
+        let mut router = Router::new("method")
+            .route("hello", |input: Value| {
+                hello(input["name"].as_str().unwrap().into())
+            })
+            .route("add", |input: Add| Ok::<_, String>(input.a + input.b));
+
+        let output = router
+            .handle(AlgoIo::from(
+                serde_json::json!({"method": "add", "a": 2, "b": 3}),
+            ))
+            .unwrap();
+        assert_eq!(output.decode::<i32>().unwrap(), 5);
+
+        let output = router
+            .handle(AlgoIo::from(
+                serde_json::json!({"method": "hello", "name": "Rust"}),
+            ))
+            .unwrap();
+        assert_eq!(output.as_string(), Some("Hello Rust"));
+
+        let err = router
+            .handle(AlgoIo::from(serde_json::json!({"method": "train"})))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Unknown method 'train', expected one of: hello, add"
+        );
+        assert!(router.handle(AlgoIo::from("add")).is_err());
+    }
+
+    #[cfg(feature = "derive")]
+    mod entry {
+        use super::*;
+
+        #[entrypoint]
+        fn shout(input: String) -> Result<String, Box<dyn Error>> {
+            Ok(input.to_uppercase())
+        }
+
+        #[test]
+        fn test_entrypoint() {
+            let _: fn() = main;
+            assert_eq!(shout("narf".into()).unwrap(), "NARF");
+        }
+    }
+
+    #[test]
+    fn test_add_metadata() {
+        add_metadata("model_version", "1.2.0");
+        add_metadata("duration", 42);
+        let json = serialize_output(Ok(AlgoIo::from("ok")));
+        let output: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            output["metadata"],
+            serde_json::json!({"content_type": "json", "model_version": "1.2.0"})
+        );
+
+        add_metadata("cache_hit", true);
+        let json = serialize_output(Err("failed".into()));
+        assert!(!json.contains("cache_hit"));
+        assert!(take_metadata().is_empty());
+    }
+
+    #[cfg(feature = "handler-server")]
+    #[test]
+    fn test_serve_locally() {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        thread::spawn(move || serve_with(&server, hello));
+
+        let client = Algorithmia::client_with_url("", &format!("http://{}", addr)).unwrap();
+        let algo = client.algo("local/Hello");
+
+        let res = algo.pipe("Rust").unwrap();
+        assert_eq!(res.as_string(), Some("Hello Rust"));
+
+        let err = algo.pipe("").err().unwrap();
+        assert_eq!(err.api_error().unwrap().message, "name is required");
+    }
+}
@@ -0,0 +1,80 @@
+//! Health checks for the Algorithmia API
+
+use crate::client::HttpClient;
+use crate::error::{process_http_response, Error, ResultExt};
+
+use reqwest::StatusCode;
+use std::time::{Duration, Instant};
+
+/// Cheap authenticated resource used to check connectivity and credentials
+static PING_PATH: &str = "v1/connector/data/.my";
+
+/// Result of [`Algorithmia::ping`](struct.Algorithmia.html#method.ping)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PingStatus {
+    /// Round trip time of the request
+    pub latency: Duration,
+    /// Whether the API accepted the configured credentials
+    ///
+    /// Also `false` when the client has no API key.
+    pub key_valid: bool,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+pub(crate) fn ping(client: &HttpClient) -> Result<PingStatus, Error> {
+    let url = client
+        .data_base_url()
+        .join(PING_PATH)
+        .context("invalid ping URL")?;
+    let start = Instant::now();
+    let res = client
+        .send(client.head(url))
+        .context("request error pinging the Algorithmia API")?;
+    let latency = start.elapsed();
+
+    let key_valid = match res.status() {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => false,
+        _ => {
+            process_http_response(res).context("response error pinging the Algorithmia API")?;
+            true
+        }
+    };
+    Ok(PingStatus {
+        latency,
+        key_valid,
+        _dummy: (),
+    })
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use crate::error::ErrorCategory;
+    use crate::testing::{FakeResponse, FakeServer};
+
+    #[test]
+    fn test_ping() {
+        let server = FakeServer::start().unwrap();
+        let client = server.client().unwrap();
+
+        server.route("HEAD", "/v1/connector/data/.my", FakeResponse::status(200));
+        assert!(client.ping().unwrap().key_valid);
+
+        server.route("HEAD", "/v1/connector/data/.my", FakeResponse::status(401));
+        assert!(!client.ping().unwrap().key_valid);
+
+        server.route("HEAD", "/v1/connector/data/.my", FakeResponse::status(500));
+        let err = client.ping().unwrap_err();
+        assert_eq!(err.category(), ErrorCategory::Api);
+
+        // Nothing listens on a port that was just released
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let client =
+            crate::Algorithmia::client_with_url("", format!("http://{}", addr).as_str()).unwrap();
+        let err = client.ping().unwrap_err();
+        assert_eq!(err.category(), ErrorCategory::Network);
+    }
+}
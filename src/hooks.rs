@@ -0,0 +1,110 @@
+//! Instrumentation hooks called around each HTTP request
+
+use http::{Method, StatusCode};
+use reqwest::Url;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A request about to be sent, passed to hooks registered with
+/// [`ClientBuilder::on_request`](struct.ClientBuilder.html#method.on_request)
+#[derive(Debug, Clone)]
+pub struct RequestEvent {
+    /// HTTP method of the request
+    pub method: Method,
+    /// URL of the request, including any query parameters
+    pub url: Url,
+    /// Attempt number, starting at 1 and increasing when the client retries the request
+    pub attempt: u32,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+/// The outcome of a request, passed to hooks registered with
+/// [`ClientBuilder::on_response`](struct.ClientBuilder.html#method.on_response)
+#[derive(Debug, Clone)]
+pub struct ResponseEvent {
+    /// HTTP method of the request
+    pub method: Method,
+    /// URL of the request, including any query parameters
+    pub url: Url,
+    /// Attempt number, starting at 1 and increasing when the client retries the request
+    pub attempt: u32,
+    /// Status of the response, or `None` if no response was received (e.g. connection errors)
+    pub status: Option<StatusCode>,
+    /// Wall time from sending the request until the response headers were received
+    ///
+    /// Reading the response body, e.g. of a file download, is not included.
+    pub elapsed: Duration,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+type RequestHook = dyn Fn(&RequestEvent) + Send + Sync;
+type ResponseHook = dyn Fn(&ResponseEvent) + Send + Sync;
+
+/// Hooks registered on a client, shared by its clones
+#[derive(Clone, Default)]
+pub(crate) struct Hooks {
+    on_request: Vec<Arc<RequestHook>>,
+    on_response: Vec<Arc<ResponseHook>>,
+}
+
+impl Hooks {
+    pub(crate) fn add_request_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&RequestEvent) + Send + Sync + 'static,
+    {
+        self.on_request.push(Arc::new(hook));
+    }
+
+    pub(crate) fn add_response_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&ResponseEvent) + Send + Sync + 'static,
+    {
+        self.on_response.push(Arc::new(hook));
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.on_request.is_empty() && self.on_response.is_empty()
+    }
+
+    pub(crate) fn request(&self, method: &Method, url: &Url, attempt: u32) {
+        if self.on_request.is_empty() {
+            return;
+        }
+        let event = RequestEvent {
+            method: method.clone(),
+            url: url.clone(),
+            attempt,
+            _dummy: (),
+        };
+        for hook in &self.on_request {
+            hook(&event);
+        }
+    }
+
+    pub(crate) fn response(
+        &self,
+        method: Method,
+        url: Url,
+        attempt: u32,
+        status: Option<StatusCode>,
+        elapsed: Duration,
+    ) {
+        if self.on_response.is_empty() {
+            return;
+        }
+        let event = ResponseEvent {
+            method,
+            url,
+            attempt,
+            status,
+            elapsed,
+            _dummy: (),
+        };
+        for hook in &self.on_response {
+            hook(&event);
+        }
+    }
+}
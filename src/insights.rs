@@ -0,0 +1,234 @@
+//! Reporting algorithm insights (metrics) to Algorithmia
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use algorithmia::Algorithmia;
+//! use algorithmia::insights::InsightsReporter;
+//!
+//! let client = Algorithmia::client("111112222233333444445555566")?;
+//! let reporter = InsightsReporter::new(&client);
+//! reporter.report("confidence", 0.87);
+//! // Buffered insights are flushed in the background and when the reporter is dropped
+//! # Ok::<(), Box<std::error::Error>>(())
+//! ```
+
+use crate::client::HttpClient;
+use crate::error::{process_http_response, Error, ResultExt};
+use crate::Algorithmia;
+
+use serde::Serialize;
+use serde_json::Value;
+use std::mem;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+static INSIGHTS_PATH: &str = "v1/insights";
+
+/// A single insight (named metric value)
+#[derive(Debug, Clone, Serialize)]
+pub struct Insight {
+    /// Name of the insight
+    #[serde(rename = "insight_key")]
+    pub key: String,
+    /// Value of the insight
+    #[serde(rename = "insight_value")]
+    pub value: Value,
+}
+
+impl Insight {
+    /// Create an insight from any serializable value
+    pub fn new<K: Into<String>, V: Serialize>(key: K, value: V) -> Insight {
+        Insight {
+            key: key.into(),
+            value: serde_json::to_value(value).unwrap_or(Value::Null),
+        }
+    }
+}
+
+impl Algorithmia {
+    /// Publish insights immediately in a single request
+    ///
+    /// For reporting insights on every algorithm call, prefer an
+    /// [`InsightsReporter`](insights/struct.InsightsReporter.html) which batches requests.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use algorithmia::Algorithmia;
+    /// # use algorithmia::insights::Insight;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// client.report_insights(&[Insight::new("model_version", "2.1"), Insight::new("latency_ms", 42)])?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn report_insights(&self, insights: &[Insight]) -> Result<(), Error> {
+        send_insights(&self.http_client, insights)
+    }
+}
+
+fn send_insights(client: &HttpClient, insights: &[Insight]) -> Result<(), Error> {
+    let url = client
+        .base_url
+        .join(INSIGHTS_PATH)
+        .context("invalid insights URL")?;
+    client
+        .send(client.post(url).json(insights))
+        .context("request error reporting insights")
+        .and_then(process_http_response)
+        .context("response error reporting insights")?;
+    Ok(())
+}
+
+enum Message {
+    Report(Insight),
+    Flush(Sender<Result<(), Error>>),
+}
+
+/// Buffers insights and publishes them in batches from a background thread
+///
+/// Buffered insights are flushed whenever `max_batch_size` insights are buffered,
+/// every `flush_interval`, on an explicit call to `flush`, and when the reporter is dropped.
+/// Errors from background flushes are retained and can be retrieved with `take_errors`.
+pub struct InsightsReporter {
+    sender: Option<Sender<Message>>,
+    worker: Option<JoinHandle<()>>,
+    errors: Arc<Mutex<Vec<Error>>>,
+}
+
+impl InsightsReporter {
+    /// Default number of buffered insights that triggers a flush
+    pub const DEFAULT_MAX_BATCH_SIZE: usize = 100;
+    /// Default maximum time an insight is buffered before being flushed
+    pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+    /// Create a reporter with the default batch size and flush interval
+    pub fn new(client: &Algorithmia) -> InsightsReporter {
+        InsightsReporter::with_config(
+            client,
+            Self::DEFAULT_MAX_BATCH_SIZE,
+            Self::DEFAULT_FLUSH_INTERVAL,
+        )
+    }
+
+    /// Create a reporter with a custom batch size and flush interval
+    pub fn with_config(
+        client: &Algorithmia,
+        max_batch_size: usize,
+        flush_interval: Duration,
+    ) -> InsightsReporter {
+        let (sender, receiver) = mpsc::channel();
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let http_client = client.http_client.clone();
+        let worker_errors = errors.clone();
+        let max_batch_size = max_batch_size.max(1);
+
+        let worker = thread::spawn(move || {
+            let mut buffer = Vec::with_capacity(max_batch_size);
+            let mut next_flush = Instant::now() + flush_interval;
+            let flush = |buffer: &mut Vec<Insight>| -> Result<(), Error> {
+                match buffer.len() {
+                    0 => Ok(()),
+                    _ => send_insights(&http_client, &mem::take(buffer)),
+                }
+            };
+            let record = |res: Result<(), Error>| {
+                if let Err(err) = res {
+                    worker_errors.lock().unwrap().push(err);
+                }
+            };
+
+            loop {
+                let timeout = next_flush.saturating_duration_since(Instant::now());
+                match receiver.recv_timeout(timeout) {
+                    Ok(Message::Report(insight)) => {
+                        buffer.push(insight);
+                        if buffer.len() >= max_batch_size {
+                            record(flush(&mut buffer));
+                        }
+                    }
+                    Ok(Message::Flush(ack)) => {
+                        // If the caller stopped waiting, report the error in the background
+                        if let Err(mpsc::SendError(res)) = ack.send(flush(&mut buffer)) {
+                            record(res);
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        record(flush(&mut buffer));
+                        next_flush = Instant::now() + flush_interval;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => {
+                        record(flush(&mut buffer));
+                        break;
+                    }
+                }
+            }
+        });
+
+        InsightsReporter {
+            sender: Some(sender),
+            worker: Some(worker),
+            errors,
+        }
+    }
+
+    /// Buffer an insight for publishing; never blocks on the network
+    pub fn report<K: Into<String>, V: Serialize>(&self, key: K, value: V) {
+        self.send(Message::Report(Insight::new(key, value)));
+    }
+
+    /// Publish all buffered insights, blocking until the request completes
+    pub fn flush(&self) -> Result<(), Error> {
+        let (ack, done) = mpsc::channel();
+        self.send(Message::Flush(ack));
+        match done.recv() {
+            Ok(res) => res,
+            Err(_) => bail!("insights reporter background thread has stopped"),
+        }
+    }
+
+    /// Take any errors that occurred while flushing in the background
+    pub fn take_errors(&self) -> Vec<Error> {
+        mem::take(&mut *self.errors.lock().unwrap())
+    }
+
+    fn send(&self, msg: Message) {
+        if let Some(sender) = &self.sender {
+            // Failure means the worker is gone, which `flush` reports
+            let _ = sender.send(msg);
+        }
+    }
+}
+
+impl Drop for InsightsReporter {
+    fn drop(&mut self) {
+        // Disconnecting the channel causes the worker to flush and exit
+        self.sender.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insight_encoding() {
+        let json = serde_json::to_value(&[Insight::new("latency_ms", 42)]).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{ "insight_key": "latency_ms", "insight_value": 42 }])
+        );
+    }
+
+    #[test]
+    fn test_empty_flush_makes_no_request() {
+        let client = Algorithmia::client_with_url("", "http://127.0.0.1:1").unwrap();
+        let reporter = InsightsReporter::new(&client);
+        assert!(reporter.flush().is_ok());
+        assert!(reporter.take_errors().is_empty());
+    }
+}
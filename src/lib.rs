@@ -20,41 +20,75 @@
 #![doc(html_logo_url = "https://algorithmia.com/assets/images/logos/png/bintreePurple.png")]
 #![doc(test(attr(allow(unused_variables), allow(dead_code))))]
 #![allow(unknown_lints)]
+#![allow(clippy::manual_non_exhaustive)]
 #![recursion_limit = "1024"]
 
+use crate::account::{Org, User, UserInfo};
 use crate::algo::{AlgoUri, Algorithm};
 use crate::client::HttpClient;
-use crate::data::{DataDir, DataFile, DataObject, HasDataPath};
+use crate::data::{BackgroundWriter, DataDir, DataFile, DataObject, HasDataPath};
+
+// Allows derive macros to refer to `::algorithmia` within this crate's tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as algorithmia;
 
 #[macro_use]
 pub mod error;
+pub mod account;
 pub mod algo;
+#[cfg(feature = "async")]
+pub mod r#async;
+#[cfg(feature = "catalog")]
+pub mod catalog;
 pub mod data;
+pub mod insights;
 
 #[cfg(feature = "handler")]
 pub mod handler;
 
-use crate::client::ApiAuth;
-use crate::error::Error;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use crate::builder::ClientBuilder;
+use crate::error::{Error, ResultExt};
+pub use crate::health::PingStatus;
+pub use crate::hooks::{RequestEvent, ResponseEvent};
+pub use crate::paging::Paginated;
+pub use crate::retry::RetryBudget;
+pub use crate::stats::TransferStats;
 pub use reqwest::Body;
+pub use reqwest::RedirectPolicy;
+pub use reqwest::{Certificate, Proxy};
 pub use reqwest::{IntoUrl, Url};
 
 /// Reexports of the most common types and traits
 pub mod prelude {
-    pub use crate::algo::AlgoIo;
-    pub use crate::data::HasDataPath;
+    pub use crate::algo::{AlgoIo, AlgoResponse, AlgoUri, ByteVec};
+    pub use crate::data::{DataAcl, HasDataPath, ReadAcl};
+    pub use crate::error::{ApiError, Error};
     pub use crate::Algorithmia;
     pub use serde_json::Value;
 
     #[cfg(feature = "handler")]
     pub use crate::handler;
+    #[cfg(all(feature = "handler", feature = "derive"))]
+    pub use crate::handler::entrypoint;
 }
 
+mod builder;
 mod client;
+#[cfg(feature = "handler")]
+mod env_config;
+mod health;
+mod hooks;
+mod paging;
+mod profile;
+mod retry;
+mod stats;
+#[cfg(feature = "async")]
+mod unblock;
 mod version;
 
-const DEFAULT_API_BASE_URL: &'static str = "https://api.algorithmia.com";
-
 /// The top-level struct for instantiating Algorithmia client endpoints
 pub struct Algorithmia {
     http_client: HttpClient,
@@ -64,17 +98,11 @@ impl Algorithmia {
     /// Instantiate a new client
     ///
     /// The Algorithmia client uses environment variables
-    ///   `ALGORITHMIA_API` to override the default base URL of the API
-    ///   and `ALGORITHMIA_API_KEY` to optionally the API key.
+    ///   `ALGORITHMIA_API` to override the default base URL of the API,
+    ///   `ALGORITHMIA_API_KEY` to optionally the API key,
+    ///   and `ALGORITHMIA_CA_CERT` to optionally trust the root certificates in a PEM file.
     pub fn new() -> Result<Algorithmia, Error> {
-        let api_address =
-            std::env::var("ALGORITHMIA_API").unwrap_or_else(|_| DEFAULT_API_BASE_URL.into());
-        let auth = std::env::var("ALGORITHMIA_API_KEY")
-            .map(ApiAuth::from)
-            .unwrap_or(ApiAuth::None);
-        Ok(Algorithmia {
-            http_client: HttpClient::new(auth, &api_address)?,
-        })
+        Algorithmia::builder().build()
     }
 
     /// Instantiate a new client
@@ -89,21 +117,119 @@ impl Algorithmia {
     /// let client = Algorithmia::client("simUseYourApiKey");
     /// ```
     pub fn client<A: Into<String>>(api_key: A) -> Result<Algorithmia, Error> {
-        let api_address =
-            std::env::var("ALGORITHMIA_API").unwrap_or_else(|_| DEFAULT_API_BASE_URL.into());
-        Ok(Algorithmia {
-            http_client: HttpClient::new(ApiAuth::from(api_key.into()), &api_address)?,
-        })
+        Algorithmia::builder().api_key(api_key).build()
     }
 
     /// Instantiate a new client against alternate API servers
+    ///
+    /// `base_url` must use `https://` unless it points at a loopback address for local testing.
+    /// Use [`builder`](#method.builder) with
+    /// [`allow_insecure_http`](struct.ClientBuilder.html#method.allow_insecure_http)
+    /// to connect over plaintext HTTP to other hosts.
     pub fn client_with_url<A: Into<String>, U: IntoUrl>(
         api_key: A,
         base_url: U,
     ) -> Result<Algorithmia, Error> {
-        Ok(Algorithmia {
-            http_client: HttpClient::new(ApiAuth::from(api_key.into()), base_url)?,
-        })
+        let base_url = base_url.into_url().context("Invalid base URL")?;
+        Algorithmia::builder()
+            .api_key(api_key)
+            .base_url(base_url.as_str())
+            .build()
+    }
+
+    /// Instantiate a new client that sends requests with an existing `reqwest::Client`
+    ///
+    /// For applications that already configure a `reqwest` client (proxies, TLS, timeouts)
+    /// and want API calls to use the same settings and connection pool.
+    /// The client must be from the same major version of `reqwest` as this crate.
+    /// An empty API key results in unauthenticated requests.
+    ///
+    /// # Examples
+    /// ```
+    /// use algorithmia::Algorithmia;
+    /// use std::time::Duration;
+    ///
+    /// let http_client = reqwest::Client::builder()
+    ///     .timeout(Duration::from_secs(120))
+    ///     .build()?;
+    /// let client = Algorithmia::with_http_client(
+    ///     http_client,
+    ///     "simUseYourApiKey",
+    ///     "https://api.algorithmia.com",
+    /// )?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn with_http_client<A: Into<String>, U: IntoUrl>(
+        http_client: reqwest::Client,
+        api_key: A,
+        base_url: U,
+    ) -> Result<Algorithmia, Error> {
+        let base_url = base_url.into_url().context("Invalid base URL")?;
+        Algorithmia::builder()
+            .api_key(api_key)
+            .base_url(base_url.as_str())
+            .http_client(http_client)
+            .build()
+    }
+
+    /// Instantiate a new client from a named profile in the Algorithmia config file
+    ///
+    /// Profiles are read from `~/.algorithmia/config` (shared with the Algorithmia CLI),
+    ///   or the path in the `ALGORITHMIA_CONFIG` environment variable.
+    ///   Each profile may set an `api_key` and an `api_server` base URL:
+    ///
+    /// ```toml
+    /// [profiles.default]
+    /// api_key = "simUseYourApiKey"
+    /// ```
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use algorithmia::Algorithmia;
+    /// let client = Algorithmia::from_profile("default")?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn from_profile(name: &str) -> Result<Algorithmia, Error> {
+        Algorithmia::builder().profile(name).build()
+    }
+
+    /// Configure a new client with additional options
+    ///
+    /// See [`ClientBuilder`](struct.ClientBuilder.html) for the available options.
+    ///
+    /// # Examples
+    /// ```
+    /// use algorithmia::Algorithmia;
+    /// let client = Algorithmia::builder()
+    ///     .api_key("simUseYourApiKey")
+    ///     .default_header("x-gateway-token", "abc123")
+    ///     .build()?;
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Check connectivity to the API and whether the API key is valid
+    ///
+    /// Makes a single cheap authenticated request. An invalid or missing API key is
+    /// reported as `key_valid: false` rather than an error, while network failures
+    /// and unexpected API errors are returned as errors, which makes it useful
+    /// to fail fast at startup.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let status = client.ping()?;
+    /// if !status.key_valid {
+    ///     panic!("Invalid ALGORITHMIA_API_KEY");
+    /// }
+    /// println!("API latency: {:?}", status.latency);
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn ping(&self) -> Result<PingStatus, Error> {
+        crate::health::ping(&self.http_client)
     }
 
     /// Instantiate an [`Algorithm`](algo/algorithm.struct.html) from this client
@@ -121,6 +247,51 @@ impl Algorithmia {
         Algorithm::new(self.http_client.clone(), algorithm.into())
     }
 
+    /// Get the profile of the user that owns this client's API key
+    ///
+    /// Useful for building paths from the caller's username, e.g. when running
+    /// outside of the platform.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let me = client.me()?;
+    /// let home = client.user(&me.username).data_home();
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn me(&self) -> Result<UserInfo, Error> {
+        account::user_info(&self.http_client, account::CURRENT_USER)
+    }
+
+    /// Instantiate a [`User`](account/struct.User.html) from this client
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let anowell = client.user("anowell");
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn user(&self, username: &str) -> User {
+        User::new(self.http_client.clone(), username)
+    }
+
+    /// Instantiate an [`Org`](account/struct.Org.html) from this client
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let team = client.org("ml-team");
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn org(&self, name: &str) -> Org {
+        Org::new(self.http_client.clone(), name)
+    }
+
     /// Instantiate a `DataDirectory` from this client
     ///
     /// # Examples
@@ -164,6 +335,24 @@ impl Algorithmia {
     pub fn data(&self, path: &str) -> DataObject {
         DataObject::new(self.http_client.clone(), path)
     }
+
+    /// Instantiate a `BackgroundWriter` that uploads data files from `workers` background threads
+    ///
+    /// See [`BackgroundWriter`](data/struct.BackgroundWriter.html) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use algorithmia::Algorithmia;
+    /// let client = Algorithmia::client("111112222233333444445555566")?;
+    /// let writer = client.background_writer(4);
+    /// writer.put(".my/logs/event.json", br#"{"event": "started"}"#.to_vec());
+    /// assert!(writer.join().is_empty());
+    /// # Ok::<(), Box<std::error::Error>>(())
+    /// ```
+    pub fn background_writer(&self, workers: usize) -> BackgroundWriter {
+        BackgroundWriter::new(self.http_client.clone(), workers)
+    }
 }
 
 /// Allow cloning in order to reuse http client (and API key) for multiple connections
@@ -0,0 +1,110 @@
+//! Lazy iteration over marker-paginated API listings
+
+use crate::client::HttpClient;
+use crate::error::{process_http_response, Error, ResultExt};
+
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::vec::IntoIter;
+
+/// A single page of results from a paginated API listing
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    results: Vec<T>,
+    marker: Option<String>,
+}
+
+/// Iterator over a marker-paginated API listing
+///
+/// Pages are only requested once all previously fetched items have been consumed,
+/// so iteration can be stopped early without fetching every page.
+pub struct Paginated<T> {
+    client: HttpClient,
+    url: Option<Url>,
+    description: String,
+    err: Option<Error>,
+    items: IntoIter<T>,
+    marker: Option<String>,
+    query_count: u32,
+}
+
+impl<T> Paginated<T> {
+    /// Any error constructing the listing URL is deferred until the first call to `next`
+    pub(crate) fn new<D: Into<String>>(
+        client: HttpClient,
+        url: Result<Url, Error>,
+        description: D,
+    ) -> Self {
+        let (url, err) = match url {
+            Ok(url) => (Some(url), None),
+            Err(err) => (None, Some(err)),
+        };
+        Paginated {
+            client,
+            url,
+            description: description.into(),
+            err,
+            items: Vec::new().into_iter(),
+            marker: None,
+            query_count: 0,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Paginated<T> {
+    fn get_page(&self) -> Result<Page<T>, Error> {
+        let mut url = match self.url.clone() {
+            Some(url) => url,
+            None => bail!("missing URL listing {}", self.description),
+        };
+        if let Some(ref m) = self.marker {
+            url.query_pairs_mut().append_pair("marker", m);
+        }
+
+        let mut res = self
+            .client
+            .send(self.client.get(url))
+            .with_context(|| format!("request error listing {}", self.description))
+            .and_then(process_http_response)
+            .with_context(|| format!("response error listing {}", self.description))?;
+
+        res.json()
+            .with_context(|| format!("JSON decoding error listing {}", self.description))
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for Paginated<T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.err.take() {
+            self.query_count += 1;
+            return Some(Err(err));
+        }
+
+        match self.items.next() {
+            Some(item) => Some(Ok(item)),
+            None => {
+                // Query if there is another page of results
+                if self.query_count == 0 || self.marker.is_some() {
+                    self.query_count += 1;
+                    match self.get_page() {
+                        Ok(page) => {
+                            self.items = page.results.into_iter();
+                            self.marker = page.marker;
+                            self.next()
+                        }
+                        Err(err) => {
+                            // Avoid re-requesting the same page forever
+                            self.marker = None;
+                            Some(Err(err))
+                        }
+                    }
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
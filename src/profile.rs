@@ -0,0 +1,91 @@
+//! Named client profiles stored in the Algorithmia config file
+//!
+//! The config file is shared with the Algorithmia CLI and defaults to `~/.algorithmia/config`:
+//!
+//! ```toml
+//! [profiles.default]
+//! api_key = "simUseYourApiKey"
+//!
+//! [profiles.enterprise]
+//! api_key = "simAnotherApiKey"
+//! api_server = "https://api.enterprise.example.com"
+//! ```
+
+use crate::error::{Error, ResultExt};
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Default, Deserialize)]
+struct Config {
+    #[serde(default)]
+    profiles: HashMap<String, Profile>,
+}
+
+/// A single named profile
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Profile {
+    pub api_key: Option<String>,
+    pub api_server: Option<String>,
+}
+
+/// Path of the config file, overridable with `ALGORITHMIA_CONFIG`
+fn config_path() -> Result<PathBuf, Error> {
+    if let Ok(path) = std::env::var("ALGORITHMIA_CONFIG") {
+        return Ok(PathBuf::from(path));
+    }
+    match std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+        Some(home) => Ok(PathBuf::from(home).join(".algorithmia").join("config")),
+        None => bail!("unable to locate home directory for Algorithmia config"),
+    }
+}
+
+fn parse_profile(config: &str, name: &str) -> Result<Profile, Error> {
+    let mut config: Config = toml::from_str(config).context("invalid Algorithmia config")?;
+    match config.profiles.remove(name) {
+        Some(profile) => Ok(profile),
+        None => bail!("profile '{}' not found in Algorithmia config", name),
+    }
+}
+
+/// Load a named profile from the config file
+pub(crate) fn load_profile(name: &str) -> Result<Profile, Error> {
+    let path = config_path()?;
+    let config = fs::read_to_string(&path)
+        .with_context(|| format!("reading Algorithmia config '{}'", path.display()))?;
+    parse_profile(&config, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        [profiles.default]
+        api_key = "simDefault"
+
+        [profiles.enterprise]
+        api_key = "simEnterprise"
+        api_server = "https://api.enterprise.example.com"
+    "#;
+
+    #[test]
+    fn test_parse_profile() {
+        let profile = parse_profile(CONFIG, "enterprise").unwrap();
+        assert_eq!(profile.api_key.unwrap(), "simEnterprise");
+        assert_eq!(
+            profile.api_server.unwrap(),
+            "https://api.enterprise.example.com"
+        );
+
+        let profile = parse_profile(CONFIG, "default").unwrap();
+        assert!(profile.api_server.is_none());
+    }
+
+    #[test]
+    fn test_missing_profile() {
+        assert!(parse_profile(CONFIG, "staging").is_err());
+    }
+}
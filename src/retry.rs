@@ -0,0 +1,135 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Client-wide limit on retries, replenished by successful requests
+///
+/// Retrying a failed request is cheap for one caller, but during a widespread outage every
+/// caller retrying multiplies the load on a struggling service. A retry budget is a token
+/// bucket shared by every request made through a client (and its clones): each retry
+/// spends a token, and each successful request earns back a fraction of one. While the
+/// bucket is empty, failures are returned without retrying.
+///
+/// The default budget allows a burst of 10 retries, and sustains retries of up to
+/// 10% of successful requests. Share one budget between several clients by passing
+/// clones of it to each [`ClientBuilder::retry_budget`](struct.ClientBuilder.html#method.retry_budget).
+///
+/// # Examples
+///
+/// ```
+/// use algorithmia::{Algorithmia, RetryBudget};
+///
+/// let budget = RetryBudget::new(50, 0.2);
+/// let client = Algorithmia::builder()
+///     .api_key("111112222233333444445555566")
+///     .retry_budget(budget.clone())
+///     .build()?;
+/// assert_eq!(budget.available(), 50);
+/// # Ok::<(), Box<std::error::Error>>(())
+/// ```
+#[derive(Clone)]
+pub struct RetryBudget {
+    bucket: Option<Arc<Mutex<Bucket>>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    max_tokens: f64,
+    refill_per_success: f64,
+}
+
+impl RetryBudget {
+    /// Allow bursts of up to `max_retries`, earning `refill_per_success` retries per success
+    ///
+    /// The budget starts full.
+    pub fn new(max_retries: u32, refill_per_success: f64) -> RetryBudget {
+        let max_tokens = f64::from(max_retries);
+        RetryBudget {
+            bucket: Some(Arc::new(Mutex::new(Bucket {
+                tokens: max_tokens,
+                max_tokens,
+                refill_per_success: refill_per_success.max(0.0),
+            }))),
+        }
+    }
+
+    /// Never limit retries
+    pub fn unlimited() -> RetryBudget {
+        RetryBudget { bucket: None }
+    }
+
+    /// Number of retries currently allowed, or `u32::MAX` if unlimited
+    pub fn available(&self) -> u32 {
+        match &self.bucket {
+            Some(bucket) => bucket.lock().unwrap().tokens as u32,
+            None => u32::MAX,
+        }
+    }
+
+    /// Spend a token if one is available, returning whether a retry is allowed
+    pub(crate) fn try_retry(&self) -> bool {
+        match &self.bucket {
+            Some(bucket) => {
+                let mut bucket = bucket.lock().unwrap();
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    true
+                } else {
+                    false
+                }
+            }
+            None => true,
+        }
+    }
+
+    /// Earn back part of a token for a successful request
+    pub(crate) fn record_success(&self) {
+        if let Some(bucket) = &self.bucket {
+            let mut bucket = bucket.lock().unwrap();
+            bucket.tokens = (bucket.tokens + bucket.refill_per_success).min(bucket.max_tokens);
+        }
+    }
+}
+
+impl Default for RetryBudget {
+    fn default() -> RetryBudget {
+        RetryBudget::new(10, 0.1)
+    }
+}
+
+impl fmt::Debug for RetryBudget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.bucket {
+            Some(_) => write!(f, "RetryBudget {{ available: {} }}", self.available()),
+            None => f.write_str("RetryBudget::unlimited()"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_budget() {
+        let budget = RetryBudget::new(2, 0.5);
+        let shared = budget.clone();
+        assert!(budget.try_retry());
+        assert!(shared.try_retry());
+        assert!(!budget.try_retry());
+
+        // Two successes earn back a single retry
+        budget.record_success();
+        assert!(!shared.try_retry());
+        budget.record_success();
+        assert!(shared.try_retry());
+
+        // Successes never fill the budget beyond its maximum
+        for _ in 0..10 {
+            budget.record_success();
+        }
+        assert_eq!(budget.available(), 2);
+
+        let unlimited = RetryBudget::unlimited();
+        assert!((0..100).all(|_| unlimited.try_retry()));
+    }
+}
@@ -0,0 +1,35 @@
+//! Lightweight statistics about individual API calls
+
+use std::time::Duration;
+
+/// Byte counts and timing of a single API call
+///
+/// Available from [`AlgoResponse::stats`](algo/struct.AlgoResponse.html#method.stats)
+/// and [`FileData::stats`](data/struct.FileData.html#method.stats).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferStats {
+    /// Bytes sent in the request body, if the length was known
+    pub request_bytes: Option<u64>,
+    /// Bytes of the response body read so far
+    pub response_bytes: u64,
+    /// Wall time from sending the request until the response body was fully read
+    /// (or until now, if the body hasn't been fully read yet)
+    pub elapsed: Duration,
+    // Placeholder for API stability if additional fields are added later
+    _dummy: (),
+}
+
+impl TransferStats {
+    pub(crate) fn new(
+        request_bytes: Option<u64>,
+        response_bytes: u64,
+        elapsed: Duration,
+    ) -> TransferStats {
+        TransferStats {
+            request_bytes,
+            response_bytes,
+            elapsed,
+            _dummy: (),
+        }
+    }
+}
@@ -0,0 +1,455 @@
+//! Fake Algorithmia API server for integration tests [feature = "testing"]
+//!
+//! # Examples
+//!
+//! ```
+//! use algorithmia::testing::FakeServer;
+//! use serde_json::json;
+//!
+//! let server = FakeServer::start()?;
+//! server.algo_result("anowell/Pinky/0.1", json!({"plan": "take over the world"}));
+//!
+//! let client = server.client()?;
+//! let output: serde_json::Value = client.algo("anowell/Pinky/0.1").pipe("Brain")?.decode()?;
+//! assert_eq!(output["plan"], "take over the world");
+//! assert_eq!(server.requests()[0].body_string(), r#""Brain""#);
+//! # Ok::<(), Box<std::error::Error>>(())
+//! ```
+
+use crate::data::parse_data_uri;
+use crate::error::Error;
+use crate::Algorithmia;
+
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Canned response returned by a `FakeServer` route
+#[derive(Debug, Clone)]
+pub struct FakeResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    delay: Option<Duration>,
+}
+
+impl FakeResponse {
+    /// Response with the given status code and an empty body
+    pub fn status(status: u16) -> FakeResponse {
+        FakeResponse {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+            delay: None,
+        }
+    }
+
+    /// Response with a JSON body
+    pub fn json(status: u16, body: &Value) -> FakeResponse {
+        FakeResponse::status(status)
+            .with_header("Content-Type", "application/json")
+            .with_body(body.to_string())
+    }
+
+    /// Add a response header
+    pub fn with_header(mut self, name: &str, value: &str) -> FakeResponse {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Set the response body
+    pub fn with_body<B: Into<Vec<u8>>>(mut self, body: B) -> FakeResponse {
+        self.body = body.into();
+        self
+    }
+
+    /// Wait before responding, e.g. to keep a request in flight while others are sent
+    ///
+    /// The server handles one request at a time, so other requests wait too.
+    pub fn with_delay(mut self, delay: Duration) -> FakeResponse {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+/// Request received by a `FakeServer`
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    /// HTTP method, e.g. `POST`
+    pub method: String,
+    /// Request path and query string, e.g. `/v1/algo/anowell/Pinky?timeout=3`
+    pub url: String,
+    /// Request headers as (name, value) pairs
+    pub headers: Vec<(String, String)>,
+    /// Raw request body
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// Get the value of a request header (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Request body decoded lossily as UTF-8
+    pub fn body_string(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+struct Route {
+    method: String,
+    path: String,
+    response: FakeResponse,
+}
+
+#[derive(Default)]
+struct State {
+    routes: Vec<Route>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// Local HTTP server that mimics the Algorithmia API with registered fixtures
+///
+/// Routes match on HTTP method and path (ignoring the query string unless the route includes one),
+/// with later registrations taking precedence. Unmatched requests receive a 404 error response.
+/// The server shuts down when dropped.
+pub struct FakeServer {
+    server: Arc<tiny_http::Server>,
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl FakeServer {
+    /// Start a fake server on a random local port
+    pub fn start() -> Result<FakeServer, Error> {
+        let server = tiny_http::Server::http("127.0.0.1:0")
+            .map_err(|err| Error::from(format!("failed to start fake server: {}", err)))?;
+        let addr = match server.server_addr().to_ip() {
+            Some(addr) => addr,
+            None => bail!("fake server is not listening on an IP address"),
+        };
+        let server = Arc::new(server);
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let worker = {
+            let server = server.clone();
+            let state = state.clone();
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle(&state, request);
+                }
+            })
+        };
+
+        Ok(FakeServer {
+            server,
+            addr,
+            state,
+            worker: Some(worker),
+        })
+    }
+
+    /// Base URL of the fake server, e.g. `http://127.0.0.1:34567`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Instantiate an `Algorithmia` client that sends requests to this server
+    ///
+    /// Connections aren't kept alive between requests: the server may queue a new connection
+    /// behind threads waiting on idle keep-alive connections, stalling concurrent requests.
+    pub fn client(&self) -> Result<Algorithmia, Error> {
+        Algorithmia::builder()
+            .api_key("simFakeServerKey")
+            .base_url(self.url())
+            .max_idle_per_host(0)
+            .build()
+    }
+
+    /// Register a response for a method and path
+    pub fn route(&self, method: &str, path: &str, response: FakeResponse) {
+        self.state.lock().unwrap().routes.push(Route {
+            method: method.to_uppercase(),
+            path: path.to_owned(),
+            response,
+        });
+    }
+
+    /// Register a successful JSON result for calls to an algorithm
+    pub fn algo_result(&self, algo: &str, result: Value) {
+        self.algo_response(
+            algo,
+            FakeResponse::json(
+                200,
+                &json!({
+                    "result": result,
+                    "metadata": { "content_type": "json", "duration": 0.001 }
+                }),
+            ),
+        );
+    }
+
+    /// Register an error for calls to an algorithm
+    pub fn algo_error(&self, algo: &str, error_type: &str, message: &str) {
+        self.algo_response(
+            algo,
+            FakeResponse::json(
+                400,
+                &json!({
+                    "error": { "message": message, "error_type": error_type }
+                }),
+            ),
+        );
+    }
+
+    /// Register a raw response for calls to an algorithm
+    pub fn algo_response(&self, algo: &str, response: FakeResponse) {
+        let algo = algo.trim_start_matches("algo://").trim_start_matches('/');
+        self.route("POST", &format!("/v1/algo/{}", algo), response);
+    }
+
+    /// Register a data file that can be downloaded and checked for existence
+    pub fn data_file<B: Into<Vec<u8>>>(&self, data_uri: &str, contents: B) {
+        let path = data_path(data_uri);
+        let response = FakeResponse::status(200)
+            .with_header("X-Data-Type", "file")
+            .with_body(contents);
+        self.route("HEAD", &path, response.clone().with_body(Vec::new()));
+        self.route("GET", &path, response);
+    }
+
+    /// Register a data directory listing
+    ///
+    /// `listing` is the JSON body returned by the Data API, e.g.
+    /// `{"folders": [{"name": "sub"}], "files": [{"filename": "a.txt", "size": 3, "last_modified": "..."}]}`
+    pub fn data_dir(&self, data_uri: &str, listing: Value) {
+        let path = data_path(data_uri);
+        let response = FakeResponse::json(200, &listing).with_header("X-Data-Type", "directory");
+        self.route("HEAD", &path, response.clone().with_body(Vec::new()));
+        self.route("GET", &path, response);
+    }
+
+    /// All requests received so far, in order
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for FakeServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn data_path(data_uri: &str) -> String {
+    format!("/v1/connector/{}", parse_data_uri(data_uri))
+}
+
+fn handle(state: &Mutex<State>, mut request: tiny_http::Request) {
+    let mut body = Vec::new();
+    let _ = request.as_reader().read_to_end(&mut body);
+    let recorded = RecordedRequest {
+        method: request.method().as_str().to_uppercase(),
+        url: request.url().to_owned(),
+        headers: request
+            .headers()
+            .iter()
+            .map(|h| (h.field.as_str().to_string(), h.value.as_str().to_owned()))
+            .collect(),
+        body,
+    };
+
+    let response = {
+        let mut state = state.lock().unwrap();
+        let path = recorded.url.split('?').next().unwrap_or("");
+        let response = state
+            .routes
+            .iter()
+            .rev()
+            .find(|r| {
+                r.method == recorded.method
+                    && (r.path == path || (r.path.contains('?') && r.path == recorded.url))
+            })
+            .map(|r| r.response.clone())
+            .unwrap_or_else(|| {
+                FakeResponse::json(
+                    404,
+                    &json!({
+                        "error": { "message": format!("no fake route for {} {}", recorded.method, path) }
+                    }),
+                )
+            });
+        state.requests.push(recorded);
+        response
+    };
+
+    if let Some(delay) = response.delay {
+        thread::sleep(delay);
+    }
+    let mut http_response =
+        tiny_http::Response::from_data(response.body).with_status_code(response.status);
+    for (name, value) in &response.headers {
+        if let Ok(header) = tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()) {
+            http_response.add_header(header);
+        }
+    }
+    let _ = request.respond(http_response);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::HasDataPath;
+
+    #[test]
+    fn test_algo_error() {
+        let server = FakeServer::start().unwrap();
+        server.algo_error("anowell/Pinky", "AlgorithmError", "Narf!");
+
+        let client = server.client().unwrap();
+        let err = client.algo("anowell/Pinky").pipe("Brain").err().unwrap();
+        assert_eq!(err.api_error().unwrap().message, "Narf!");
+        assert_eq!(err.code(), Some(crate::error::ErrorCode::AlgorithmError));
+        assert_eq!(err.status().map(|s| s.as_u16()), Some(400));
+        assert_eq!(
+            server.requests()[0].header("Authorization"),
+            Some("Simple simFakeServerKey")
+        );
+    }
+
+    #[test]
+    fn test_algo_timeout() {
+        let server = FakeServer::start().unwrap();
+        server.algo_result("anowell/Pinky", json!("Narf!"));
+
+        let client = server.client().unwrap();
+        let mut algo = client.algo("anowell/Pinky");
+        algo.stdout(true)
+            .timeout(std::time::Duration::from_millis(1500))
+            .pipe("Brain")
+            .unwrap();
+        assert_eq!(
+            server.requests()[0].url,
+            "/v1/algo/anowell/Pinky?stdout=true&timeout=2"
+        );
+
+        let err = algo
+            .timeout(std::time::Duration::from_secs(0))
+            .pipe("Brain")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "algorithm timeout must be greater than zero"
+        );
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_non_json_error_body() {
+        let server = FakeServer::start().unwrap();
+        server.algo_response(
+            "anowell/Pinky",
+            FakeResponse::status(502)
+                .with_header("X-Request-Id", "req-123")
+                .with_body("<html>Bad Gateway</html>"),
+        );
+
+        let client = server.client().unwrap();
+        let err = client.algo("anowell/Pinky").pipe("Brain").err().unwrap();
+        assert_eq!(err.body_snippet(), Some("<html>Bad Gateway</html>"));
+        assert_eq!(err.request_id(), Some("req-123"));
+    }
+
+    #[test]
+    fn test_data_file() {
+        let server = FakeServer::start().unwrap();
+        server.data_file("data://.my/foo/bar.txt", "hello");
+
+        let client = server.client().unwrap();
+        let file = client.file("data://.my/foo/bar.txt");
+        assert!(file.exists().unwrap());
+        assert_eq!(file.get().unwrap().into_string().unwrap(), "hello");
+        assert!(!client.file("data://.my/foo/missing.txt").exists().unwrap());
+    }
+
+    #[test]
+    fn test_transfer_stats() {
+        let server = FakeServer::start().unwrap();
+        server.algo_result("anowell/Pinky", json!("Narf!"));
+        server.data_file("data://.my/foo/bar.txt", "hello");
+
+        let client = server.client().unwrap();
+        let res = client.algo("anowell/Pinky").pipe("Brain").unwrap();
+        let stats = res.stats().unwrap();
+        let request_len = server.requests()[0].body.len() as u64;
+        assert_eq!(stats.request_bytes, Some(request_len));
+        assert!(stats.response_bytes > 0);
+
+        let mut data = client.file("data://.my/foo/bar.txt").get().unwrap();
+        assert_eq!(data.stats().response_bytes, 0);
+        std::io::Read::read_to_end(&mut data, &mut Vec::new()).unwrap();
+        let stats = data.stats();
+        assert_eq!(stats.response_bytes, 5);
+        assert_eq!(data.stats().elapsed, stats.elapsed);
+    }
+
+    #[test]
+    fn test_redirect_policy() {
+        let server = FakeServer::start().unwrap();
+        let location = format!("{}/v1/algo/anowell/Pinky/0.2", server.url());
+        server.algo_response(
+            "anowell/Pinky",
+            FakeResponse::status(307).with_header("Location", &location),
+        );
+        server.algo_result("anowell/Pinky/0.2", json!("Narf!"));
+
+        let client = server.client().unwrap();
+        let res = client.algo("anowell/Pinky").pipe("Brain").unwrap();
+        assert_eq!(res.decode::<String>().unwrap(), "Narf!");
+        let requests = server.requests();
+        assert_eq!(requests[1].url, "/v1/algo/anowell/Pinky/0.2");
+        assert_eq!(
+            requests[1].header("Authorization"),
+            Some("Simple simFakeServerKey")
+        );
+
+        let client = Algorithmia::builder()
+            .api_key("simFakeServerKey")
+            .base_url(server.url())
+            .redirect_policy(crate::RedirectPolicy::none())
+            .build()
+            .unwrap();
+        let err = client.algo("anowell/Pinky").pipe("Brain").err().unwrap();
+        assert_eq!(err.status().map(|s| s.as_u16()), Some(307));
+    }
+
+    #[test]
+    fn test_data_dir() {
+        let server = FakeServer::start().unwrap();
+        server.data_dir(
+            "data://.my/foo",
+            json!({
+                "acl": { "read": [] },
+                "folders": [{ "name": "sub" }],
+                "files": [{ "filename": "a.txt", "size": 3, "last_modified": "2015-03-14T08:00:00Z" }]
+            }),
+        );
+
+        let client = server.client().unwrap();
+        let info = client.dir("data://.my/foo").show().unwrap();
+        assert_eq!(info.acl.unwrap().read, Vec::<String>::new());
+        assert_eq!(info.entries.len(), 2);
+        assert!(info.marker.is_none());
+    }
+}
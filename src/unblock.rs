@@ -0,0 +1,158 @@
+//! Runtime-agnostic helper for running blocking client calls from async code
+
+use crate::error::Error;
+use futures::channel::oneshot;
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+/// Maximum number of threads running blocking operations at once
+const MAX_THREADS: usize = 256;
+/// Idle threads exit after this long without work
+const IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Threads shared by every blocking operation run from async code
+///
+/// Threads are started on demand, reused while there is work, and stop when idle,
+/// so fanning out many calls doesn't start a thread per call.
+struct Pool {
+    state: Mutex<PoolState>,
+    available: Condvar,
+}
+
+#[derive(Default)]
+struct PoolState {
+    jobs: VecDeque<Job>,
+    threads: usize,
+    idle: usize,
+}
+
+fn pool() -> &'static Pool {
+    static POOL: OnceLock<Pool> = OnceLock::new();
+    POOL.get_or_init(|| Pool {
+        state: Mutex::new(PoolState::default()),
+        available: Condvar::new(),
+    })
+}
+
+impl Pool {
+    fn execute(&'static self, job: Job) {
+        let mut state = self.state.lock().unwrap();
+        state.jobs.push_back(job);
+        if state.idle > 0 {
+            self.available.notify_one();
+        }
+        // Idle threads only stop counting as idle once they wake, so a burst of jobs
+        // queued before then needs a thread for each job beyond those idle threads
+        if state.jobs.len() > state.idle && state.threads < MAX_THREADS {
+            state.threads += 1;
+            thread::spawn(move || self.work());
+        }
+    }
+
+    fn work(&self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(job) = state.jobs.pop_front() {
+                drop(state);
+                // A panicking job drops its result sender, which its caller reports as an error
+                let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                state = self.state.lock().unwrap();
+                continue;
+            }
+            state.idle += 1;
+            let (guard, wait) = self.available.wait_timeout(state, IDLE_TIMEOUT).unwrap();
+            state = guard;
+            state.idle -= 1;
+            if wait.timed_out() && state.jobs.is_empty() {
+                state.threads -= 1;
+                return;
+            }
+        }
+    }
+}
+
+/// Run a blocking operation on a pooled thread, resolving once it completes
+///
+/// This avoids blocking the executor without depending on any particular async runtime.
+pub(crate) async fn unblock<T, F>(f: F) -> Result<T, Error>
+where
+    F: FnOnce() -> Result<T, Error> + Send + 'static,
+    T: Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    pool().execute(Box::new(move || {
+        let _ = tx.send(f());
+    }));
+    match rx.await {
+        Ok(res) => res,
+        Err(_) => bail!("background thread for blocking operation panicked"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::join_all;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    #[test]
+    fn test_unblock() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let calls = (0..20).map(|i| {
+            let running = running.clone();
+            let max_running = max_running.clone();
+            unblock(move || {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(now, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(20));
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok(i)
+            })
+        });
+        let results = futures::executor::block_on(join_all(calls));
+        let values: Vec<_> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(values, (0..20).collect::<Vec<_>>());
+        // Calls ran concurrently rather than one at a time
+        assert!(max_running.load(Ordering::SeqCst) > 1);
+
+        let err = futures::executor::block_on(unblock(|| -> Result<(), Error> { panic!() }));
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_unblock_burst_on_warm_pool() {
+        const BURST: usize = 32;
+
+        // Leave an idle thread in the pool
+        futures::executor::block_on(unblock(|| Ok(()))).unwrap();
+
+        // Every call waits for the whole burst to start, which only happens if they run concurrently
+        let arrived = Arc::new(AtomicUsize::new(0));
+        let gave_up = Arc::new(AtomicBool::new(false));
+        let calls = (0..BURST).map(|_| {
+            let (arrived, gave_up) = (arrived.clone(), gave_up.clone());
+            unblock(move || {
+                arrived.fetch_add(1, Ordering::SeqCst);
+                let deadline = Instant::now() + Duration::from_secs(5);
+                while arrived.load(Ordering::SeqCst) < BURST {
+                    if gave_up.load(Ordering::SeqCst) || Instant::now() > deadline {
+                        gave_up.store(true, Ordering::SeqCst);
+                        return Ok(false);
+                    }
+                    thread::sleep(Duration::from_millis(1));
+                }
+                Ok(true)
+            })
+        });
+        let results = futures::executor::block_on(join_all(calls));
+        assert!(results.into_iter().all(|res| res.unwrap()));
+    }
+}